@@ -0,0 +1,80 @@
+// Counts heap allocations made while listing every key's subkey and value names, comparing the
+// owned `subkeys`/`values` iterators with the borrowed accessors of an in-memory hive.
+//
+// Usage: cargo bench --bench borrowed_names -- <hive file>
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs,
+    io::Cursor,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+use hivedigger::Hive;
+
+// Allocator that counts allocations on top of the system allocator
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    // cargo passes `--bench` to benchmarks without the libtest harness
+    let Some(hive_path) = std::env::args().skip(1).find(|arg| !arg.starts_with("--")) else {
+        println!("Usage: cargo bench --bench borrowed_names -- <hive file>");
+        return;
+    };
+    let bytes = fs::read(&hive_path).expect("Failed to read hive");
+    let mut hive = Hive::from_reader(Cursor::new(bytes)).expect("Failed to open hive");
+    let key_nodes: Vec<_> = hive.walk().filter_map(|entry| entry.ok()).map(|(_, key_node)| key_node).collect();
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let mut name_bytes = 0;
+    for key_node in &key_nodes {
+        for (subkey_name, _) in hive.subkeys(key_node).filter_map(Result::ok) {
+            name_bytes += subkey_name.len();
+        }
+        for (value_name, _) in hive.values(key_node).filter_map(Result::ok) {
+            name_bytes += value_name.len();
+        }
+    }
+    println!(
+        "owned:    {} allocations, {:?}, {} name bytes",
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        start.elapsed(),
+        name_bytes
+    );
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let mut name_bytes = 0;
+    for key_node in &key_nodes {
+        for subkey_name in hive.subkey_names_borrowed(key_node).unwrap_or_default() {
+            name_bytes += subkey_name.len();
+        }
+        for value_name in hive.value_names_borrowed(key_node).unwrap_or_default() {
+            name_bytes += value_name.len();
+        }
+    }
+    println!(
+        "borrowed: {} allocations, {:?}, {} name bytes",
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        start.elapsed(),
+        name_bytes
+    );
+}
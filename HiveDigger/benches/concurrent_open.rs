@@ -0,0 +1,87 @@
+// Compares ingesting many hives concurrently through the sync and the async (tokio) API.
+//
+// Usage: cargo bench --features tokio --bench concurrent_open -- <directory of hive files>
+// Each hive is opened and fully walked COPIES times over, once with one OS thread per hive
+// and once with one tokio task per hive on a multi-threaded runtime.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use hivedigger::{AsyncHive, Hive};
+
+const COPIES: usize = 16;
+
+// Function to walk a whole hive, returning the number of keys seen
+fn count_keys<R: std::io::Read + std::io::Seek>(hive: &mut Hive<R>) -> usize {
+    hive.walk().filter(|entry| entry.is_ok()).count()
+}
+
+fn bench_sync(hive_paths: &[PathBuf]) -> (Duration, usize) {
+    let start = Instant::now();
+    let key_count = thread::scope(|scope| {
+        let handles: Vec<_> = hive_paths
+            .iter()
+            .map(|hive_path| {
+                scope.spawn(move || match Hive::open(hive_path) {
+                    Ok(mut hive) => count_keys(&mut hive),
+                    Err(_) => 0,
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+    });
+    (start.elapsed(), key_count)
+}
+
+fn bench_async(hive_paths: &[PathBuf]) -> (Duration, usize) {
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+
+    let start = Instant::now();
+    let key_count = runtime.block_on(async {
+        let tasks: Vec<_> = hive_paths
+            .iter()
+            .cloned()
+            .map(|hive_path| {
+                tokio::spawn(async move {
+                    match AsyncHive::open(&hive_path).await {
+                        Ok(mut hive) => count_keys(&mut hive),
+                        Err(_) => 0,
+                    }
+                })
+            })
+            .collect();
+
+        let mut key_count = 0;
+        for task in tasks {
+            key_count += task.await.unwrap();
+        }
+        key_count
+    });
+    (start.elapsed(), key_count)
+}
+
+fn main() {
+    // cargo passes `--bench` to benchmarks without the libtest harness
+    let Some(hive_dir) = std::env::args().skip(1).find(|arg| !arg.starts_with("--")) else {
+        println!("Usage: cargo bench --features tokio --bench concurrent_open -- <directory of hive files>");
+        return;
+    };
+
+    let hive_files: Vec<PathBuf> = fs::read_dir(Path::new(&hive_dir))
+        .expect("Failed to read hive directory")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|hive_path| hive_path.is_file())
+        .collect();
+    let hive_paths: Vec<PathBuf> = (0..COPIES).flat_map(|_| hive_files.iter().cloned()).collect();
+
+    let (sync_elapsed, sync_keys) = bench_sync(&hive_paths);
+    let (async_elapsed, async_keys) = bench_async(&hive_paths);
+
+    println!("{} hives ({} files x {})", hive_paths.len(), hive_files.len(), COPIES);
+    println!("sync  (thread per hive): {:?}, {} keys", sync_elapsed, sync_keys);
+    println!("async (task per hive):   {:?}, {} keys", async_elapsed, async_keys);
+}
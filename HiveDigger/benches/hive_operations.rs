@@ -0,0 +1,98 @@
+// Criterion benchmarks for the operations most performance work targets, run on hives generated
+// with the test fixture builder so the numbers are comparable between checkouts:
+// - walk: a full depth-first walk of a large hive
+// - open_key: opening a key 64 levels down
+// - subkey lookup: opening one key among 10,000 siblings
+// - export: writing every key and value of the hive as a .reg file
+//
+// Usage: cargo bench --bench hive_operations [-- <filter>]
+// Save a baseline with `-- --save-baseline before` and compare with `-- --baseline before`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::io::{self, Cursor};
+
+use common::{
+    builder::{HiveBuilder, Key, ListKind},
+    fixtures,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hivedigger::{Hive, RegFormat};
+
+const VENDORS: usize = 1_000;
+const LARGE_KEY_SUBKEYS: usize = 10_000;
+const DEEP_KEY_DEPTH: usize = 64;
+
+// Function to build a hive shaped like a large SOFTWARE hive: a thousand vendor keys with a few
+// levels and values each, a class key with 10,000 subkeys behind an index root, and a chain of
+// nested keys
+fn large_hive() -> Vec<u8> {
+    let mut software = Key::new("Software");
+    for vendor in 0..VENDORS {
+        let product = Key::new("Product")
+            .sz("InstallPath", &format!("C:\\Program Files\\Vendor{:04}", vendor))
+            .dword("Version", vendor as u32)
+            .binary("Settings", &[vendor as u8; 64])
+            .subkey(Key::new("Options").dword("Enabled", 1).multi_sz("Plugins", &["a", "b", "c"]));
+        software = software.subkey(Key::new(&format!("Vendor{:04}", vendor)).sz("", "vendor").subkey(product));
+    }
+
+    let mut classes = Key::new("Classes").list(ListKind::Ri);
+    for class in 0..LARGE_KEY_SUBKEYS {
+        classes = classes.subkey(Key::new(&format!(".ext{:05}", class)));
+    }
+
+    let mut deep = Key::new(&format!("Level{}", DEEP_KEY_DEPTH - 1)).sz("Marker", "bottom");
+    for level in (0..DEEP_KEY_DEPTH - 1).rev() {
+        deep = Key::new(&format!("Level{}", level)).subkey(deep);
+    }
+
+    let root = Key::new("ROOT").subkey(software).subkey(classes).subkey(deep);
+    HiveBuilder::new().file_name("SOFTWARE").build(&root)
+}
+
+fn open(bytes: &[u8]) -> Hive<Cursor<&[u8]>> {
+    Hive::from_reader(Cursor::new(bytes)).expect("Failed to open hive")
+}
+
+fn bench_walk(c: &mut Criterion) {
+    let bytes = large_hive();
+    let mut hive = open(&bytes);
+    c.bench_function("walk/large", |b| b.iter(|| hive.walk().filter(Result::is_ok).count()));
+
+    let system = (fixtures::FIXTURES[0].build)();
+    let mut hive = open(&system);
+    c.bench_function("walk/system_fixture", |b| b.iter(|| hive.walk().filter(Result::is_ok).count()));
+}
+
+fn bench_open_key(c: &mut Criterion) {
+    let bytes = large_hive();
+    let mut hive = open(&bytes);
+    let deep_path = (0..DEEP_KEY_DEPTH).map(|level| format!("Level{}", level)).collect::<Vec<_>>().join("\\");
+    hive.open_key(&deep_path).expect("Deep key is missing");
+    c.bench_function("open_key/deep", |b| b.iter(|| hive.open_key(black_box(&deep_path)).unwrap()));
+}
+
+fn bench_subkey_lookup(c: &mut Criterion) {
+    let bytes = large_hive();
+    let mut hive = open(&bytes);
+    let last = format!("Classes\\.ext{:05}", LARGE_KEY_SUBKEYS - 1);
+    c.bench_function("open_key/large_key_last_subkey", |b| b.iter(|| hive.open_key(black_box(&last)).unwrap()));
+    let missing = "Classes\\.missing";
+    c.bench_function("open_key/large_key_missing_subkey", |b| b.iter(|| hive.open_key(black_box(missing)).is_err()));
+}
+
+fn bench_export(c: &mut Criterion) {
+    let bytes = large_hive();
+    let mut hive = open(&bytes);
+    let mut group = c.benchmark_group("export");
+    group.sample_size(20);
+    group.bench_function("reg/large", |b| {
+        b.iter(|| hive.export_reg(io::sink(), "HKEY_LOCAL_MACHINE\\SOFTWARE", RegFormat::Utf16V5).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_walk, bench_open_key, bench_subkey_lookup, bench_export);
+criterion_main!(benches);
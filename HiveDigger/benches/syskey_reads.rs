@@ -0,0 +1,58 @@
+// Counts the reads and seeks extract_syskey makes while descending to Control\Lsa\JD, and times it.
+//
+// Usage: cargo bench --bench syskey_reads -- <SYSTEM hive>
+
+use std::{
+    fs,
+    io::{Cursor, Read, Seek, SeekFrom},
+    time::Instant,
+};
+
+use hivedigger::extract_syskey_from_reader;
+
+const ITERATIONS: u32 = 10_000;
+
+// Reader that counts the calls made through it
+struct CountingReader<R> {
+    inner: R,
+    reads: u64,
+    seeks: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reads += 1;
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.seeks += 1;
+        self.inner.seek(pos)
+    }
+}
+
+fn main() {
+    // cargo passes `--bench` to benchmarks without the libtest harness
+    let Some(hive_path) = std::env::args().skip(1).find(|arg| !arg.starts_with("--")) else {
+        println!("Usage: cargo bench --bench syskey_reads -- <SYSTEM hive>");
+        return;
+    };
+    let bytes = fs::read(&hive_path).expect("Failed to read hive");
+
+    let mut reader = CountingReader {
+        inner: Cursor::new(&bytes),
+        reads: 0,
+        seeks: 0,
+    };
+    let result = extract_syskey_from_reader(&mut reader);
+    println!("result: {:?}", result.map(|syskey| syskey.len()));
+    println!("reads: {}, seeks: {}", reader.reads, reader.seeks);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let _ = extract_syskey_from_reader(&mut Cursor::new(&bytes));
+    }
+    println!("{:?} per extraction", start.elapsed() / ITERATIONS);
+}
@@ -0,0 +1,26 @@
+#![no_main]
+
+use std::io::Cursor;
+
+use hivedigger::{Hive, HiveOptions};
+use libfuzzer_sys::fuzz_target;
+
+// Open arbitrary bytes as a hive, with and without recovery, and read everything reachable
+fuzz_target!(|data: &[u8]| {
+    for recovery in [false, true] {
+        let options = HiveOptions {
+            skip_errors: true,
+            recovery,
+            strict: true,
+            ..HiveOptions::default()
+        };
+        let Ok(mut hive) = Hive::from_reader_with_options(Cursor::new(data), options) else {
+            continue;
+        };
+        let _ = hive.validate();
+        let _ = hive.statistics();
+        for value in hive.all_values() {
+            let _ = value;
+        }
+    }
+});
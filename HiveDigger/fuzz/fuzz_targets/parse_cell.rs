@@ -0,0 +1,48 @@
+#![no_main]
+
+use std::io::Cursor;
+
+use hivedigger::{Cell, Hive, HiveOptions};
+use libfuzzer_sys::fuzz_target;
+
+// The first four bytes pick a cell offset; the rest is opened as a (recovered) hive and the cell
+// there, and any cells it points at, are parsed
+fuzz_target!(|data: &[u8]| {
+    let Some((offset, hive_file)) = data.split_first_chunk::<4>() else {
+        return;
+    };
+    let options = HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    };
+    let Ok(mut hive) = Hive::from_reader_with_options(Cursor::new(hive_file), options) else {
+        return;
+    };
+
+    let offset = u32::from_le_bytes(*offset);
+    let linked = match hive.parse_cell(offset) {
+        Ok(Cell::KeyNode { key_node, .. }) => {
+            let _ = key_node.describe();
+            let _ = hive.class_name(&key_node);
+            for subkey in hive.subkeys(&key_node).take(64) {
+                let _ = subkey;
+            }
+            let values: Vec<_> = hive.values(&key_node).take(64).flatten().collect();
+            for (_, key_value) in values {
+                let _ = hive.read_value(&key_value);
+            }
+            Vec::new()
+        }
+        Ok(Cell::KeyValue { key_value, .. }) => {
+            let _ = hive.read_value(&key_value);
+            Vec::new()
+        }
+        Ok(Cell::SubkeyList { offsets, .. }) => offsets,
+        Ok(Cell::SecurityDescriptor { flink, blink, .. }) => vec![flink, blink],
+        Ok(Cell::BigData { segment_list_offset, .. }) => vec![segment_list_offset],
+        _ => Vec::new(),
+    };
+    for offset in linked.into_iter().take(64) {
+        let _ = hive.parse_cell(offset);
+    }
+});
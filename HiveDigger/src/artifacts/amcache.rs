@@ -0,0 +1,65 @@
+use std::io::{Read, Seek};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::{reg::decode_reg_sz, Hive, HiveError};
+
+const INVENTORY_APPLICATION_FILE_PATH: &str = "Root\\InventoryApplicationFile";
+
+// Format of the LinkDate value, the PE header's link (compile) time in UTC
+const LINK_DATE_FORMAT: &str = "%m/%d/%Y %H:%M:%S";
+
+// Struct representing one InventoryApplicationFile entry of an Amcache.hve hive: an executable
+// Windows has seen on the system, whether or not it ever ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmcacheFile {
+    pub key_name: String,                 // e.g. "notepad.exe|8f1e3c2d5a6b7c9e"
+    pub path: Option<String>,             // LowerCaseLongPath, e.g. "c:\windows\system32\notepad.exe"
+    pub sha1: Option<String>,             // From FileId, which puts "0000" in front of the SHA-1
+    pub link_date: Option<DateTime<Utc>>, // LinkDate, from the PE header
+    pub publisher: Option<String>,        // From the file's version resource, lowercased by Windows
+    pub last_written: DateTime<Utc>,      // When the entry was last updated
+}
+
+// Function to read the InventoryApplicationFile entries of an Amcache.hve hive (Windows 10 and
+// later layout). Missing or malformed values are left as None; a missing
+// InventoryApplicationFile key is an error.
+pub fn parse_inventory_application_file<R: Read + Seek>(amcache: &mut Hive<R>) -> Result<Vec<AmcacheFile>, HiveError> {
+    let inventory = amcache.open_key(INVENTORY_APPLICATION_FILE_PATH)?;
+    let entry_keys = amcache.subkeys(&inventory).collect::<Result<Vec<_>, _>>()?;
+
+    let mut files = Vec::new();
+    for (key_name, entry_key) in entry_keys {
+        let mut string = |name: &str| -> Result<Option<String>, HiveError> {
+            let data = amcache.value_data_named(&entry_key, name)?;
+            Ok(data.and_then(|(_, data)| decode_reg_sz(&data)).filter(|string| !string.is_empty()))
+        };
+        let path = string("LowerCaseLongPath")?;
+        let sha1 = string("FileId")?.and_then(|file_id| file_id_to_sha1(&file_id));
+        let link_date = string("LinkDate")?
+            .and_then(|link_date| NaiveDateTime::parse_from_str(&link_date, LINK_DATE_FORMAT).ok())
+            .map(|link_date| link_date.and_utc());
+        let publisher = string("Publisher")?;
+
+        files.push(AmcacheFile {
+            key_name,
+            path,
+            sha1,
+            link_date,
+            publisher,
+            last_written: entry_key.last_written(),
+        });
+    }
+
+    Ok(files)
+}
+
+// Function to get the SHA-1 from a FileId, which is the hash in hex behind four zeros. Anything
+// else isn't a SHA-1 (files over 31 MB aren't hashed) and gives None.
+fn file_id_to_sha1(file_id: &str) -> Option<String> {
+    let sha1 = file_id.strip_prefix("0000").unwrap_or(file_id);
+    if sha1.len() != 40 || !sha1.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(sha1.to_ascii_lowercase())
+}
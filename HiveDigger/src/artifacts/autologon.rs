@@ -0,0 +1,54 @@
+use std::io::{Read, Seek};
+
+use crate::{reg::decode_reg_sz, Hive, HiveError, KeyNode};
+
+const WINLOGON_PATH: &str = "Microsoft\\Windows NT\\CurrentVersion\\Winlogon";
+
+const REG_DWORD: u32 = 4;
+
+// Struct representing the automatic logon settings of a SOFTWARE hive's Winlogon key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoLogon {
+    pub enabled: bool, // AutoAdminLogon is "1"
+    pub user_name: Option<String>,
+    pub domain_name: Option<String>,
+    // DefaultPassword in cleartext. Windows normally keeps the password as an LSA secret
+    // instead, but tools and scripts that set up autologon often write it here.
+    pub password: Option<String>,
+}
+
+// Function to read the automatic logon settings from a SOFTWARE hive. Returns None when autologon
+// isn't configured: AutoAdminLogon is unset or off and no cleartext DefaultPassword is stored.
+pub fn autologon_info<R: Read + Seek>(software_hive: &mut Hive<R>) -> Result<Option<AutoLogon>, HiveError> {
+    let winlogon = match software_hive.open_key(WINLOGON_PATH) {
+        Ok(winlogon) => winlogon,
+        Err(HiveError::KeyNotFound(_)) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let enabled = match software_hive.value_data_named(&winlogon, "AutoAdminLogon")? {
+        // Usually REG_SZ "0" or "1", though some setups write a DWORD
+        Some((REG_DWORD, data)) if data.len() == 4 => u32::from_le_bytes([data[0], data[1], data[2], data[3]]) != 0,
+        Some((_, data)) => decode_reg_sz(&data).is_some_and(|enabled| enabled.trim() == "1"),
+        None => false,
+    };
+    let password = string_value(software_hive, &winlogon, "DefaultPassword")?;
+    if !enabled && password.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(AutoLogon {
+        enabled,
+        user_name: string_value(software_hive, &winlogon, "DefaultUserName")?,
+        domain_name: string_value(software_hive, &winlogon, "DefaultDomainName")?,
+        password,
+    }))
+}
+
+// Function to read a string value, treating an empty string like a missing value
+fn string_value<R: Read + Seek>(hive: &mut Hive<R>, key_node: &KeyNode, name: &str) -> Result<Option<String>, HiveError> {
+    let data = hive.value_data_named(key_node, name)?;
+    Ok(data
+        .and_then(|(_, data)| decode_reg_sz(&data))
+        .filter(|string| !string.is_empty()))
+}
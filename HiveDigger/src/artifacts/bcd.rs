@@ -0,0 +1,186 @@
+use std::io::{Read, Seek};
+
+use crate::{reg::decode_reg_sz, Hive, HiveError, RegistryValue};
+
+const OBJECTS_PATH: &str = "Objects";
+
+const REG_MULTI_SZ: u32 = 7;
+
+// Element formats, bits 24-27 of an element type
+const FORMAT_DEVICE: u32 = 1;
+const FORMAT_STRING: u32 = 2;
+const FORMAT_OBJECT: u32 = 3;
+const FORMAT_OBJECT_LIST: u32 = 4;
+const FORMAT_INTEGER: u32 = 5;
+const FORMAT_BOOLEAN: u32 = 6;
+const FORMAT_INTEGER_LIST: u32 = 7;
+
+// bcdedit names of commonly inspected elements
+const ELEMENT_NAMES: &[(u32, &str)] = &[
+    (0x11000001, "device"),
+    (0x12000002, "path"),
+    (0x12000004, "description"),
+    (0x12000005, "locale"),
+    (0x14000006, "inherit"),
+    (0x16000009, "recoveryenabled"),
+    (0x16000048, "nointegritychecks"),
+    (0x16000049, "testsigning"),
+    (0x21000001, "osdevice"),
+    (0x22000002, "systemroot"),
+    (0x23000003, "resumeobject"),
+    (0x23000006, "default"),
+    (0x24000001, "displayorder"),
+    (0x24000010, "toolsdisplayorder"),
+    (0x25000004, "timeout"),
+    (0x25000080, "safeboot"),
+    (0x250000c2, "bootmenupolicy"),
+    (0x260000a0, "debug"),
+];
+
+// Struct representing one object of a BCD (boot configuration data) hive: a boot manager,
+// OS loader, resume application, device or set of inherited settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BcdObject {
+    pub id: String,               // The object's key name, a GUID such as "{9dea862c-5cdd-4e70-acc1-f32b344d4795}"
+    pub object_type: Option<u32>, // Description\Type
+    pub elements: Vec<BcdElement>,
+}
+
+// Struct representing one element (setting) of a BCD object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BcdElement {
+    pub element_type: u32,          // The element's key name, in hex
+    pub name: Option<&'static str>, // bcdedit's name for common elements
+    pub value: BcdElementValue,
+}
+
+// Enum for element data, decoded according to the format encoded in the element type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BcdElementValue {
+    // The device structure is undocumented, so it is left raw
+    Device(Vec<u8>),
+    String(String),
+    Object(String), // GUID of another object
+    ObjectList(Vec<String>),
+    Integer(u64),
+    Boolean(bool),
+    IntegerList(Vec<u64>),
+    // Unknown formats and data that doesn't fit its format
+    Other(RegistryValue),
+}
+
+impl BcdObject {
+    // Function to name the kind of object, following bcdedit ("bootmgr", "osloader", ...)
+    pub fn kind(&self) -> Option<&'static str> {
+        let object_type = self.object_type?;
+        match object_type >> 28 {
+            // Application objects carry the application type in the low 20 bits
+            1 => match object_type & 0xFFFFF {
+                1 => Some("fwbootmgr"),
+                2 => Some("bootmgr"),
+                3 => Some("osloader"),
+                4 => Some("resume"),
+                5 => Some("memdiag"),
+                6 => Some("ntldr"),
+                7 => Some("setupldr"),
+                8 => Some("bootsector"),
+                9 => Some("startup"),
+                _ => None,
+            },
+            2 => Some("inherit"),
+            3 => Some("device"),
+            _ => None,
+        }
+    }
+
+    // Function to find an element by type
+    pub fn element(&self, element_type: u32) -> Option<&BcdElement> {
+        self.elements.iter().find(|element| element.element_type == element_type)
+    }
+}
+
+// Function to list the objects of a BCD hive (\Boot\BCD or \EFI\Microsoft\Boot\BCD) with their
+// elements. Element keys whose names aren't hex element types are skipped.
+pub fn parse_bcd<R: Read + Seek>(hive: &mut Hive<R>) -> Result<Vec<BcdObject>, HiveError> {
+    let objects_key = hive.open_key(OBJECTS_PATH)?;
+    let object_keys = hive.subkeys(&objects_key).collect::<Result<Vec<_>, _>>()?;
+
+    let mut objects = Vec::new();
+    for (id, _) in object_keys {
+        let object_path = format!("{}\\{}", OBJECTS_PATH, id);
+
+        let description_path = format!("{}\\Description", object_path);
+        let object_type = match hive.open_key(&description_path) {
+            Ok(description) => match hive.value_data_named(&description, "Type")? {
+                Some((_, data)) if data.len() == 4 => Some(u32::from_le_bytes([data[0], data[1], data[2], data[3]])),
+                _ => None,
+            },
+            Err(HiveError::KeyNotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let elements_path = format!("{}\\Elements", object_path);
+        let element_keys = match hive.open_key(&elements_path) {
+            Ok(elements_key) => hive.subkeys(&elements_key).collect::<Result<Vec<_>, _>>()?,
+            Err(HiveError::KeyNotFound(_)) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let mut elements = Vec::new();
+        for (element_name, element_key) in element_keys {
+            let Ok(element_type) = u32::from_str_radix(&element_name, 16) else {
+                continue;
+            };
+            let Some((data_type, data)) = hive.value_data_named(&element_key, "Element")? else {
+                continue;
+            };
+            elements.push(BcdElement {
+                element_type,
+                name: element_name_of(element_type),
+                value: decode_element(element_type, data_type, &data),
+            });
+        }
+
+        objects.push(BcdObject {
+            id,
+            object_type,
+            elements,
+        });
+    }
+
+    Ok(objects)
+}
+
+// Function to look up bcdedit's name for an element type
+fn element_name_of(element_type: u32) -> Option<&'static str> {
+    ELEMENT_NAMES
+        .iter()
+        .find(|&&(known_type, _)| known_type == element_type)
+        .map(|&(_, name)| name)
+}
+
+// Function to decode element data by the format in bits 24-27 of its type. Integers are stored
+// as little-endian REG_BINARY, shorter than 8 bytes for some elements.
+fn decode_element(element_type: u32, data_type: u32, data: &[u8]) -> BcdElementValue {
+    let integer = |bytes: &[u8]| {
+        let mut integer_bytes = [0u8; 8];
+        integer_bytes[..bytes.len()].copy_from_slice(bytes);
+        u64::from_le_bytes(integer_bytes)
+    };
+
+    let decoded = match (element_type >> 24) & 0xF {
+        FORMAT_DEVICE => Some(BcdElementValue::Device(data.to_vec())),
+        FORMAT_STRING => decode_reg_sz(data).map(BcdElementValue::String),
+        FORMAT_OBJECT => decode_reg_sz(data).map(BcdElementValue::Object),
+        FORMAT_OBJECT_LIST => match RegistryValue::decode(REG_MULTI_SZ, data) {
+            RegistryValue::MultiString(objects) => Some(BcdElementValue::ObjectList(objects)),
+            _ => None,
+        },
+        FORMAT_INTEGER if !data.is_empty() && data.len() <= 8 => Some(BcdElementValue::Integer(integer(data))),
+        FORMAT_BOOLEAN if !data.is_empty() => Some(BcdElementValue::Boolean(data.iter().any(|&byte| byte != 0))),
+        FORMAT_INTEGER_LIST if data.len().is_multiple_of(8) => {
+            Some(BcdElementValue::IntegerList(data.chunks_exact(8).map(integer).collect()))
+        }
+        _ => None,
+    };
+    decoded.unwrap_or_else(|| BcdElementValue::Other(RegistryValue::decode(data_type, data)))
+}
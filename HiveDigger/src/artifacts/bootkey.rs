@@ -0,0 +1,74 @@
+use std::io::{Read, Seek};
+
+use crate::{Hive, HiveError};
+
+// The four keys under Control\Lsa whose class names hold the boot key, in the order they are joined
+const COMPONENT_KEYS: [&str; 4] = ["JD", "Skew1", "GBG", "Data"];
+
+// Order in which the bytes of the joined class names make up the boot key
+const BOOTKEY_PERMUTATION: [usize; 16] = [8, 5, 4, 2, 11, 9, 13, 3, 0, 6, 1, 12, 14, 10, 15, 7];
+
+// Struct representing a SYSTEM hive's boot key (the "syskey") and each step of deriving it, so a
+// result that looks wrong can be traced back to the class names it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootkeyResult {
+    pub control_set: String, // The control set read, e.g. "ControlSet001"
+    // Class names of Control\Lsa\JD, Skew1, GBG and Data as stored: 8 hex digits each
+    pub jd: String,
+    pub skew1: String,
+    pub gbg: String,
+    pub data: String,
+    pub scrambled: [u8; 16], // The four class names decoded from hex and joined
+    pub bootkey: [u8; 16],   // `scrambled` reordered by the fixed permutation
+}
+
+// Function to derive the boot key from the class names under Control\Lsa in the SYSTEM hive's
+// current control set
+pub fn extract_bootkey<R: Read + Seek>(system_hive: &mut Hive<R>) -> Result<BootkeyResult, HiveError> {
+    let control_set = system_hive.control_set_path("Current")?;
+    let lsa_path = format!("{}\\Control\\Lsa", control_set);
+    let lsa = system_hive.open_key(&lsa_path)?;
+
+    let mut components: [String; 4] = Default::default();
+    let mut scrambled = [0u8; 16];
+    for (position, component) in COMPONENT_KEYS.into_iter().enumerate() {
+        let Some(key_node) = system_hive.subkey(&lsa, component)? else {
+            return Err(HiveError::KeyNotFound(format!("{}\\{}", lsa_path, component)));
+        };
+        let class_name = system_hive.class_name(&key_node)?.unwrap_or_default();
+        let bytes = decode_hex(&class_name).filter(|bytes| bytes.len() == 4).ok_or_else(|| {
+            HiveError::InvalidValueData(format!(
+                "Class name of {}\\{} should be 8 hex digits, found \"{}\"",
+                lsa_path, component, class_name
+            ))
+        })?;
+        scrambled[position * 4..position * 4 + 4].copy_from_slice(&bytes);
+        components[position] = class_name;
+    }
+
+    let mut bootkey = [0u8; 16];
+    for (byte, &source) in bootkey.iter_mut().zip(&BOOTKEY_PERMUTATION) {
+        *byte = scrambled[source];
+    }
+    let [jd, skew1, gbg, data] = components;
+    Ok(BootkeyResult {
+        control_set,
+        jd,
+        skew1,
+        gbg,
+        data,
+        scrambled,
+        bootkey,
+    })
+}
+
+// Function to decode a string of hex digit pairs, or None if it isn't one
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|position| hex.get(position..position + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
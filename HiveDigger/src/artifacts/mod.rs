@@ -0,0 +1,26 @@
+// Parsers for well-known forensic artifacts stored in specific hives
+mod amcache;
+mod autologon;
+mod bcd;
+mod bootkey;
+mod mounted_devices;
+mod network_list;
+mod ntuser;
+mod os_info;
+mod profile_list;
+mod sam;
+mod shellbags;
+mod shimcache;
+
+pub use amcache::{parse_inventory_application_file, AmcacheFile};
+pub use autologon::{autologon_info, AutoLogon};
+pub use bcd::{parse_bcd, BcdElement, BcdElementValue, BcdObject};
+pub use bootkey::{extract_bootkey, BootkeyResult};
+pub use mounted_devices::{decode_mounted_device_data, mounted_devices, MountedDevice, MountedDeviceData};
+pub use network_list::{network_profiles, NetworkKind, NetworkProfile};
+pub use ntuser::{recent_docs, run_mru, typed_paths, MruEntry};
+pub use os_info::{os_info, OsInfo};
+pub use profile_list::{list_user_profiles, ProfileKind, UserProfile};
+pub use sam::{parse_sam_f, AccountFlag, SamAccountMeta};
+pub use shellbags::{parse_bagmru, parse_shell_item, ShellBagEntry, ShellItem};
+pub use shimcache::{parse_shimcache, parse_shimcache_data, ShimcacheEntry, ShimcacheFormat};
@@ -0,0 +1,119 @@
+use std::io::{Read, Seek};
+
+use crate::{Hive, HiveError};
+
+const MOUNTED_DEVICES_PATH: &str = "MountedDevices";
+
+// Value name prefixes for drive letters ("\DosDevices\C:") and volume GUIDs ("\??\Volume{...}")
+const DRIVE_LETTER_PREFIX: &str = "\\DosDevices\\";
+const VOLUME_PREFIX: &str = "\\??\\Volume";
+
+// GPT partitions are recorded as this marker followed by the partition GUID
+const GPT_MARKER: &[u8; 8] = b"DMIO:ID:";
+
+// Enum for what a MountedDevices entry's data identifies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MountedDeviceData {
+    // A partition of an MBR disk: the disk signature and the partition's byte offset (12 bytes)
+    Mbr { disk_signature: u32, partition_offset: u64 },
+    // A GPT partition, by its partition GUID
+    Gpt { partition_guid: String },
+    // A device interface path stored as UTF-16LE, e.g.
+    // "_??_USBSTOR#Disk&Ven_SanDisk&Prod_Cruzer&Rev_1.00#4C530001230101112094&0#{53f56307-...}"
+    // for a USB mass storage device
+    Device(String),
+    // Data in none of the layouts above
+    Unknown(Vec<u8>),
+}
+
+// Struct representing one value of the SYSTEM hive's MountedDevices key, which maps drive
+// letters and volume GUIDs to the disk or device they were last mounted from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountedDevice {
+    pub name: String,                // The value name, e.g. "\DosDevices\E:"
+    pub drive_letter: Option<char>,  // Set for "\DosDevices\X:" entries
+    pub volume_guid: Option<String>, // Set for "\??\Volume{GUID}" entries, e.g. "{4d36e967-...}"
+    pub data: MountedDeviceData,
+}
+
+// Function to read the MountedDevices entries of a SYSTEM hive, in stored order. A hive without
+// the key has no entries.
+pub fn mounted_devices<R: Read + Seek>(system_hive: &mut Hive<R>) -> Result<Vec<MountedDevice>, HiveError> {
+    let mounted_devices_key = match system_hive.open_key(MOUNTED_DEVICES_PATH) {
+        Ok(key_node) => key_node,
+        Err(HiveError::KeyNotFound(_)) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let values = system_hive.values(&mounted_devices_key).collect::<Result<Vec<_>, _>>()?;
+
+    let mut devices = Vec::new();
+    for (name, key_value) in values {
+        let data = system_hive.value_data(&key_value)?;
+
+        let drive_letter = name
+            .strip_prefix(DRIVE_LETTER_PREFIX)
+            .and_then(|drive| drive.strip_suffix(':'))
+            .and_then(|letter| {
+                let mut characters = letter.chars();
+                match (characters.next(), characters.next()) {
+                    (Some(letter), None) => Some(letter),
+                    _ => None,
+                }
+            });
+        let volume_guid = name.strip_prefix(VOLUME_PREFIX).map(str::to_string);
+
+        devices.push(MountedDevice {
+            name,
+            drive_letter,
+            volume_guid,
+            data: decode_mounted_device_data(&data),
+        });
+    }
+
+    Ok(devices)
+}
+
+// Function to tell the MBR, GPT and device path layouts of MountedDevices data apart
+pub fn decode_mounted_device_data(data: &[u8]) -> MountedDeviceData {
+    if data.len() == 12 {
+        let mut partition_offset = [0u8; 8];
+        partition_offset.copy_from_slice(&data[4..12]);
+        return MountedDeviceData::Mbr {
+            disk_signature: u32::from_le_bytes([data[0], data[1], data[2], data[3]]),
+            partition_offset: u64::from_le_bytes(partition_offset),
+        };
+    }
+    if data.len() == 24 && data.starts_with(GPT_MARKER) {
+        if let Some(partition_guid) = guid_to_string(&data[8..]) {
+            return MountedDeviceData::Gpt { partition_guid };
+        }
+    }
+
+    // Device paths are UTF-16LE text without a terminator, starting with "_??_" or "\??\"
+    if data.len() >= 8 && data.len().is_multiple_of(2) {
+        let path_utf16: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .take_while(|&character| character != 0)
+            .collect();
+        if let Ok(path) = String::from_utf16(&path_utf16) {
+            if path.starts_with("_??_") || path.starts_with("\\??\\") {
+                return MountedDeviceData::Device(path);
+            }
+        }
+    }
+
+    MountedDeviceData::Unknown(data.to_vec())
+}
+
+// Function to render a binary GUID (little-endian first three fields) as
+// "{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}"
+pub(crate) fn guid_to_string(bytes: &[u8]) -> Option<String> {
+    let bytes = bytes.get(..16)?;
+    let data1 = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let data2 = u16::from_le_bytes([bytes[4], bytes[5]]);
+    let data3 = u16::from_le_bytes([bytes[6], bytes[7]]);
+    let data4: String = bytes[8..10].iter().map(|byte| format!("{:02x}", byte)).collect();
+    let data5: String = bytes[10..16].iter().map(|byte| format!("{:02x}", byte)).collect();
+    Some(format!("{{{:08x}-{:04x}-{:04x}-{}-{}}}", data1, data2, data3, data4, data5))
+}
@@ -0,0 +1,93 @@
+use std::io::{Read, Seek};
+
+use chrono::NaiveDateTime;
+
+use crate::{reg::decode_reg_sz, time::systemtime_to_datetime, Hive, HiveError, KeyNode};
+
+const PROFILES_PATH: &str = "Microsoft\\Windows NT\\CurrentVersion\\NetworkList\\Profiles";
+
+const REG_DWORD: u32 = 4;
+
+// Enum for the kinds of network a profile describes, from its NameType value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkKind {
+    Wired,      // 0x06
+    Wireless,   // 0x47
+    Mobile,     // 0x17 (mobile broadband)
+    Other(u32), // Anything else, e.g. VPN connections
+}
+
+impl NetworkKind {
+    fn from_name_type(name_type: u32) -> NetworkKind {
+        match name_type {
+            0x06 => NetworkKind::Wired,
+            0x47 => NetworkKind::Wireless,
+            0x17 => NetworkKind::Mobile,
+            other => NetworkKind::Other(other),
+        }
+    }
+}
+
+// Struct representing a network the machine has connected to, from the SOFTWARE hive's
+// NetworkList\Profiles. For a wireless network the profile name is usually the SSID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkProfile {
+    pub guid: String, // The profile's key name, e.g. "{6A1C4E2B-...}"
+    pub profile_name: Option<String>,
+    pub description: Option<String>,
+    pub kind: Option<NetworkKind>,
+    // Managed networks are ones joined to a domain; None when the profile doesn't say
+    pub managed: Option<bool>,
+    // In the machine's local time, which the hive doesn't record
+    pub date_created: Option<NaiveDateTime>,
+    pub date_last_connected: Option<NaiveDateTime>,
+}
+
+// Function to list the network profiles recorded in a SOFTWARE hive, in subkey order. A hive
+// without NetworkList gives no profiles.
+pub fn network_profiles<R: Read + Seek>(software_hive: &mut Hive<R>) -> Result<Vec<NetworkProfile>, HiveError> {
+    let profiles_key = match software_hive.open_key(PROFILES_PATH) {
+        Ok(profiles_key) => profiles_key,
+        Err(HiveError::KeyNotFound(_)) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let profile_keys = software_hive.subkeys(&profiles_key).collect::<Result<Vec<_>, _>>()?;
+
+    let mut profiles = Vec::new();
+    for (guid, profile_key) in profile_keys {
+        let date = |hive: &mut Hive<R>, name: &str| -> Result<Option<NaiveDateTime>, HiveError> {
+            // Unset dates are stored as zeros
+            let data = hive.value_data_named(&profile_key, name)?;
+            Ok(data
+                .and_then(|(_, data)| <[u8; 16]>::try_from(data).ok())
+                .and_then(|systemtime| systemtime_to_datetime(&systemtime).ok())
+                .map(|datetime| datetime.naive_utc()))
+        };
+        profiles.push(NetworkProfile {
+            profile_name: string_value(software_hive, &profile_key, "ProfileName")?,
+            description: string_value(software_hive, &profile_key, "Description")?,
+            kind: dword_value(software_hive, &profile_key, "NameType")?.map(NetworkKind::from_name_type),
+            managed: dword_value(software_hive, &profile_key, "Managed")?.map(|managed| managed != 0),
+            date_created: date(software_hive, "DateCreated")?,
+            date_last_connected: date(software_hive, "DateLastConnected")?,
+            guid,
+        });
+    }
+    Ok(profiles)
+}
+
+// Function to read a string value, treating an empty string like a missing value
+fn string_value<R: Read + Seek>(hive: &mut Hive<R>, key_node: &KeyNode, name: &str) -> Result<Option<String>, HiveError> {
+    let data = hive.value_data_named(key_node, name)?;
+    Ok(data
+        .and_then(|(_, data)| decode_reg_sz(&data))
+        .filter(|string| !string.is_empty()))
+}
+
+// Function to read a DWORD value, or None if it is missing or has another type
+fn dword_value<R: Read + Seek>(hive: &mut Hive<R>, key_node: &KeyNode, name: &str) -> Result<Option<u32>, HiveError> {
+    Ok(match hive.value_data_named(key_node, name)? {
+        Some((REG_DWORD, data)) if data.len() == 4 => Some(u32::from_le_bytes([data[0], data[1], data[2], data[3]])),
+        _ => None,
+    })
+}
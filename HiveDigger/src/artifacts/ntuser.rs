@@ -0,0 +1,126 @@
+// Triage helpers for a user's NTUSER.DAT: most-recently-used lists kept by Explorer
+use std::io::{Read, Seek};
+
+use crate::{reg::decode_reg_sz, Hive, HiveError, KeyNode, Located};
+
+const RUN_MRU_PATH: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RunMRU";
+const TYPED_PATHS_PATH: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\TypedPaths";
+const RECENT_DOCS_PATH: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RecentDocs";
+
+// Explorer appends this to each command it records in RunMRU
+const RUN_MRU_SUFFIX: &str = "\\1";
+
+// Struct representing one entry of a most-recently-used list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MruEntry {
+    pub position: usize,    // 0 is the most recent
+    pub value_name: String, // The value the entry is stored in ("a", "url1", "3", ...)
+    pub value: String,      // The command, path or document name
+}
+
+// Function to list the commands typed into the Run dialog, most recent first. The order comes
+// from MRUList, a string of value names such as "cab".
+pub fn run_mru<R: Read + Seek>(ntuser_hive: &mut Hive<R>) -> Result<Vec<MruEntry>, HiveError> {
+    let Some(run_mru) = open_optional(ntuser_hive, RUN_MRU_PATH)? else {
+        return Ok(Vec::new());
+    };
+    let Some((_, mru_list)) = ntuser_hive.value_data_named(&run_mru, "MRUList")? else {
+        return Ok(Vec::new());
+    };
+    let order: Vec<String> = decode_reg_sz(&mru_list)
+        .unwrap_or_default()
+        .chars()
+        .map(String::from)
+        .collect();
+
+    let mut entries = read_entries(ntuser_hive, &run_mru, order, decode_reg_sz)?;
+    for entry in &mut entries {
+        if let Some(command) = entry.value.strip_suffix(RUN_MRU_SUFFIX) {
+            entry.value = command.to_string();
+        }
+    }
+    Ok(entries)
+}
+
+// Function to list the paths typed into the Explorer address bar, most recent first. There is
+// no MRU list: values are named url1, url2, ... with url1 the most recent.
+pub fn typed_paths<R: Read + Seek>(ntuser_hive: &mut Hive<R>) -> Result<Vec<MruEntry>, HiveError> {
+    let Some(typed_paths) = open_optional(ntuser_hive, TYPED_PATHS_PATH)? else {
+        return Ok(Vec::new());
+    };
+    let values = ntuser_hive.values(&typed_paths).collect::<Result<Vec<_>, _>>()?;
+    let mut numbered: Vec<(u32, String)> = values
+        .into_iter()
+        .filter_map(|(value_name, _)| {
+            let number = value_name.get(3..)?.parse().ok()?;
+            value_name[..3].eq_ignore_ascii_case("url").then_some((number, value_name))
+        })
+        .collect();
+    numbered.sort();
+
+    let order = numbered.into_iter().map(|(_, value_name)| value_name).collect();
+    read_entries(ntuser_hive, &typed_paths, order, decode_reg_sz)
+}
+
+// Function to list the documents recently opened through Explorer, most recent first. The order
+// comes from MRUListEx, a list of 32-bit value numbers ending in 0xFFFFFFFF, and each value
+// holds the document name followed by a shell item. Only the combined list is read; the
+// per-extension lists in the subkeys (".docx", ...) hold the same documents.
+pub fn recent_docs<R: Read + Seek>(ntuser_hive: &mut Hive<R>) -> Result<Vec<MruEntry>, HiveError> {
+    let Some(recent_docs) = open_optional(ntuser_hive, RECENT_DOCS_PATH)? else {
+        return Ok(Vec::new());
+    };
+    let Some((_, mru_list_ex)) = ntuser_hive.value_data_named(&recent_docs, "MRUListEx")? else {
+        return Ok(Vec::new());
+    };
+    let order = mru_list_ex
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .take_while(|&number| number != 0xFFFFFFFF)
+        .map(|number| number.to_string())
+        .collect();
+
+    read_entries(ntuser_hive, &recent_docs, order, |data| {
+        // The name is NUL-terminated UTF-16; the shell item after it can make the length odd
+        let name_utf16: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .take_while(|&character| character != 0)
+            .collect();
+        Some(String::from_utf16_lossy(&name_utf16))
+    })
+}
+
+// Function to open a key that is only present once the user has used the feature it records
+fn open_optional<R: Read + Seek>(hive: &mut Hive<R>, path: &str) -> Result<Option<Located<KeyNode>>, HiveError> {
+    match hive.open_key(path) {
+        Ok(key_node) => Ok(Some(key_node)),
+        Err(HiveError::KeyNotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Function to read the values named in an MRU order, skipping names with no value or data
+// that doesn't decode
+fn read_entries<R: Read + Seek>(
+    hive: &mut Hive<R>,
+    key_node: &KeyNode,
+    order: Vec<String>,
+    decode: impl Fn(&[u8]) -> Option<String>,
+) -> Result<Vec<MruEntry>, HiveError> {
+    let mut entries = Vec::new();
+    for value_name in order {
+        let Some((_, data)) = hive.value_data_named(key_node, &value_name)? else {
+            continue;
+        };
+        let Some(value) = decode(&data) else {
+            continue;
+        };
+        entries.push(MruEntry {
+            position: entries.len(),
+            value_name,
+            value,
+        });
+    }
+    Ok(entries)
+}
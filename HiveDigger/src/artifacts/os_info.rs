@@ -0,0 +1,64 @@
+use std::io::{Read, Seek};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    reg::decode_reg_sz,
+    time::{filetime_to_datetime, unix_epoch_to_datetime},
+    Hive, HiveError,
+};
+
+const CURRENT_VERSION_PATH: &str = "Microsoft\\Windows NT\\CurrentVersion";
+
+// Struct representing the operating system details recorded in a SOFTWARE hive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsInfo {
+    pub product_name: Option<String>,    // e.g. "Windows 10 Pro"
+    pub display_version: Option<String>, // e.g. "22H2" (ReleaseId on older builds)
+    pub current_build: Option<String>,
+    pub registered_owner: Option<String>,
+    pub install_date: Option<DateTime<Utc>>, // InstallDate: Unix epoch seconds (REG_DWORD)
+    pub install_time: Option<DateTime<Utc>>, // InstallTime: FILETIME (REG_QWORD), Windows 10+
+}
+
+// Function to read the operating system details from a SOFTWARE hive.
+// Missing values are left as None; a missing CurrentVersion key is an error.
+pub fn os_info<R: Read + Seek>(hive: &mut Hive<R>) -> Result<OsInfo, HiveError> {
+    let current_version = hive.open_key(CURRENT_VERSION_PATH)?;
+
+    let mut string = |name: &str| -> Result<Option<String>, HiveError> {
+        let data = hive.value_data_named(&current_version, name)?;
+        Ok(data.and_then(|(_, data)| decode_reg_sz(&data)))
+    };
+    let product_name = string("ProductName")?;
+    let display_version = match string("DisplayVersion")? {
+        Some(display_version) => Some(display_version),
+        None => string("ReleaseId")?,
+    };
+    let current_build = string("CurrentBuild")?;
+    let registered_owner = string("RegisteredOwner")?;
+
+    let install_date = match hive.value_data_named(&current_version, "InstallDate")? {
+        Some((_, data)) if data.len() == 4 => {
+            Some(unix_epoch_to_datetime(u32::from_le_bytes([data[0], data[1], data[2], data[3]])))
+        }
+        _ => None,
+    };
+    let install_time = match hive.value_data_named(&current_version, "InstallTime")? {
+        Some((_, data)) if data.len() == 8 => {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&data);
+            Some(filetime_to_datetime(u64::from_le_bytes(bytes)))
+        }
+        _ => None,
+    };
+
+    Ok(OsInfo {
+        product_name,
+        display_version,
+        current_build,
+        registered_owner,
+        install_date,
+        install_time,
+    })
+}
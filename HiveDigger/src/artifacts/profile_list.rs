@@ -0,0 +1,94 @@
+use std::io::{Read, Seek};
+
+use crate::{reg::decode_reg_sz, Hive, HiveError};
+
+const PROFILE_LIST_PATH: &str = "Microsoft\\Windows NT\\CurrentVersion\\ProfileList";
+
+// Suffix Windows gives the original profile key when it creates a temporary profile instead
+const BACKUP_SUFFIX: &str = ".bak";
+
+// Enum for the kinds of account a profile belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileKind {
+    User,
+    System,         // S-1-5-18 (LocalSystem)
+    LocalService,   // S-1-5-19
+    NetworkService, // S-1-5-20
+}
+
+// Struct representing one entry of the SOFTWARE hive's ProfileList
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserProfile {
+    pub sid: String,
+    pub kind: ProfileKind,
+    pub profile_image_path: Option<String>, // e.g. "C:\Users\alice" (may contain %SystemDrive% etc.)
+    pub backup: bool,                       // The key name ends in ".bak"
+}
+
+// Function to list the user profiles recorded in a SOFTWARE hive, mapping each account SID to
+// its profile directory
+pub fn list_user_profiles<R: Read + Seek>(software_hive: &mut Hive<R>) -> Result<Vec<UserProfile>, HiveError> {
+    let profile_list = software_hive.open_key(PROFILE_LIST_PATH)?;
+    let profile_keys = software_hive.subkeys(&profile_list).collect::<Result<Vec<_>, _>>()?;
+
+    let mut profiles = Vec::new();
+    for (key_name, profile_key) in profile_keys {
+        let (key_sid, backup) = match key_name.strip_suffix(BACKUP_SUFFIX) {
+            Some(key_sid) => (key_sid, true),
+            None => (key_name.as_str(), false),
+        };
+
+        // The key is named after the SID; fall back to the binary Sid value if it isn't
+        let sid = if key_sid.starts_with("S-") {
+            key_sid.to_string()
+        } else {
+            match software_hive.value_data_named(&profile_key, "Sid")? {
+                Some((_, data)) => match sid_to_string(&data) {
+                    Some(sid) => sid,
+                    None => continue,
+                },
+                None => continue,
+            }
+        };
+
+        let profile_image_path = software_hive
+            .value_data_named(&profile_key, "ProfileImagePath")?
+            .and_then(|(_, data)| decode_reg_sz(&data));
+
+        let kind = match sid.as_str() {
+            "S-1-5-18" => ProfileKind::System,
+            "S-1-5-19" => ProfileKind::LocalService,
+            "S-1-5-20" => ProfileKind::NetworkService,
+            _ => ProfileKind::User,
+        };
+
+        profiles.push(UserProfile {
+            sid,
+            kind,
+            profile_image_path,
+            backup,
+        });
+    }
+
+    Ok(profiles)
+}
+
+// Function to render a binary SID in its "S-1-5-21-..." string form
+pub(crate) fn sid_to_string(bytes: &[u8]) -> Option<String> {
+    let revision = *bytes.first()?;
+    let sub_authority_count = *bytes.get(1)? as usize;
+    let identifier_authority = bytes.get(2..8)?;
+    let sub_authorities = bytes.get(8..8 + sub_authority_count * 4)?;
+
+    // The identifier authority is a 48-bit big-endian number
+    let authority = identifier_authority
+        .iter()
+        .fold(0u64, |authority, &byte| (authority << 8) | byte as u64);
+
+    let mut sid = format!("S-{}-{}", revision, authority);
+    for sub_authority in sub_authorities.chunks_exact(4) {
+        let sub_authority = u32::from_le_bytes([sub_authority[0], sub_authority[1], sub_authority[2], sub_authority[3]]);
+        sid.push_str(&format!("-{}", sub_authority));
+    }
+    Some(sid)
+}
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+
+use crate::{time::filetime_to_datetime, HiveError};
+
+// Offsets into the fixed-size F value of a SAM\Domains\Account\Users\<RID> key
+const LAST_LOGON_OFFSET: usize = 0x08;
+const PASSWORD_LAST_SET_OFFSET: usize = 0x18;
+const ACCOUNT_EXPIRES_OFFSET: usize = 0x20;
+const LAST_FAILED_LOGON_OFFSET: usize = 0x28;
+const RID_OFFSET: usize = 0x30;
+const ACCOUNT_CONTROL_OFFSET: usize = 0x38;
+const FAILED_LOGON_COUNT_OFFSET: usize = 0x40;
+const LOGON_COUNT_OFFSET: usize = 0x42;
+// Bytes up to and including the last field read; Windows writes 0x50
+const MINIMUM_F_LENGTH: usize = 0x44;
+
+// FILETIME stored for "never" in the expiry field
+const FILETIME_NEVER: u64 = 0x7FFF_FFFF_FFFF_FFFF;
+
+// Enum for the user account control (ACB_*) bits of a SAM account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountFlag {
+    Disabled,                     // 0x0001
+    HomeDirectoryRequired,        // 0x0002
+    PasswordNotRequired,          // 0x0004
+    TempDuplicateAccount,         // 0x0008
+    NormalAccount,                // 0x0010
+    MnsLogonAccount,              // 0x0020
+    InterdomainTrustAccount,      // 0x0040
+    WorkstationTrustAccount,      // 0x0080
+    ServerTrustAccount,           // 0x0100
+    PasswordDoesNotExpire,        // 0x0200
+    AutoLocked,                   // 0x0400, locked out after too many failed logons
+    EncryptedTextPasswordAllowed, // 0x0800
+    SmartcardRequired,            // 0x1000
+    TrustedForDelegation,         // 0x2000
+    NotDelegated,                 // 0x4000
+    UseDesKeyOnly,                // 0x8000
+}
+
+impl AccountFlag {
+    // Flags in bit order, so a flag's position is its bit number
+    const ALL: [AccountFlag; 16] = [
+        AccountFlag::Disabled,
+        AccountFlag::HomeDirectoryRequired,
+        AccountFlag::PasswordNotRequired,
+        AccountFlag::TempDuplicateAccount,
+        AccountFlag::NormalAccount,
+        AccountFlag::MnsLogonAccount,
+        AccountFlag::InterdomainTrustAccount,
+        AccountFlag::WorkstationTrustAccount,
+        AccountFlag::ServerTrustAccount,
+        AccountFlag::PasswordDoesNotExpire,
+        AccountFlag::AutoLocked,
+        AccountFlag::EncryptedTextPasswordAllowed,
+        AccountFlag::SmartcardRequired,
+        AccountFlag::TrustedForDelegation,
+        AccountFlag::NotDelegated,
+        AccountFlag::UseDesKeyOnly,
+    ];
+
+    // Function to get the flag's bit in the account control field
+    pub fn bit(self) -> u16 {
+        1 << self as u16
+    }
+
+    // Function to list the flags set in an account control field, in bit order
+    pub fn from_bits(account_control: u16) -> Vec<AccountFlag> {
+        AccountFlag::ALL
+            .into_iter()
+            .filter(|flag| account_control & flag.bit() != 0)
+            .collect()
+    }
+}
+
+// Struct representing the account metadata in a SAM user's F value. Times Windows stores as 0
+// (never happened) or as the maximum FILETIME (never expires) are None.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SamAccountMeta {
+    pub rid: u32, // Relative ID, also the user's key name in hex (e.g. 500 is 000001F4)
+    pub last_logon: Option<DateTime<Utc>>,
+    pub password_last_set: Option<DateTime<Utc>>,
+    pub account_expires: Option<DateTime<Utc>>,
+    pub last_failed_logon: Option<DateTime<Utc>>,
+    pub account_control: u16,    // Raw ACB_* bits, including any AccountFlag doesn't name
+    pub flags: Vec<AccountFlag>, // The named bits of `account_control`
+    pub failed_logon_count: u16, // Bad password attempts since the last successful logon
+    pub logon_count: u16,
+}
+
+impl SamAccountMeta {
+    // Function to check whether the account is disabled
+    pub fn is_disabled(&self) -> bool {
+        self.flags.contains(&AccountFlag::Disabled)
+    }
+}
+
+// Function to decode the F value of a SAM\Domains\Account\Users\<RID> key
+pub fn parse_sam_f(bytes: &[u8]) -> Result<SamAccountMeta, HiveError> {
+    if bytes.len() < MINIMUM_F_LENGTH {
+        return Err(HiveError::InvalidValueData(format!(
+            "SAM F value is {} bytes, expected at least {}",
+            bytes.len(),
+            MINIMUM_F_LENGTH
+        )));
+    }
+
+    let u16_at = |offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+    let u32_at =
+        |offset: usize| u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]]);
+    let time_at = |offset: usize| {
+        let mut filetime = [0u8; 8];
+        filetime.copy_from_slice(&bytes[offset..offset + 8]);
+        match u64::from_le_bytes(filetime) {
+            0 | FILETIME_NEVER => None,
+            filetime => Some(filetime_to_datetime(filetime)),
+        }
+    };
+
+    let account_control = u16_at(ACCOUNT_CONTROL_OFFSET);
+    Ok(SamAccountMeta {
+        rid: u32_at(RID_OFFSET),
+        last_logon: time_at(LAST_LOGON_OFFSET),
+        password_last_set: time_at(PASSWORD_LAST_SET_OFFSET),
+        account_expires: time_at(ACCOUNT_EXPIRES_OFFSET),
+        last_failed_logon: time_at(LAST_FAILED_LOGON_OFFSET),
+        account_control,
+        flags: AccountFlag::from_bits(account_control),
+        failed_logon_count: u16_at(FAILED_LOGON_COUNT_OFFSET),
+        logon_count: u16_at(LOGON_COUNT_OFFSET),
+    })
+}
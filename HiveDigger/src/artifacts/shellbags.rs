@@ -0,0 +1,284 @@
+// Shellbags: the folders a user has browsed in Explorer, recorded in UsrClass.dat under BagMRU.
+// Each BagMRU key lists the folders opened below one folder as numbered values holding a shell
+// item, with a subkey of the same number for each folder's own children.
+use std::{
+    collections::HashSet,
+    io::{Read, Seek},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{time::dos_datetime_to_datetime, Hive, HiveError, KeyNode, Located};
+
+const BAG_MRU_PATH: &str = "Local Settings\\Software\\Microsoft\\Windows\\Shell\\BagMRU";
+
+// Windows never nests folders this deep; a deeper BagMRU tree is corrupt
+const MAX_BAG_MRU_DEPTH: usize = 512;
+
+// Signature of the extension block in file entry shell items that holds the long name and the
+// creation and access times
+const FILE_ENTRY_EXTENSION_SIGNATURE: u32 = 0xBEEF0004;
+
+// Shell folders commonly found at the top of BagMRU, by CLSID
+const KNOWN_FOLDERS: [(&str, &str); 8] = [
+    ("20D04FE0-3AEA-1069-A2D8-08002B30309D", "My Computer"),
+    ("450D8FBA-AD25-11D0-98A8-0800361B1103", "My Documents"),
+    ("59031A47-3F72-44A7-89C5-5595FE6B30EE", "Users Files"),
+    ("645FF040-5081-101B-9F08-00AA002F954E", "Recycle Bin"),
+    ("679F85CB-0220-4080-B29B-5540CC05AAB6", "Quick Access"),
+    ("031E4825-7B94-4DC3-B131-E946B44C8DD5", "Libraries"),
+    ("F02C1A0D-BE21-4350-88B0-7367FC96EF3C", "Network"),
+    ("26EE0668-A00A-44D7-9371-BEB064C98683", "Control Panel"),
+];
+
+// Enum for the shell items a BagMRU value can hold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellItem {
+    // A shell folder identified by CLSID, such as My Computer (class type 0x1F)
+    RootFolder { clsid: String, name: Option<&'static str> },
+    // A drive, e.g. "C:\" (class types 0x20 to 0x2F)
+    Volume { name: String },
+    // A folder or file on a volume (class types 0x30 to 0x3F). The short name is always
+    // there; the long name and the creation and access times come from the extension block
+    // Windows XP and later add. Times are to 2-second precision.
+    FileEntry {
+        short_name: String,
+        long_name: Option<String>,
+        is_directory: bool,
+        modified: Option<DateTime<Utc>>,
+        created: Option<DateTime<Utc>>,
+        accessed: Option<DateTime<Utc>>,
+    },
+    // Any other class type (network locations, control panel items, ...), kept as stored
+    Unknown { class_type: u8, raw: Vec<u8> },
+}
+
+impl ShellItem {
+    // Function to get the name the item contributes to a folder path
+    pub fn name(&self) -> String {
+        match self {
+            ShellItem::RootFolder { clsid, name } => name.map(str::to_string).unwrap_or_else(|| format!("{{{}}}", clsid)),
+            ShellItem::Volume { name } => name.trim_end_matches('\\').to_string(),
+            ShellItem::FileEntry { short_name, long_name, .. } => long_name.clone().unwrap_or_else(|| short_name.clone()),
+            ShellItem::Unknown { class_type, .. } => format!("[shell item {:#04x}]", class_type),
+        }
+    }
+}
+
+// Struct representing one folder recorded in BagMRU
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellBagEntry {
+    pub path: String,                   // Names of the items from the top, e.g. "My Computer\C:\Users"
+    pub key_path: String,               // BagMRU key holding the item, e.g. "BagMRU\0\1"
+    pub value_name: String,             // The numbered value holding the item
+    pub mru_position: Option<usize>,    // Place in the key's MRUListEx, 0 being the most recent
+    pub node_slot: Option<u32>,         // The folder's Bags\<n> key with its view settings
+    pub item: ShellItem,
+    pub last_written: Option<DateTime<Utc>>, // Of the folder's own BagMRU key, when it has one
+}
+
+// Function to read every folder in a UsrClass.dat hive's BagMRU tree, parents before their
+// children and siblings most recently used first. Values that aren't in MRUListEx come after
+// those that are, by number. A hive without BagMRU gives no entries.
+pub fn parse_bagmru<R: Read + Seek>(usrclass: &mut Hive<R>) -> Result<Vec<ShellBagEntry>, HiveError> {
+    let bag_mru = match usrclass.open_key(BAG_MRU_PATH) {
+        Ok(bag_mru) => bag_mru,
+        Err(HiveError::KeyNotFound(_)) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    // Explicit stack of (key, its path in BagMRU, folder path of its items, depth)
+    let mut stack = vec![(bag_mru, "BagMRU".to_string(), String::new(), 0)];
+    let mut visited = HashSet::new();
+    let mut entries = Vec::new();
+    while let Some((key_node, key_path, folder_path, depth)) = stack.pop() {
+        if !visited.insert(key_node.offset) || depth >= MAX_BAG_MRU_DEPTH {
+            continue;
+        }
+
+        let mut children = Vec::new();
+        let (order, listed) = slot_order(usrclass, &key_node)?;
+        for (position, number) in order.into_iter().enumerate() {
+            let value_name = number.to_string();
+            let Some((_, data)) = usrclass.value_data_named(&key_node, &value_name)? else {
+                continue;
+            };
+            let item = parse_shell_item(&data);
+            let path = if folder_path.is_empty() {
+                item.name()
+            } else {
+                format!("{}\\{}", folder_path, item.name())
+            };
+
+            let subkey = usrclass.subkey(&key_node, &value_name)?;
+            let node_slot = match &subkey {
+                Some(subkey) => usrclass
+                    .value_data_named(subkey, "NodeSlot")?
+                    .filter(|(_, data)| data.len() == 4)
+                    .map(|(_, data)| u32::from_le_bytes([data[0], data[1], data[2], data[3]])),
+                None => None,
+            };
+            let child_key_path = format!("{}\\{}", key_path, value_name);
+            entries.push(ShellBagEntry {
+                path: path.clone(),
+                key_path: key_path.clone(),
+                value_name,
+                mru_position: (position < listed).then_some(position),
+                node_slot,
+                item,
+                last_written: subkey.as_ref().map(|subkey| subkey.last_written()),
+            });
+            if let Some(subkey) = subkey {
+                children.push((subkey, child_key_path, path, depth + 1));
+            }
+        }
+        // Reversed so the stack pops them in MRU order
+        stack.extend(children.into_iter().rev());
+    }
+    Ok(entries)
+}
+
+// Function to get the numbers of a BagMRU key's item values in MRUListEx order, followed by
+// any numbered values it leaves out, and how many of them MRUListEx lists
+fn slot_order<R: Read + Seek>(hive: &mut Hive<R>, key_node: &Located<KeyNode>) -> Result<(Vec<u32>, usize), HiveError> {
+    let mut order: Vec<u32> = match hive.value_data_named(key_node, "MRUListEx")? {
+        Some((_, mru_list_ex)) => mru_list_ex
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .take_while(|&number| number != 0xFFFFFFFF)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut unlisted: Vec<u32> = hive
+        .values(key_node)
+        .map(|value| value.map(|(value_name, _)| value_name.parse().ok()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .filter(|number| !order.contains(number))
+        .collect();
+    unlisted.sort_unstable();
+    let listed = order.len();
+    order.extend(unlisted);
+    Ok((order, listed))
+}
+
+// Function to decode the shell item at the start of a BagMRU value. Items that are too short
+// for their class type come back as Unknown.
+pub fn parse_shell_item(data: &[u8]) -> ShellItem {
+    // The item's own size comes first; the value ends with the 2-byte terminator of the list
+    let size = data.get(..2).map(|size| u16::from_le_bytes([size[0], size[1]]) as usize).unwrap_or(0);
+    let item = data.get(..size).unwrap_or(data);
+    let class_type = item.get(2).copied().unwrap_or(0);
+
+    let parsed = match class_type {
+        0x1F => parse_root_folder(item),
+        0x20..=0x2F => parse_volume(item),
+        0x30..=0x3F => parse_file_entry(item, class_type),
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| ShellItem::Unknown {
+        class_type,
+        raw: item.to_vec(),
+    })
+}
+
+// Function to decode a root folder item: a sort index, then the folder's CLSID
+fn parse_root_folder(item: &[u8]) -> Option<ShellItem> {
+    let clsid = format_guid(item.get(4..20)?);
+    let name = KNOWN_FOLDERS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(&clsid))
+        .map(|(_, name)| *name);
+    Some(ShellItem::RootFolder { clsid, name })
+}
+
+// Function to decode a volume item: the drive path as a NUL-terminated ASCII string
+fn parse_volume(item: &[u8]) -> Option<ShellItem> {
+    let name = ascii_string(item.get(3..)?);
+    if name.is_empty() {
+        return None;
+    }
+    Some(ShellItem::Volume { name })
+}
+
+// Function to decode a file entry item: size, modification time and attributes, the short
+// name, then extension blocks, of which the BEEF0004 one is read
+fn parse_file_entry(item: &[u8], class_type: u8) -> Option<ShellItem> {
+    let u16_at = |offset: usize| item.get(offset..offset + 2).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]));
+    let modified = dos_datetime_to_datetime(u16_at(8)?, u16_at(10)?);
+    // Bit 2 of the class type marks a UTF-16 short name
+    let short_name = if class_type & 0x04 != 0 {
+        utf16_string(item.get(14..)?)
+    } else {
+        ascii_string(item.get(14..)?)
+    };
+
+    let mut long_name = None;
+    let (mut created, mut accessed) = (None, None);
+    let signature = FILE_ENTRY_EXTENSION_SIGNATURE.to_le_bytes();
+    if let Some(signature_offset) = item.windows(4).position(|window| window == signature) {
+        let block = item.get(signature_offset.checked_sub(4)?..)?;
+        let block_u16 = |offset: usize| block.get(offset..offset + 2).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]));
+        let version = block_u16(2)?;
+        created = dos_datetime_to_datetime(block_u16(8)?, block_u16(10)?);
+        accessed = dos_datetime_to_datetime(block_u16(12)?, block_u16(14)?);
+
+        // The long name follows fields that were added version by version
+        let mut name_offset = 18;
+        if version >= 7 {
+            name_offset += 18; // Unknown, NTFS file reference, unknown
+        }
+        if version >= 3 {
+            name_offset += 2; // Size of the localized name
+        }
+        if version >= 9 {
+            name_offset += 4;
+        }
+        if version >= 8 {
+            name_offset += 4;
+        }
+        long_name = block.get(name_offset..).map(utf16_string).filter(|name| !name.is_empty());
+    }
+
+    Some(ShellItem::FileEntry {
+        short_name,
+        long_name,
+        is_directory: class_type & 0x01 != 0,
+        modified,
+        created,
+        accessed,
+    })
+}
+
+// Function to format a GUID stored in its mixed-endian binary layout
+fn format_guid(bytes: &[u8]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{}-{}",
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        u16::from_le_bytes([bytes[6], bytes[7]]),
+        bytes[8..10].iter().map(|byte| format!("{:02X}", byte)).collect::<String>(),
+        bytes[10..16].iter().map(|byte| format!("{:02X}", byte)).collect::<String>()
+    )
+}
+
+// Function to read a NUL-terminated 8-bit string
+fn ascii_string(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .take_while(|&&byte| byte != 0)
+        .map(|&byte| byte as char)
+        .collect()
+}
+
+// Function to read a NUL-terminated UTF-16LE string
+fn utf16_string(bytes: &[u8]) -> String {
+    let string_utf16: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .take_while(|&character| character != 0)
+        .collect();
+    String::from_utf16_lossy(&string_utf16)
+}
@@ -0,0 +1,204 @@
+use std::io::{Read, Seek};
+
+use chrono::{DateTime, Utc};
+
+use crate::{time::filetime_to_datetime, Hive, HiveError};
+
+const APP_COMPAT_CACHE_PATH: &str = "Control\\Session Manager\\AppCompatCache";
+
+// Windows 7 / Server 2008 R2 header magic; entries are fixed-size records pointing into the data
+const WIN7_MAGIC: u32 = 0xBADC0FEE;
+const WIN7_HEADER_SIZE: usize = 128;
+const WIN7_X64_ENTRY_SIZE: usize = 48;
+
+// Windows 8 and 8.1 use a 128-byte header, Windows 10 and 11 a 48- or 52-byte one (the header
+// starts with its own size). Entries in both are variable-length records with a signature.
+const WIN8_HEADER_SIZE: u32 = 0x80;
+const WIN10_HEADER_SIZES: [u32; 2] = [0x30, 0x34];
+const WIN8_ENTRY_SIGNATURE: &[u8; 4] = b"00ts";
+const WIN81_ENTRY_SIGNATURE: &[u8; 4] = b"10ts"; // Also used by Windows 10 and 11
+
+// Enum for the AppCompatCache layouts `parse_shimcache` understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShimcacheFormat {
+    Win7x64,
+    Win8,  // Windows 8 and 8.1
+    Win10, // Windows 10 and 11
+}
+
+// Struct representing one AppCompatCache (ShimCache) entry. The cache records executables the
+// shim engine looked at; their presence shows a file existed, not necessarily that it ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShimcacheEntry {
+    pub position: usize, // Order in the cache, 0 being the most recently inserted
+    pub path: String,
+    pub last_modified: Option<DateTime<Utc>>, // The file's own modification time (None when 0)
+    // Windows 7 and 8 only. Bit 1 (0x2) is commonly read as "executed" on Windows 7.
+    pub insertion_flags: Option<u32>,
+}
+
+// Function to read the AppCompatCache entries of a SYSTEM hive's current control set, in cache order
+pub fn parse_shimcache<R: Read + Seek>(system_hive: &mut Hive<R>) -> Result<Vec<ShimcacheEntry>, HiveError> {
+    let control_set_path = system_hive.control_set_path("Current")?;
+    let key_path = format!("{}\\{}", control_set_path, APP_COMPAT_CACHE_PATH);
+    let app_compat_cache = system_hive.open_key(&key_path)?;
+    let Some((_, data)) = system_hive.value_data_named(&app_compat_cache, "AppCompatCache")? else {
+        return Err(HiveError::ValueNotFound {
+            key_path,
+            value_name: "AppCompatCache".to_string(),
+        });
+    };
+
+    let (_, entries) = parse_shimcache_data(&data)?;
+    Ok(entries)
+}
+
+// Function to parse raw AppCompatCache value data, detecting its format from the header
+pub fn parse_shimcache_data(data: &[u8]) -> Result<(ShimcacheFormat, Vec<ShimcacheEntry>), HiveError> {
+    let mut reader = ByteReader { data, position: 0 };
+    let magic = reader.u32()?;
+    match magic {
+        WIN7_MAGIC => Ok((ShimcacheFormat::Win7x64, parse_win7_x64(data)?)),
+        WIN8_HEADER_SIZE => Ok((ShimcacheFormat::Win8, parse_signed_entries(data, WIN8_HEADER_SIZE as usize, true)?)),
+        header_size if WIN10_HEADER_SIZES.contains(&header_size) => {
+            Ok((ShimcacheFormat::Win10, parse_signed_entries(data, header_size as usize, false)?))
+        }
+        _ => Err(HiveError::InvalidValueData(format!("Unknown AppCompatCache header {:#010x}", magic))),
+    }
+}
+
+// Function to parse the Windows 7 x64 layout: an entry count, then fixed records holding the
+// path and data as offsets from the start of the value
+fn parse_win7_x64(data: &[u8]) -> Result<Vec<ShimcacheEntry>, HiveError> {
+    let mut header = ByteReader { data, position: 4 };
+    let entry_count = header.u32()? as usize;
+
+    let mut entries = Vec::new();
+    for position in 0..entry_count {
+        let mut entry = ByteReader {
+            data,
+            position: WIN7_HEADER_SIZE + position * WIN7_X64_ENTRY_SIZE,
+        };
+        let path_length = entry.u16()? as usize;
+        entry.skip(2 + 4)?; // Maximum path length and padding
+        let path_offset = entry.u64()? as usize;
+        let last_modified = entry.u64()?;
+        let insertion_flags = entry.u32()?;
+
+        let path = ByteReader {
+            data,
+            position: path_offset,
+        }
+        .utf16(path_length)?;
+        entries.push(ShimcacheEntry {
+            position,
+            path,
+            last_modified: filetime(last_modified),
+            insertion_flags: Some(insertion_flags),
+        });
+    }
+    Ok(entries)
+}
+
+// Function to parse the Windows 8 and later layout: records starting with a "00ts"/"10ts"
+// signature and their own length, one after another up to the end of the data
+fn parse_signed_entries(data: &[u8], header_size: usize, win8: bool) -> Result<Vec<ShimcacheEntry>, HiveError> {
+    let mut reader = ByteReader {
+        data,
+        position: header_size,
+    };
+
+    let mut entries = Vec::new();
+    while reader.position < data.len() {
+        let signature = reader.bytes(4)?;
+        if signature != WIN8_ENTRY_SIGNATURE && signature != WIN81_ENTRY_SIGNATURE {
+            return Err(HiveError::InvalidValueData(format!(
+                "Bad AppCompatCache entry signature at offset {:#x}",
+                reader.position - 4
+            )));
+        }
+        reader.skip(4)?; // Unknown
+        let entry_size = reader.u32()? as usize;
+        let mut entry = ByteReader {
+            data: reader.bytes(entry_size)?,
+            position: 0,
+        };
+
+        let path_length = entry.u16()? as usize;
+        let path = entry.utf16(path_length)?;
+        let insertion_flags = if win8 {
+            // Package name, then the insertion and shim flags
+            let package_length = entry.u16()? as usize;
+            entry.skip(package_length)?;
+            let insertion_flags = entry.u32()?;
+            entry.skip(4)?;
+            Some(insertion_flags)
+        } else {
+            None
+        };
+        let last_modified = entry.u64()?;
+
+        entries.push(ShimcacheEntry {
+            position: entries.len(),
+            path,
+            last_modified: filetime(last_modified),
+            insertion_flags,
+        });
+    }
+    Ok(entries)
+}
+
+// Function to convert a FILETIME, treating 0 as unset
+fn filetime(filetime: u64) -> Option<DateTime<Utc>> {
+    (filetime != 0).then(|| filetime_to_datetime(filetime))
+}
+
+// Struct representing a bounds-checked little-endian cursor over the value data
+struct ByteReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8], HiveError> {
+        let end = self.position.checked_add(length);
+        let Some(bytes) = end.and_then(|end| self.data.get(self.position..end)) else {
+            return Err(HiveError::InvalidValueData(format!(
+                "AppCompatCache data truncated at offset {:#x}",
+                self.position
+            )));
+        };
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, length: usize) -> Result<(), HiveError> {
+        self.bytes(length).map(|_| ())
+    }
+
+    fn u16(&mut self) -> Result<u16, HiveError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, HiveError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64, HiveError> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.bytes(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    // Function to read a UTF-16LE string of `length` bytes
+    fn utf16(&mut self, length: usize) -> Result<String, HiveError> {
+        let string_utf16: Vec<u16> = self
+            .bytes(length)?
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect();
+        Ok(String::from_utf16_lossy(&string_utf16))
+    }
+}
@@ -0,0 +1,74 @@
+use std::{io::Cursor, ops::{Deref, DerefMut}, path::Path};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+use crate::{Hive, HiveError, HiveOptions};
+
+// Struct representing a hive ingested through tokio.
+//
+// Parsing a hive is a long series of small seeks and reads (key node, then subkey list, then
+// key node again) and running each of those through the async runtime would cost far more than
+// the parsing itself. Hives are also small enough to hold in memory, typically a few MB and
+// rarely over a few hundred. So the async surface is the ingestion only: the hive is read into
+// a buffer without blocking a runtime thread and then parsed synchronously from memory, where
+// every read is a memcpy. All of the `Hive` API is available through `Deref`.
+pub struct AsyncHive {
+    hive: Hive<Cursor<Vec<u8>>>,
+}
+
+impl AsyncHive {
+    // Function to read a hive file with the default options
+    pub async fn open(hive_path: impl AsRef<Path>) -> Result<AsyncHive, HiveError> {
+        AsyncHive::open_with_options(hive_path, HiveOptions::default()).await
+    }
+
+    // Function to read a hive file with the given options
+    pub async fn open_with_options(hive_path: impl AsRef<Path>, options: HiveOptions) -> Result<AsyncHive, HiveError> {
+        let bytes = tokio::fs::read(hive_path).await?;
+        AsyncHive::from_bytes(bytes, options)
+    }
+
+    // Function to read a hive from any async seekable reader with the default options
+    pub async fn from_reader<R>(reader: R) -> Result<AsyncHive, HiveError>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        AsyncHive::from_reader_with_options(reader, HiveOptions::default()).await
+    }
+
+    // Function to read a hive from any async seekable reader with the given options.
+    // The whole reader is buffered, starting from offset 0 wherever its cursor currently is.
+    pub async fn from_reader_with_options<R>(mut reader: R, options: HiveOptions) -> Result<AsyncHive, HiveError>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        reader.seek(std::io::SeekFrom::Start(0)).await?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        AsyncHive::from_bytes(bytes, options)
+    }
+
+    // Function to unwrap the in-memory hive
+    pub fn into_inner(self) -> Hive<Cursor<Vec<u8>>> {
+        self.hive
+    }
+
+    fn from_bytes(bytes: Vec<u8>, options: HiveOptions) -> Result<AsyncHive, HiveError> {
+        let hive = Hive::from_reader_with_options(Cursor::new(bytes), options)?;
+        Ok(AsyncHive { hive })
+    }
+}
+
+impl Deref for AsyncHive {
+    type Target = Hive<Cursor<Vec<u8>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.hive
+    }
+}
+
+impl DerefMut for AsyncHive {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.hive
+    }
+}
@@ -0,0 +1,31 @@
+// Struct representing the format features a hive may use, which depend on its minor version:
+//
+//   1.0 - 1.2  Windows NT 3.1 and 3.5: index leaves ("li") and index roots ("ri") only
+//   1.3        Windows NT 3.51 and later: fast leaves ("lf") too
+//   1.4        Big data ("db") for values over 16344 bytes
+//   1.5        Windows XP and later: hash leaves ("lh") too
+//   1.6        Windows 10 containers: layered keys, for differencing hives
+//
+// Each version keeps the features of the ones before it. Older hives loaded by newer Windows
+// are written back in their original version, so a feature's absence means it can't occur
+// (big data in a 1.3 hive is read as an ordinary value cell), while its presence only means it
+// may.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HiveCapabilities {
+    pub fast_leaves: bool,
+    pub big_data: bool,
+    pub hash_leaves: bool,
+    pub layered_keys: bool,
+}
+
+impl HiveCapabilities {
+    // Function to get the features of a hive format minor version
+    pub fn from_minor_version(minor_version: u32) -> HiveCapabilities {
+        HiveCapabilities {
+            fast_leaves: minor_version >= 3,
+            big_data: minor_version >= 4,
+            hash_leaves: minor_version >= 5,
+            layered_keys: minor_version >= 6,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::{HiveError, KeyNode, KeyValue, HIVE_BINS_OFFSET};
+
+// Enum for a cell of the hive bins data, parsed according to its signature
+#[derive(Debug, Clone)]
+pub enum Cell {
+    KeyNode { name: String, key_node: KeyNode },
+    KeyValue { name: String, key_value: KeyValue },
+    // li, lf, lh or ri list; `offsets` are the list's elements (key nodes, or further lists for ri)
+    SubkeyList { signature: [u8; 2], offsets: Vec<u32> },
+    SecurityDescriptor { flink: u32, blink: u32, reference_count: u32, descriptor: Vec<u8> },
+    BigData { segment_count: u16, segment_list_offset: u32 },
+    // Cells without a signature: value data, value lists, class names and big data segments
+    ValueData(Vec<u8>),
+    // Cells whose first two bytes look like a record signature but not one we know
+    Unknown { signature: [u8; 2] },
+}
+
+// Struct representing the 4-byte header in front of every cell: the cell's size, header included,
+// stored negated while the cell is allocated and positive once it has been freed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellHeader {
+    size: i32,
+}
+
+impl CellHeader {
+    // Function to interpret the 4 bytes at the start of a cell
+    pub fn from_bytes(bytes: [u8; 4]) -> CellHeader {
+        CellHeader {
+            size: i32::from_le_bytes(bytes),
+        }
+    }
+
+    // Function to check whether the cell is in use. A free cell can still hold the record of a
+    // deleted key or value, so only allocated cells are live data.
+    pub fn is_allocated(&self) -> bool {
+        self.size < 0
+    }
+
+    // Function to get the cell's length in bytes, header included, whether allocated or free
+    pub fn size(&self) -> u32 {
+        self.size.unsigned_abs()
+    }
+
+    // Function to get the number of bytes the cell holds after its header
+    pub fn data_size(&self) -> u32 {
+        self.size().saturating_sub(4)
+    }
+}
+
+// Function to read the header of the cell at an offset in the hive bins data, leaving the file
+// positioned at the cell's data
+pub(crate) fn read_cell_header<R: Read + Seek>(file: &mut R, offset: u32) -> Result<CellHeader, std::io::Error> {
+    let mut header_bytes = [0u8; 4];
+    file.seek(SeekFrom::Start(HIVE_BINS_OFFSET + offset as u64))?;
+    file.read_exact(&mut header_bytes)?;
+    Ok(CellHeader::from_bytes(header_bytes))
+}
+
+// Function to parse a cell whose data isn't a key node or key value (those are read through the
+// hive so names are decoded consistently). `offset` is only used in error reports.
+pub(crate) fn parse_cell_data(offset: u32, data: &[u8]) -> Result<Cell, HiveError> {
+    let truncated = || HiveError::InvalidCell { offset };
+    let u16_at = |at: usize| data.get(at..at + 2).map(|field| u16::from_le_bytes([field[0], field[1]]));
+    let u32_at = |at: usize| {
+        data.get(at..at + 4)
+            .map(|field| u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+    };
+
+    let signature = match data.get(..2) {
+        Some(&[first, second]) => [first, second],
+        _ => return Ok(Cell::ValueData(data.to_vec())),
+    };
+
+    match &signature {
+        b"li" | b"lf" | b"lh" | b"ri" => {
+            let count = u16_at(2).ok_or_else(truncated)? as usize;
+            // Fast and hash leaves pair every key node offset with a 4-byte name hint or hash
+            let element_size = if matches!(&signature, b"lf" | b"lh") { 8 } else { 4 };
+            let elements = data.get(4..4 + count * element_size).ok_or_else(truncated)?;
+            let offsets = elements
+                .chunks_exact(element_size)
+                .map(|element| u32::from_le_bytes([element[0], element[1], element[2], element[3]]))
+                .collect();
+            Ok(Cell::SubkeyList { signature, offsets })
+        }
+        b"sk" => {
+            let descriptor_size = u32_at(16).ok_or_else(truncated)? as usize;
+            let descriptor_end = 20usize.checked_add(descriptor_size).ok_or_else(truncated)?;
+            let descriptor = data.get(20..descriptor_end).ok_or_else(truncated)?;
+            Ok(Cell::SecurityDescriptor {
+                flink: u32_at(4).ok_or_else(truncated)?,
+                blink: u32_at(8).ok_or_else(truncated)?,
+                reference_count: u32_at(12).ok_or_else(truncated)?,
+                descriptor: descriptor.to_vec(),
+            })
+        }
+        b"db" => Ok(Cell::BigData {
+            segment_count: u16_at(2).ok_or_else(truncated)?,
+            segment_list_offset: u32_at(4).ok_or_else(truncated)?,
+        }),
+        // Record signatures are always two lowercase ASCII letters; anything else is raw data
+        [first, second] if first.is_ascii_lowercase() && second.is_ascii_lowercase() => {
+            Ok(Cell::Unknown { signature })
+        }
+        _ => Ok(Cell::ValueData(data.to_vec())),
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+
+use crate::RegistryValue;
+
+// Struct representing the differences between two subtrees, from `Hive::diff_subtrees`, in
+// path order. Paths are relative to the two subtree roots and names are matched
+// case-insensitively, so a key or value that only changed case is not a difference.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HiveDiff {
+    pub entries: Vec<DiffEntry>,
+}
+
+impl HiveDiff {
+    // Function to check whether the two subtrees hold the same keys and values
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+// Enum for one difference between subtree `a` and subtree `b`. A key only on one side is
+// reported along with every key below it; the values of such keys are not listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffEntry {
+    KeyAdded { path: String },   // Only in `b`
+    KeyRemoved { path: String }, // Only in `a`
+    ValueAdded { path: String, name: String, value: RegistryValue },
+    ValueRemoved { path: String, name: String, value: RegistryValue },
+    ValueChanged { path: String, name: String, old: RegistryValue, new: RegistryValue },
+}
+
+// The keys of one subtree by uppercased path, each with its stored path and its values by
+// uppercased name
+pub(crate) type Snapshot = BTreeMap<String, (String, BTreeMap<String, (String, RegistryValue)>)>;
+
+// Function to compare two subtree snapshots
+pub(crate) fn diff_snapshots(mut a: Snapshot, mut b: Snapshot) -> HiveDiff {
+    let mut paths: Vec<String> = a.keys().chain(b.keys()).cloned().collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut entries = Vec::new();
+    for path in paths {
+        match (a.remove(&path), b.remove(&path)) {
+            (Some((path, _)), None) => entries.push(DiffEntry::KeyRemoved { path }),
+            (None, Some((path, _))) => entries.push(DiffEntry::KeyAdded { path }),
+            (Some((_, mut a_values)), Some((path, mut b_values))) => {
+                let mut names: Vec<String> = a_values.keys().chain(b_values.keys()).cloned().collect();
+                names.sort();
+                names.dedup();
+                for name in names {
+                    let path = path.clone();
+                    match (a_values.remove(&name), b_values.remove(&name)) {
+                        (Some((name, value)), None) => entries.push(DiffEntry::ValueRemoved { path, name, value }),
+                        (None, Some((name, value))) => entries.push(DiffEntry::ValueAdded { path, name, value }),
+                        (Some((_, old)), Some((name, new))) if old != new => {
+                            entries.push(DiffEntry::ValueChanged { path, name, old, new })
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    HiveDiff { entries }
+}
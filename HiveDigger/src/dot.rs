@@ -0,0 +1,23 @@
+// Formatting of keys for Graphviz DOT graphs of a hive's layout
+
+// Function to get the DOT node ID of the key node at a cell offset. Offsets are unique within a
+// hive, unlike key names, so a key reached twice through a cross-link stays one node.
+pub(crate) fn node_id(offset: u32) -> String {
+    format!("\"k{:x}\"", offset)
+}
+
+// Function to render a key name as a quoted DOT label, with the value count on a second line
+// when one is given. Control characters are shown escaped like `display_name` does.
+pub(crate) fn label(name: &str, value_count: Option<u32>) -> String {
+    let mut label = escape(&crate::display_name(name));
+    if let Some(value_count) = value_count {
+        let plural = if value_count == 1 { "" } else { "s" };
+        label.push_str(&format!("\\n{} value{}", value_count, plural));
+    }
+    format!("\"{}\"", label)
+}
+
+// Function to escape text for a double-quoted DOT string
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
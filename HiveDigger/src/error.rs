@@ -0,0 +1,164 @@
+use std::fmt;
+
+use crate::{value::type_name, HiveKind};
+
+// Enum for the errors returned by the Hive API
+#[derive(Debug)]
+pub enum HiveError {
+    Io(std::io::Error),
+    // Reading a record failed, e.g. because the hive is truncated. `record` names what was being
+    // read ("nk", "subkey list", ...) and `offset` is its cell offset in the hive bins data
+    // (the base block is reported at offset 0).
+    Read {
+        record: &'static str,
+        offset: u32,
+        source: std::io::Error,
+    },
+    // No key exists at the requested path
+    KeyNotFound(String),
+    // The key exists but has no value with the requested name
+    ValueNotFound { key_path: String, value_name: String },
+    // A hive bin header is missing or inconsistent (offset is relative to the hive bins data)
+    InvalidBin { offset: u32 },
+    // A cell size would step outside its bin or stall the scan (offset is relative to the hive bins data)
+    InvalidCell { offset: u32 },
+    // Offset arithmetic would go past u32::MAX (or past usize::MAX on 32-bit targets)
+    OffsetOverflow { offset: u32 },
+    // The data cell at offset `a` runs into the allocated cell at offset `b` (strict mode only)
+    OverlappingCell { a: u32, b: u32 },
+    // The cell at an offset is free or isn't the kind of record expected there ("nk", ...)
+    UnexpectedCell { offset: u32, expected: &'static str },
+    // The root cell offset doesn't point to a key node flagged as the hive's root
+    InvalidRootKey { offset: u32 },
+    // The base block's stored checksum doesn't match the one computed over its first 508 bytes
+    InvalidChecksum { stored: u32, computed: u32 },
+    // `HiveOptions::recovery` couldn't salvage a hive whose base block is damaged
+    RecoveryFailed(&'static str),
+    // The base block's file format is not 1 (direct memory load); carries the value found
+    UnsupportedFileFormat(u32),
+    // The file isn't a primary hive, e.g. a transaction log or a differencing hive (see `HiveKind`)
+    UnsupportedHiveType(HiveKind),
+    // The base block's clustering factor is not a plausible sector size multiplier
+    InvalidClusteringFactor(u32),
+    // A key node counts values but has no value list
+    InconsistentValueList { number_of_key_values: u32 },
+    // Value data doesn't match the layout expected for its type
+    InvalidValueData(String),
+    // A big data segment list cell is too small for the segment count in its "db" record
+    // (`capacity` is the cell's data size in bytes)
+    TruncatedSegmentList { offset: u32, segment_count: u16, capacity: u32 },
+    // Following the security descriptor list's forward links came back to the record at
+    // `offset` without returning to where the walk started
+    SecurityListCycle { offset: u32 },
+    // A value read through one of the typed helpers (`Hive::value_as_u32`, ...) has another
+    // REG_* type, or an integer type with data of the wrong size. `expected` describes the types
+    // accepted and `found` is the value's type.
+    TypeMismatch { expected: &'static str, found: u32 },
+    // A value list or subkey list declares more entries than its cell can hold (`capacity` is
+    // the cell's data size in bytes)
+    CountExceedsCell { offset: u32, count: u32, capacity: u32 },
+    // A value's type isn't one of the documented REG_* types (strict mode only); carries the type
+    UnknownValueType(u32),
+    // A walk reached the `HiveOptions::max_keys` limit and was stopped
+    BudgetExceeded { max_keys: u64 },
+    // A walk or export can't resume after the key node at `offset`: it isn't a key reachable
+    // from the root through the subkey lists of its parents
+    InvalidCheckpoint { offset: u32 },
+    // No hive bin holds a cell at the offset: it is past the hive bins data or inside a bin header
+    OffsetOutsideBins { offset: u32 },
+}
+
+impl fmt::Display for HiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HiveError::Io(e) => write!(f, "{}", e),
+            HiveError::Read { record, offset, source } => {
+                write!(f, "Failed reading {} at {:#x}: {}", record, offset, source)
+            }
+            HiveError::KeyNotFound(path) => write!(f, "Key \"{}\" not found", path),
+            HiveError::ValueNotFound { key_path, value_name } => {
+                write!(f, "Value \"{}\" not found in key \"{}\"", value_name, key_path)
+            }
+            HiveError::InvalidBin { offset } => write!(f, "Invalid hive bin at offset {:#x}", offset),
+            HiveError::InvalidCell { offset } => write!(f, "Invalid cell size at offset {:#x}", offset),
+            HiveError::OffsetOverflow { offset } => write!(f, "Offset arithmetic overflowed at offset {:#x}", offset),
+            HiveError::OverlappingCell { a, b } => {
+                write!(f, "Data cell at offset {:#x} overlaps allocated cell at offset {:#x}", a, b)
+            }
+            HiveError::UnexpectedCell { offset, expected } => {
+                write!(f, "Expected an allocated {} cell at offset {:#x}", expected, offset)
+            }
+            HiveError::InvalidRootKey { offset } => write!(f, "Invalid root key at offset {:#x}", offset),
+            HiveError::InvalidChecksum { stored, computed } => {
+                write!(f, "Invalid base block checksum {:#010x} (computed {:#010x})", stored, computed)
+            }
+            HiveError::RecoveryFailed(reason) => write!(f, "Hive recovery failed: {}", reason),
+            HiveError::UnsupportedFileFormat(file_format) => {
+                write!(f, "Unsupported file format {} (expected 1, direct memory load)", file_format)
+            }
+            HiveError::UnsupportedHiveType(hive_kind) => match hive_kind {
+                HiveKind::TransactionLog(_) => write!(f, "File is a transaction log, not a primary hive"),
+                HiveKind::Other(file_type) => write!(
+                    f,
+                    "Unsupported file type {} (expected 0, a primary hive; layered hives can't be read on their own)",
+                    file_type
+                ),
+                HiveKind::Primary => write!(f, "Unsupported hive type {:?}", hive_kind),
+            },
+            HiveError::InvalidClusteringFactor(clustering_factor) => {
+                write!(f, "Invalid clustering factor {}", clustering_factor)
+            }
+            HiveError::InconsistentValueList { number_of_key_values } => {
+                write!(f, "Key node has {} values but no value list", number_of_key_values)
+            }
+            HiveError::InvalidValueData(message) => write!(f, "Invalid value data: {}", message),
+            HiveError::TruncatedSegmentList { offset, segment_count, capacity } => write!(
+                f,
+                "Big data segment list at offset {:#x} holds {} bytes, too few for {} segments",
+                offset, capacity, segment_count
+            ),
+            HiveError::TypeMismatch { expected, found } => match type_name(*found) {
+                Some(found) => write!(f, "Expected a {} value, found {}", expected, found),
+                None => write!(f, "Expected a {} value, found type {}", expected, found),
+            },
+            HiveError::CountExceedsCell { offset, count, capacity } => write!(
+                f,
+                "List cell at offset {:#x} holds {} bytes, too few for {} entries",
+                offset, capacity, count
+            ),
+            HiveError::UnknownValueType(data_type) => write!(f, "Unknown value type {:#010x}", data_type),
+            HiveError::BudgetExceeded { max_keys } => write!(f, "Stopped after walking the limit of {} keys", max_keys),
+            HiveError::InvalidCheckpoint { offset } => {
+                write!(f, "Can't resume after offset {:#x}, which isn't a key reachable from the root", offset)
+            }
+            HiveError::OffsetOutsideBins { offset } => write!(f, "No hive bin holds a cell at offset {:#x}", offset),
+            HiveError::SecurityListCycle { offset } => {
+                write!(f, "Security descriptor list loops back to offset {:#x} without closing", offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HiveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HiveError::Io(e) => Some(e),
+            HiveError::Read { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl HiveError {
+    // Function to build a `map_err` adapter that records what was being read where, e.g.
+    // `read_key_node(...).map_err(HiveError::reading("nk", offset))`
+    pub(crate) fn reading(record: &'static str, offset: u32) -> impl FnOnce(std::io::Error) -> HiveError {
+        move |source| HiveError::Read { record, offset, source }
+    }
+}
+
+impl From<std::io::Error> for HiveError {
+    fn from(e: std::io::Error) -> HiveError {
+        HiveError::Io(e)
+    }
+}
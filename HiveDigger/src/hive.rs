@@ -0,0 +1,2565 @@
+use std::{
+    borrow::Cow,
+    collections::{btree_map, BTreeMap, HashSet},
+    fs::File,
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+    mem,
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    cell,
+    cell::{read_cell_header, Cell, CellHeader},
+    cell_data_offset, diff,
+    diff::HiveDiff,
+    dot, extract_key_value_data, offset_add,
+    offset_reader::OffsetReader,
+    provenance::OpenManifest,
+    read_base_block, read_big_data_segments, read_bytes, read_class_name, read_class_name_bytes, read_named_key_node, read_named_key_value, read_subkey_offsets,
+    read_value_offsets, recovery::{find_root_cell_offset, recover_base_block}, reg,
+    reg::RegFormat,
+    report,
+    report::{ParseReport, WarningKind},
+    search,
+    search::{SearchMatch, SearchOptions, SearchPattern},
+    security::SecurityDescriptor,
+    stats::{HeaderSummary, HiveStatistics, LargestValue},
+    time,
+    time::TimeRange,
+    validate::{ValidationIssue, ValidationReport},
+    value_reader::ValueReader,
+    BaseBlock, HiveBinHeader, HiveCapabilities, HiveError, HiveKind, KeyNode, KeyNodeFlags, KeyValue, Located, RegistryValue, BIG_DATA_SEGMENT_SIZE, HIVE_BINS_OFFSET,
+};
+
+// Windows refuses to nest keys deeper than this, so a deeper tree means a cycle or corruption
+const MAX_KEY_DEPTH: usize = 512;
+
+// A key's path relative to the root and its node, as yielded by `Hive::walk`
+pub type WalkEntry = (String, Located<KeyNode>);
+
+// Struct representing the options that control how a hive is read
+#[derive(Debug, Clone, Default)]
+pub struct HiveOptions {
+    // When set, an error reading one branch is yielded by `subkeys`/`walk` and traversal carries
+    // on with its siblings. Otherwise the iterator ends after yielding the first error.
+    pub skip_errors: bool,
+    // When set and the base block is damaged (bad signature or checksum), the hive is salvaged
+    // by scanning for hive bins and the root key instead of failing to open. A base block that
+    // checks out but has a file format, file type or clustering factor this crate can't read is
+    // still refused, since rebuilding it would misparse the file. A base block whose root cell
+    // offset doesn't lead to the root key keeps its other fields and gets the root found by the
+    // scan. `Hive::recovered` reports whether either happened, and
+    // `Hive::validate` lists a relocated root as `ValidationIssue::RootKeyRelocated`.
+    pub recovery: bool,
+    // When set, value data cells are checked against the map of allocated cells and data that
+    // runs into another live cell (a sign of corruption or tampering) is rejected with
+    // `HiveError::OverlappingCell`. Building the map costs a scan of every hive bin. Decoding a
+    // value of a type outside the REG_* set also fails, with `HiveError::UnknownValueType`,
+    // instead of giving `RegistryValue::Unknown`.
+    pub strict: bool,
+    // Label for the root key in full paths, e.g. "HKEY_LOCAL_MACHINE\\SYSTEM" for a SYSTEM hive,
+    // so paths read like regedit's. Paths from `walk`, `search` and the like stay relative to the
+    // root (which is ""); `Hive::full_path` puts them under the label, and `open_key` accepts
+    // paths that start with it. The root's own stored name is never used in paths.
+    pub root_label: Option<String>,
+    // Most keys a single walk may visit, as a guard against hives from untrusted sources that
+    // are valid but enormous. A walk that reaches the limit yields `HiveError::BudgetExceeded`
+    // and ends, even with `skip_errors`. Everything built on walks (`statistics`, `search`,
+    // `export_reg`, ...) is bounded by it. None (the default) means no limit.
+    pub max_keys: Option<u64>,
+}
+
+// Struct representing an open registry hive
+#[derive(Clone)]
+pub struct Hive<R = File> {
+    file: R,
+    base_block: BaseBlock,
+    options: HiveOptions,
+    root: Option<(String, Located<KeyNode>)>,
+    provenance: OpenManifest, // What was checked and repaired at open, for `provenance`
+    allocated_cells: Option<Vec<(u32, u32)>>, // (offset, length) of each allocated cell, for strict mode
+    bin_index: Option<Vec<HiveBinHeader>>,     // Header of every bin in offset order, for `bin_at`
+}
+
+impl Hive<File> {
+    // Function to open a hive file with the default options
+    pub fn open(hive_path: &Path) -> Result<Hive<File>, HiveError> {
+        Hive::open_with_options(hive_path, HiveOptions::default())
+    }
+
+    // Function to open a hive file with the given options
+    pub fn open_with_options(hive_path: &Path, options: HiveOptions) -> Result<Hive<File>, HiveError> {
+        Hive::from_reader_with_options(File::open(hive_path)?, options)
+    }
+
+    // Function to get a second handle on the same hive file, e.g. to run one lookup while a walk
+    // is in progress. The clone shares the parsed header and options but has its own `Hive` state.
+    // Both handles refer to the same open file, including its read position; every read seeks
+    // first, so interleaving calls on one thread is fine, but use `SharedHive` to read from
+    // several threads at once.
+    pub fn try_clone(&self) -> Result<Hive<File>, HiveError> {
+        Ok(Hive {
+            file: self.file.try_clone()?,
+            base_block: self.base_block,
+            options: self.options.clone(),
+            root: self.root.clone(),
+            provenance: self.provenance.clone(),
+            allocated_cells: self.allocated_cells.clone(),
+            bin_index: self.bin_index.clone(),
+        })
+    }
+}
+
+// Hives parsed straight from bytes the caller already holds, e.g. mapped from a disk image.
+// Nothing is copied or read from elsewhere, and the borrowed accessors (`subkey_names_borrowed`,
+// ...) hand out slices of the caller's buffer.
+impl<'a> Hive<Cursor<&'a [u8]>> {
+    // Function to parse a hive from a byte slice with the default options
+    pub fn from_bytes(data: &'a [u8]) -> Result<Hive<Cursor<&'a [u8]>>, HiveError> {
+        Hive::from_bytes_with_options(data, HiveOptions::default())
+    }
+
+    // Function to parse a hive from a byte slice with the given options
+    pub fn from_bytes_with_options(data: &'a [u8], options: HiveOptions) -> Result<Hive<Cursor<&'a [u8]>>, HiveError> {
+        Hive::from_reader_with_options(Cursor::new(data), options)
+    }
+
+    // Function to get the bytes the hive was parsed from, for the full borrow lifetime
+    pub fn bytes(&self) -> &'a [u8] {
+        self.file.get_ref()
+    }
+}
+
+// Hives embedded at an offset in a larger source, e.g. found in a disk image or memory dump
+impl<R: Read + Seek> Hive<OffsetReader<R>> {
+    // Function to read a hive that starts `base_offset` bytes into a reader, with the default options
+    pub fn from_reader_at(reader: R, base_offset: u64) -> Result<Hive<OffsetReader<R>>, HiveError> {
+        Hive::from_reader_at_with_options(reader, base_offset, HiveOptions::default())
+    }
+
+    // Function to read a hive that starts `base_offset` bytes into a reader, with the given options
+    pub fn from_reader_at_with_options(
+        reader: R,
+        base_offset: u64,
+        options: HiveOptions,
+    ) -> Result<Hive<OffsetReader<R>>, HiveError> {
+        Hive::from_reader_with_options(OffsetReader::new(reader, base_offset), options)
+    }
+}
+
+impl<R: Read + Seek> Hive<R> {
+    // Function to read a hive from any seekable reader with the default options
+    pub fn from_reader(reader: R) -> Result<Hive<R>, HiveError> {
+        Hive::from_reader_with_options(reader, HiveOptions::default())
+    }
+
+    // Function to read a hive from any seekable reader with the given options
+    pub fn from_reader_with_options(mut reader: R, options: HiveOptions) -> Result<Hive<R>, HiveError> {
+        let (mut base_block, base_block_rebuilt) = match Hive::read_valid_base_block(&mut reader) {
+            Ok(base_block) => (base_block, false),
+            // A base block that checks out but describes a file that can't be read as it stands
+            // is refused: rebuilding it as an ordinary primary hive would misparse the file
+            Err(e) if options.recovery && Self::base_block_damaged(&e) => (recover_base_block(&mut reader)?, true),
+            Err(e) => return Err(e),
+        };
+        // A base block that checks out can still have lost its root cell offset, e.g. zeroed in a
+        // carved hive. When no root turns up either, opening the root fails as it would have.
+        let mut root_relocated = false;
+        if options.recovery && !base_block_rebuilt && !is_root_key_at(&mut reader, base_block.root_cell_offset) {
+            if let Some(root_cell_offset) = find_root_cell_offset(&mut reader, base_block.hive_bins_data_size)? {
+                base_block.root_cell_offset = root_cell_offset;
+                root_relocated = true;
+            }
+        }
+
+        let stored_base_block = read_base_block(&mut reader).ok();
+        let provenance = OpenManifest {
+            checksum_valid: stored_base_block.map(|stored| stored.checksum == stored.computed_checksum()),
+            dirty: stored_base_block.map(|stored| stored.primary_seq_num != stored.secondary_seq_num),
+            recovery_enabled: options.recovery,
+            base_block_rebuilt,
+            root_relocated,
+            strict: options.strict,
+            skip_errors: options.skip_errors,
+        };
+
+        Ok(Hive {
+            file: reader,
+            base_block,
+            options,
+            root: None,
+            provenance,
+            allocated_cells: None,
+            bin_index: None,
+        })
+    }
+
+    // Function to read the base block and check its checksum and fields
+    fn read_valid_base_block(reader: &mut R) -> Result<BaseBlock, HiveError> {
+        let base_block = read_base_block(reader).map_err(HiveError::reading("base block", 0))?;
+
+        let (stored, computed) = (base_block.checksum, base_block.computed_checksum());
+        if stored != computed {
+            return Err(HiveError::InvalidChecksum { stored, computed });
+        }
+
+        //Check file format, ensure it's 1 (direct memory load)
+        if base_block.file_format != 1 {
+            return Err(HiveError::UnsupportedFileFormat(base_block.file_format));
+        }
+
+        if base_block.hive_kind() != HiveKind::Primary {
+            return Err(HiveError::UnsupportedHiveType(base_block.hive_kind()));
+        }
+
+        if base_block.sector_size().is_none() {
+            return Err(HiveError::InvalidClusteringFactor(base_block.clustering_factor));
+        }
+
+        Ok(base_block)
+    }
+
+    // Function to check whether opening failed because the base block is damaged (bad signature
+    // or checksum), the only failures `HiveOptions::recovery` rebuilds it after
+    fn base_block_damaged(error: &HiveError) -> bool {
+        match error {
+            HiveError::Read { source, .. } => source.kind() == std::io::ErrorKind::InvalidData,
+            HiveError::InvalidChecksum { .. } => true,
+            _ => false,
+        }
+    }
+
+    // Function to check whether the base block was damaged and rebuilt under `HiveOptions::recovery`
+    pub fn recovered(&self) -> bool {
+        self.provenance.recovered()
+    }
+
+    // Function to get the record of what happened while the hive was opened: the stored base
+    // block's checksum and dirty state, and any recovery, see `OpenManifest`
+    pub fn provenance(&self) -> &OpenManifest {
+        &self.provenance
+    }
+
+    // Function to get the reader the hive is parsed from, for reading records this crate doesn't
+    // model. Positions are the hive's own, also for `from_reader_at`: 0 is the base block and the
+    // hive bins data starts at 4096, so a cell offset `o` is at 4096 + `o`. The handle shares the
+    // hive's cursor (and, for a `File`, so do handles from `try_clone`); every read by the `Hive`
+    // seeks first, so moving it between calls is harmless, but nothing else is synchronised.
+    pub fn as_reader(&mut self) -> &mut R {
+        &mut self.file
+    }
+
+    pub fn options(&self) -> &HiveOptions {
+        &self.options
+    }
+
+    // Function to get the hive format version (major, minor) recorded in the base block, e.g. (1, 5)
+    pub fn version(&self) -> (u32, u32) {
+        (self.base_block.major_version, self.base_block.minor_version)
+    }
+
+    // Function to get the format features the hive's minor version allows, see `HiveCapabilities`
+    pub fn capabilities(&self) -> HiveCapabilities {
+        HiveCapabilities::from_minor_version(self.base_block.minor_version)
+    }
+
+    // Function to check whether the hive is dirty: its primary and secondary sequence numbers
+    // differ, so a write was interrupted and the transaction log may hold newer data
+    pub fn is_dirty(&self) -> bool {
+        self.base_block.primary_seq_num != self.base_block.secondary_seq_num
+    }
+
+    // Function to get the primary sequence number, incremented when Windows starts writing the hive
+    pub fn primary_seq_num(&self) -> u32 {
+        self.base_block.primary_seq_num
+    }
+
+    // Function to get the secondary sequence number, set equal to the primary one once the write
+    // has completed
+    pub fn secondary_seq_num(&self) -> u32 {
+        self.base_block.secondary_seq_num
+    }
+
+    // Function to get the last-written time recorded in the base block
+    pub fn last_written(&self) -> DateTime<Utc> {
+        time::filetime_to_datetime(self.base_block.last_written_timestamp)
+    }
+
+    // Function to gather the base block's fields into one report. The checksum is checked
+    // against the base block as stored, since a recovered one is rebuilt with a valid checksum;
+    // the other fields are those in use.
+    pub fn header_summary(&mut self) -> Result<HeaderSummary, HiveError> {
+        let stored_base_block = read_base_block(&mut self.file).map_err(HiveError::reading("base block", 0))?;
+        let checksum = stored_base_block.checksum;
+        Ok(HeaderSummary {
+            major_version: self.base_block.major_version,
+            minor_version: self.base_block.minor_version,
+            primary_seq_num: self.primary_seq_num(),
+            secondary_seq_num: self.secondary_seq_num(),
+            dirty: self.is_dirty(),
+            last_written: self.last_written(),
+            root_cell_offset: self.base_block.root_cell_offset,
+            hive_bins_data_size: self.base_block.hive_bins_data_size,
+            checksum,
+            checksum_valid: checksum == stored_base_block.computed_checksum(),
+            file_name: self.file_name(),
+            boot_type: self.boot_type(),
+            boot_recover: self.boot_recover(),
+        })
+    }
+
+    // Function to get the file name stored in the base block, usually the tail of the hive's
+    // path on the system that wrote it (e.g. "\SystemRoot\System32\Config\SYSTEM" truncated)
+    pub fn file_name(&self) -> String {
+        let file_name = self.base_block.file_name;
+        let file_name: Vec<u16> = file_name.iter().copied().take_while(|&character| character != 0).collect();
+        String::from_utf16_lossy(&file_name)
+    }
+
+    // Function to get the clustering factor (logical sector size / 512) recorded in the base block
+    pub fn clustering_factor(&self) -> u32 {
+        self.base_block.clustering_factor
+    }
+
+    // Function to get the boot type recorded at the end of the base block. The boot loader and
+    // kernel use it to manage hive recovery in memory and it is normally 0 on disk (regular
+    // boot); 4 marks a hive the kernel self-healed. Any nonzero value on disk is unusual.
+    pub fn boot_type(&self) -> u32 {
+        self.base_block.boot_type
+    }
+
+    // Function to get the boot recovery state recorded at the end of the base block: 0 when the
+    // hive loaded cleanly, 1 when it was recovered from its transaction log at boot and 2 when it
+    // was recovered from an alternate hive. Like `boot_type`, normally 0 on disk.
+    pub fn boot_recover(&self) -> u32 {
+        self.base_block.boot_recover
+    }
+
+    // Function to get the root key node, read and validated on first use and cached afterwards
+    pub fn root(&mut self) -> Result<Located<KeyNode>, HiveError> {
+        let (_, root_key_node) = self.read_root()?;
+        Ok(root_key_node)
+    }
+
+    // Function to get the name of the root key. Windows gives it the name of the hive's internal
+    // root (e.g. "ROOT" or "CMI-CreateHive{...}"), though some tools write an empty name.
+    pub fn root_name(&mut self) -> Result<String, HiveError> {
+        let (root_name, _) = self.read_root()?;
+        Ok(root_name)
+    }
+
+    // Function to read the stored name of a key node. Matching on this rather than on the last
+    // component of a path keeps names that contain a backslash intact.
+    fn key_name(&mut self, key_node: &Located<KeyNode>) -> Result<String, HiveError> {
+        let (key_name, _) =
+            read_named_key_node(&mut self.file, key_node.offset).map_err(HiveError::reading("nk", key_node.offset))?;
+        Ok(key_name)
+    }
+
+    // Function to iterate over the direct subkeys of a key node, yielding each name and node.
+    // Predefined-handle keys (see `KeyNode::predefined_handle`) have no subkeys in the hive.
+    pub fn subkeys(&mut self, key_node: &KeyNode) -> Subkeys<'_, R> {
+        let (offsets, pending_error) = match self.subkey_offsets(key_node) {
+            Ok(offsets) => (offsets, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+
+        Subkeys {
+            hive: self,
+            offsets: offsets.into_iter(),
+            pending_error,
+            finished: false,
+        }
+    }
+
+    // Function to list the names of the direct subkeys of a key node in stored order
+    // (empty for a key without subkeys)
+    pub fn subkey_names(&mut self, key_node: &KeyNode) -> Result<Vec<String>, HiveError> {
+        self.subkeys(key_node).map(|subkey| subkey.map(|(name, _)| name)).collect()
+    }
+
+    // Function to look up a direct subkey by name, comparing case-insensitively, for keys that
+    // may or may not be there. A missing subkey is Ok(None); only I/O and corruption problems
+    // met while looking are errors.
+    pub fn subkey(&mut self, key_node: &KeyNode, name: &str) -> Result<Option<Located<KeyNode>>, HiveError> {
+        self.find_subkey_named(key_node, name, names_match)
+    }
+
+    // Function to get the number of subkeys a key node declares, without reading its subkey list.
+    // Keys without a list (and predefined-handle keys) count 0. This is the stored count, which a
+    // damaged hive can get wrong; `subkeys` enumerates the list itself, and `validate` reports
+    // keys where the two disagree.
+    pub fn count_subkeys(&self, key_node: &KeyNode) -> u32 {
+        if key_node.subkeys_list_offset == 0xFFFFFFFF || key_node.predefined_handle().is_some() {
+            return 0;
+        }
+        key_node.number_of_subkeys
+    }
+
+    // Function to get the number of values a key node declares, without reading its value list.
+    // Keys without a list (and predefined-handle keys) count 0, though `values` fails on a key
+    // that declares values but has no list.
+    pub fn count_values(&self, key_node: &KeyNode) -> u32 {
+        if key_node.key_values_list_offset == 0xFFFFFFFF || key_node.predefined_handle().is_some() {
+            return 0;
+        }
+        key_node.number_of_key_values
+    }
+
+    // Function to walk the list of security descriptors, starting with the one a key node uses
+    // and following the forward links around the circular list until it comes back to it, so
+    // every "sk" record in the hive is yielded once. Keys that share a descriptor have the same
+    // first offset. A list that loops without coming back fails with `SecurityListCycle`.
+    pub fn security_descriptors(&mut self, key_node: &KeyNode) -> SecurityDescriptors<'_, R> {
+        let start = key_node.key_security_offset;
+        SecurityDescriptors {
+            hive: self,
+            start,
+            next: if start == 0xFFFFFFFF { None } else { Some(start) },
+            visited: HashSet::new(),
+        }
+    }
+
+    // Function to iterate over the values of a key node in stored order, yielding each name and value.
+    // Predefined-handle keys (see `KeyNode::predefined_handle`) have no values in the hive.
+    pub fn values(&mut self, key_node: &KeyNode) -> Values<'_, R> {
+        let (offsets, pending_error) = match self.value_offsets(key_node) {
+            Ok(offsets) => (offsets, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+
+        Values {
+            hive: self,
+            offsets: offsets.into_iter(),
+            pending_error,
+            finished: false,
+        }
+    }
+
+    // Function to read a key node's class name, or None if it has none. The SYSTEM hive hides the
+    // boot key in the class names of the JD, Skew1, GBG and Data keys under Control\Lsa.
+    pub fn class_name(&mut self, key_node: &KeyNode) -> Result<Option<String>, HiveError> {
+        read_class_name(&mut self.file, key_node).map_err(HiveError::reading("class name", key_node.class_name_offset))
+    }
+
+    // Function to read the stored bytes of a key node's class name without decoding them as
+    // UTF-16, for class data that isn't text. Empty if the key has no class name. The boot key
+    // parts are UTF-16 hex digits; decoding them to the 4 key bytes is left to the caller.
+    pub fn read_class_name_bytes(&mut self, key_node: &KeyNode) -> Result<Vec<u8>, HiveError> {
+        let class_name_bytes = read_class_name_bytes(&mut self.file, key_node)
+            .map_err(HiveError::reading("class name", key_node.class_name_offset))?;
+        Ok(class_name_bytes.unwrap_or_default())
+    }
+
+    // Function to look up one of a key node's values by name like `subkey`, comparing
+    // case-insensitively ("" is the default value). A missing value is Ok(None); a value list or
+    // value that can't be read is an error.
+    pub fn value_opt(&mut self, key_node: &KeyNode, value_name: &str) -> Result<Option<Located<KeyValue>>, HiveError> {
+        for value in self.values(key_node) {
+            let (name, key_value) = value?;
+            if names_match(&name, value_name) {
+                return Ok(Some(key_value));
+            }
+        }
+        Ok(None)
+    }
+
+    // Function to read the raw data of a key value
+    pub fn value_data(&mut self, key_value: &KeyValue) -> Result<Vec<u8>, HiveError> {
+        if self.options.strict {
+            self.check_value_data_cell(key_value)?;
+        }
+        let minor_version = self.base_block.minor_version;
+        extract_key_value_data(&mut self.file, key_value, minor_version)
+    }
+
+    // Function to stream a key value's data instead of buffering it like `value_data`. Big data
+    // is read one segment at a time as the reader is consumed.
+    pub fn value_reader(&mut self, key_value: &KeyValue) -> Result<ValueReader<'_, R>, HiveError> {
+        let data_size = key_value.data_len();
+        let data_offset = key_value.data_offset;
+
+        // Data of up to 4 bytes can be held in the data offset field itself
+        if key_value.is_resident() {
+            let data_bytes = data_offset.to_le_bytes();
+            let resident = &data_bytes[..(data_size as usize).min(data_bytes.len())];
+            return Ok(ValueReader::new(&mut self.file, resident, Vec::new()));
+        }
+        if data_size == 0 {
+            return Ok(ValueReader::new(&mut self.file, &[], Vec::new()));
+        }
+        if !self.is_big_data(data_size) {
+            let segments = vec![(cell_data_offset(data_offset), data_size)];
+            return Ok(ValueReader::new(&mut self.file, &[], segments));
+        }
+
+        let segments = read_big_data_segments(&mut self.file, data_offset, data_size)?
+            .into_iter()
+            .map(|(segment_offset, segment_length)| (cell_data_offset(segment_offset), segment_length))
+            .collect();
+        Ok(ValueReader::new(&mut self.file, &[], segments))
+    }
+
+    // Function to check whether value data of a given size is stored as big data
+    fn is_big_data(&self, data_size: u32) -> bool {
+        data_size > BIG_DATA_SEGMENT_SIZE && self.capabilities().big_data
+    }
+
+    // Function to read a key value's data decoded according to its type. A type outside the
+    // REG_* set decodes to `RegistryValue::Unknown`, or fails with UnknownValueType in strict mode.
+    pub fn read_value(&mut self, key_value: &KeyValue) -> Result<RegistryValue, HiveError> {
+        let data = self.value_data(key_value)?;
+        self.decode_value(key_value.data_type(), &data)
+    }
+
+    // Function to decode value data, rejecting types outside the REG_* set in strict mode
+    fn decode_value(&self, data_type: u32, data: &[u8]) -> Result<RegistryValue, HiveError> {
+        match RegistryValue::decode(data_type, data) {
+            RegistryValue::Unknown { type_id, .. } if self.options.strict => Err(HiveError::UnknownValueType(type_id)),
+            value => Ok(value),
+        }
+    }
+
+    // Function to read and decode all of a key node's values at once, keyed by name ("" is the
+    // default value). If a damaged key lists two values under the same name, the first is kept,
+    // as with `value`. The keys keep their stored case, so look names up in their stored case.
+    pub fn values_map(&mut self, key_node: &KeyNode) -> Result<BTreeMap<String, RegistryValue>, HiveError> {
+        let values = self.values(key_node).collect::<Result<Vec<_>, _>>()?;
+        let mut values_map = BTreeMap::new();
+        for (value_name, key_value) in values {
+            if let btree_map::Entry::Vacant(entry) = values_map.entry(value_name) {
+                entry.insert(self.read_value(&key_value)?);
+            }
+        }
+        Ok(values_map)
+    }
+
+    // Function to build a key and everything below it as one JSON document, for callers that
+    // want to reshape the tree before serializing it. Each key is an object holding its
+    // "last_written" time (RFC 3339), its "values" by name (see `RegistryValue`'s JSON form below)
+    // and its "subkeys" by name, recursively. The whole subtree is held in memory, so this is
+    // meant for a scoped key such as a service or a Run key rather than a whole hive. With
+    // `skip_errors`, keys and values that can't be read are left out.
+    pub fn subtree_to_value(&mut self, key_node: &Located<KeyNode>) -> Result<serde_json::Value, HiveError> {
+        let mut visited = HashSet::from([key_node.offset]);
+        let mut keys_built = 0;
+        self.subtree_value("", key_node, 0, &mut visited, &mut keys_built)
+    }
+
+    // Function to build one key of `subtree_to_value` and, recursively, its subkeys. The tree is
+    // put together from the key nodes themselves, so subkeys whose names differ only in case or
+    // contain a backslash stay apart. It follows the same rules as a walk: at most
+    // MAX_KEY_DEPTH levels, no key node reached twice, and `HiveOptions::max_keys` keys in all.
+    // `path` is only used in error messages.
+    fn subtree_value(
+        &mut self,
+        path: &str,
+        key_node: &Located<KeyNode>,
+        depth: usize,
+        visited: &mut HashSet<u32>,
+        keys_built: &mut u64,
+    ) -> Result<serde_json::Value, HiveError> {
+        if let Some(max_keys) = self.options.max_keys {
+            if *keys_built >= max_keys {
+                return Err(HiveError::BudgetExceeded { max_keys });
+            }
+        }
+        *keys_built += 1;
+        let skip_errors = self.options.skip_errors;
+
+        let mut values = serde_json::Map::new();
+        let key_values: Vec<_> = self.values(key_node).collect();
+        for value in key_values {
+            let (value_name, key_value) = match value {
+                Ok(value) => value,
+                Err(_) if skip_errors => continue,
+                Err(e) => return Err(e),
+            };
+            match self.read_value(&key_value) {
+                Ok(value) => {
+                    values.entry(value_name).or_insert_with(|| value.to_json());
+                }
+                Err(_) if skip_errors => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut subkeys = serde_json::Map::new();
+        let subkey_entries: Vec<_> = self.subkeys(key_node).collect();
+        if !subkey_entries.is_empty() && depth >= MAX_KEY_DEPTH {
+            if !skip_errors {
+                return Err(HiveError::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Key \"{}\" is nested deeper than {} levels", path, MAX_KEY_DEPTH),
+                )));
+            }
+        } else {
+            for subkey in subkey_entries {
+                let (name, subkey_node) = match subkey {
+                    Ok(subkey) => subkey,
+                    Err(_) if skip_errors => continue,
+                    Err(e) => return Err(e),
+                };
+                // A key node reached again means cross-linked or cyclic subkey lists
+                if !visited.insert(subkey_node.offset) {
+                    if skip_errors {
+                        continue;
+                    }
+                    return Err(cross_linked_key(path, subkey_node.offset));
+                }
+
+                let subkey_path = if path.is_empty() { name.clone() } else { format!("{}\\{}", path, name) };
+                match self.subtree_value(&subkey_path, &subkey_node, depth + 1, visited, keys_built) {
+                    Ok(subkey) => {
+                        subkeys.entry(name).or_insert(subkey);
+                    }
+                    Err(e) if skip_errors && !matches!(e, HiveError::BudgetExceeded { .. }) => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(serde_json::json!({
+            "last_written": key_node.last_written().to_rfc3339(),
+            "values": values,
+            "subkeys": subkeys,
+        }))
+    }
+
+    // Function to compare two subtrees of the hive, key by key and value by value, e.g. two
+    // control sets. With `skip_errors`, keys and values that can't be read are left out of the
+    // comparison.
+    pub fn diff_subtrees(&mut self, a: &Located<KeyNode>, b: &Located<KeyNode>) -> Result<HiveDiff, HiveError> {
+        let a = self.diff_snapshot(a)?;
+        let b = self.diff_snapshot(b)?;
+        Ok(diff::diff_snapshots(a, b))
+    }
+
+    // Function to compare two control sets of a SYSTEM hive, e.g. `diff_control_sets(1, 2)` for
+    // ControlSet001 against ControlSet002 (see Select\Current and Select\LastKnownGood for
+    // which is which). Paths in the diff are relative to the control sets.
+    pub fn diff_control_sets(&mut self, a: u32, b: u32) -> Result<HiveDiff, HiveError> {
+        let a = self.open_key(&format!("ControlSet{:03}", a))?;
+        let b = self.open_key(&format!("ControlSet{:03}", b))?;
+        self.diff_subtrees(&a, &b)
+    }
+
+    // Function to read a subtree's keys and decoded values for `diff_subtrees`
+    fn diff_snapshot(&mut self, key_node: &Located<KeyNode>) -> Result<diff::Snapshot, HiveError> {
+        let skip_errors = self.options.skip_errors;
+        let keys = self
+            .walk_from("", key_node)
+            .filter(|key| !skippable(skip_errors, key))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut snapshot = diff::Snapshot::new();
+        for (path, key_node) in keys {
+            let mut values = BTreeMap::new();
+            let key_values: Vec<_> = self.values(&key_node).collect();
+            for value in key_values {
+                let (value_name, key_value) = match value {
+                    Ok(value) => value,
+                    Err(_) if skip_errors => continue,
+                    Err(e) => return Err(e),
+                };
+                match self.read_value(&key_value) {
+                    Ok(value) => {
+                        values.entry(value_name.to_uppercase()).or_insert((value_name, value));
+                    }
+                    Err(_) if skip_errors => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+            snapshot.entry(path.to_uppercase()).or_insert((path, values));
+        }
+        Ok(snapshot)
+    }
+
+    // Function to write the layout of a subtree as a Graphviz DOT graph, e.g. to render with
+    // `dot -Tsvg`: one box per key, labelled with its name (and its value count with
+    // `value_counts`), and an edge from each key to each of its subkeys. `path` is the start
+    // key's path, whose last component labels it (the root is labelled with
+    // `HiveOptions::root_label`, if set). Keys more than `max_depth` levels below the
+    // start key are left out, so 0 draws the start key alone. A key linked from more than one
+    // parent gets an edge from each but is only descended into once. With `skip_errors`,
+    // subkeys that can't be read are left out.
+    pub fn export_dot<W: Write>(
+        &mut self,
+        mut writer: W,
+        path: &str,
+        key_node: &Located<KeyNode>,
+        max_depth: usize,
+        value_counts: bool,
+    ) -> Result<(), HiveError> {
+        let skip_errors = self.options.skip_errors;
+        let value_count = |key_node: &KeyNode| {
+            let number_of_key_values = key_node.number_of_key_values;
+            match (value_counts, key_node.predefined_handle()) {
+                (false, _) => None,
+                (true, Some(_)) => Some(0),
+                (true, None) => Some(number_of_key_values),
+            }
+        };
+
+        writeln!(writer, "digraph hive {{")?;
+        writeln!(writer, "    node [shape=box];")?;
+        let name = match (path.is_empty(), &self.options.root_label) {
+            (true, Some(root_label)) => root_label.as_str(),
+            (true, None) => "(root)",
+            (false, _) => path.rsplit('\\').next().unwrap_or(path),
+        };
+        writeln!(writer, "    {} [label={}];", dot::node_id(key_node.offset), dot::label(name, value_count(key_node)))?;
+
+        let mut visited = HashSet::from([key_node.offset]);
+        let mut stack = vec![(*key_node, 0)];
+        while let Some((parent, depth)) = stack.pop() {
+            if depth == max_depth {
+                continue;
+            }
+            let subkeys: Vec<_> = self.subkeys(&parent).collect();
+            let mut children = Vec::new();
+            for subkey in subkeys {
+                let (name, subkey) = match subkey {
+                    Ok(subkey) => subkey,
+                    Err(_) if skip_errors => continue,
+                    Err(e) => return Err(e),
+                };
+                if visited.insert(subkey.offset) {
+                    writeln!(writer, "    {} [label={}];", dot::node_id(subkey.offset), dot::label(&name, value_count(&subkey)))?;
+                    children.push((subkey, depth + 1));
+                }
+                writeln!(writer, "    {} -> {};", dot::node_id(parent.offset), dot::node_id(subkey.offset))?;
+            }
+            // Reversed so subkeys are descended into in stored order
+            stack.extend(children.into_iter().rev());
+        }
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+
+    // Function to format a key and its values as a regedit export block: the `[path]` header,
+    // one line per value (`@=` for the default value, `dword:`, `hex(7):` and so on) and a
+    // trailing blank line, all with CRLF line endings
+    pub fn format_key(&mut self, key_node: &KeyNode, path: &str) -> Result<String, HiveError> {
+        self.format_key_as(key_node, path, RegFormat::Utf16V5, &mut ParseReport::default())
+    }
+
+    // Function to export every key in the hive as a .reg file in the given format. Key paths are
+    // written below `key_prefix`, the path the hive is mounted at (e.g. "HKEY_LOCAL_MACHINE\SYSTEM").
+    pub fn export_reg<W: Write>(&mut self, writer: W, key_prefix: &str, format: RegFormat) -> Result<(), HiveError> {
+        self.export_reg_with_report(writer, key_prefix, format)?;
+        Ok(())
+    }
+
+    // Function to export every key in the hive as a .reg file like `export_reg`, also returning
+    // the anomalies noticed along the way
+    pub fn export_reg_with_report<W: Write>(
+        &mut self,
+        writer: W,
+        key_prefix: &str,
+        format: RegFormat,
+    ) -> Result<ParseReport, HiveError> {
+        let keys = self.walk().collect::<Result<Vec<_>, _>>()?;
+        self.export_keys(writer, key_prefix, format, keys)
+    }
+
+    // Function to export a subtree as a .reg file like `export_reg_with_report`, starting from
+    // the key at `path` (relative to the root) instead of the root. Key paths are still written
+    // in full below `key_prefix`.
+    pub fn export_reg_from<W: Write>(
+        &mut self,
+        writer: W,
+        key_prefix: &str,
+        format: RegFormat,
+        path: &str,
+        key_node: &Located<KeyNode>,
+    ) -> Result<ParseReport, HiveError> {
+        let keys = self.walk_from(path, key_node).collect::<Result<Vec<_>, _>>()?;
+        self.export_keys(writer, key_prefix, format, keys)
+    }
+
+    // Function to export the hive as a .reg file in chunks of at most `max_keys` keys, so a long
+    // export can be checkpointed and resumed. Pass None to start, which writes the file header,
+    // and then the checkpoint each call returns to carry on after the keys already written
+    // (appending to the same output). The checkpoint is the cell offset of the last key written;
+    // None means the export is complete. Put together, the chunks are the same as `export_reg`.
+    pub fn export_reg_resumable<W: Write>(
+        &mut self,
+        mut writer: W,
+        key_prefix: &str,
+        format: RegFormat,
+        checkpoint: Option<u32>,
+        max_keys: usize,
+    ) -> Result<Option<u32>, HiveError> {
+        // Every chunk moves the export on by at least one key, and reads one key past its end to
+        // tell whether any are left
+        let max_keys = max_keys.max(1);
+        let mut keys = match checkpoint {
+            None => self.walk().take(max_keys.saturating_add(1)).collect::<Result<Vec<_>, _>>()?,
+            Some(offset) => self.walk_after(offset)?.take(max_keys.saturating_add(1)).collect::<Result<Vec<_>, _>>()?,
+        };
+        let more = keys.len() > max_keys;
+        keys.truncate(max_keys);
+        let last_offset = keys.last().map(|(_, key_node)| key_node.offset);
+
+        if checkpoint.is_none() {
+            let header = format!("{}\r\n\r\n", format.header());
+            writer.write_all(&format.encode(&header, true))?;
+        }
+        for (path, key_node) in keys {
+            let key_path = if path.is_empty() {
+                key_prefix.to_string()
+            } else {
+                format!("{}\\{}", key_prefix, path)
+            };
+            let block = self.format_key_as(&key_node, &key_path, format, &mut ParseReport::default())?;
+            writer.write_all(&format.encode(&block, false))?;
+        }
+        writer.flush()?;
+
+        Ok(last_offset.filter(|_| more))
+    }
+
+    // Function to write the .reg export of walked keys
+    fn export_keys<W: Write>(
+        &mut self,
+        mut writer: W,
+        key_prefix: &str,
+        format: RegFormat,
+        keys: Vec<WalkEntry>,
+    ) -> Result<ParseReport, HiveError> {
+        let mut report = ParseReport::default();
+
+        let header = format!("{}\r\n\r\n", format.header());
+        writer.write_all(&format.encode(&header, true))?;
+        for (path, key_node) in keys {
+            let key_path = if path.is_empty() {
+                key_prefix.to_string()
+            } else {
+                format!("{}\\{}", key_prefix, path)
+            };
+            self.check_subkey_count(&path, &key_node, &mut report)?;
+            let block = self.format_key_as(&key_node, &key_path, format, &mut report)?;
+            writer.write_all(&format.encode(&block, false))?;
+        }
+        writer.flush()?;
+
+        Ok(report)
+    }
+
+    // Function to format a key and its values as a .reg block in the given format, recording
+    // anomalies in the values' data in `report` under the key's path
+    fn format_key_as(
+        &mut self,
+        key_node: &KeyNode,
+        path: &str,
+        format: RegFormat,
+        report: &mut ParseReport,
+    ) -> Result<String, HiveError> {
+        let values = self.values(key_node).collect::<Result<Vec<_>, _>>()?;
+
+        let mut block = format!("[{}]\r\n", path);
+        for (value_name, key_value) in values {
+            let data = self.value_data(&key_value)?;
+            report::check_value(report, path, &value_name, key_value.data_type(), &data);
+            block.push_str(&reg::format_value(&value_name, key_value.data_type(), &data, format));
+            block.push_str("\r\n");
+        }
+        block.push_str("\r\n");
+
+        Ok(block)
+    }
+
+    // Function to walk every key in the hive depth-first, yielding each path and node.
+    // The root key is yielded first with an empty path; other paths are relative to the root.
+    pub fn walk(&mut self) -> Walk<'_, R> {
+        Walk {
+            hive: self,
+            start: None,
+            stack: Vec::new(),
+            visited: HashSet::new(),
+            pending_error: None,
+            started: false,
+            finished: false,
+            keys_walked: 0,
+            max_depth: MAX_KEY_DEPTH,
+            filter: |_| true,
+        }
+    }
+
+    // Function to walk every key like `walk`, with a different limit on how deep keys may be
+    // nested than the 512 levels Windows allows. Walks never recurse: each level being walked is
+    // one frame on a stack kept on the heap, so a deep hive can't overflow the call stack, and
+    // `max_depth` caps how many frames (each holding its key's path and subkey offsets) are held
+    // at once. Keys nested deeper than `max_depth` below the root are reported as an error.
+    pub fn walk_depth_limited(&mut self, max_depth: usize) -> Walk<'_, R> {
+        Walk {
+            max_depth,
+            ..self.walk()
+        }
+    }
+
+    // Function to find keys by name wherever they are in the hive, e.g. a key a piece of malware
+    // is known to create, streaming each match with its path in `walk` order. Names are compared
+    // case-insensitively against each key's stored name, which may itself hold a backslash, so
+    // the path isn't split to get it. The root key is never matched. Errors from the walk are
+    // passed through, as are names that can't be read unless `skip_errors` is set.
+    pub fn find_keys_named(&mut self, name: &str) -> impl Iterator<Item = Result<WalkEntry, HiveError>> + '_ {
+        let name = name.to_string();
+        let skip_errors = self.options.skip_errors;
+        let mut walk = self.walk();
+        std::iter::from_fn(move || loop {
+            let (path, key_node) = match walk.next()? {
+                Ok(key) => key,
+                Err(e) => return Some(Err(e)),
+            };
+            if path.is_empty() {
+                continue;
+            }
+            match walk.hive.key_name(&key_node) {
+                Ok(key_name) if names_match(&key_name, &name) => return Some(Ok((path, key_node))),
+                Ok(_) => {}
+                Err(_) if skip_errors => {}
+                Err(e) => return Some(Err(e)),
+            }
+        })
+    }
+
+    // Function to walk the hive like `walk` but only yield the keys last written within a time
+    // range. Keys outside it are still descended into, as a subkey can be written long after
+    // its parent.
+    pub fn walk_modified(&mut self, range: TimeRange) -> Walk<'_, R, impl FnMut(&KeyNode) -> bool> {
+        Walk {
+            hive: self,
+            start: None,
+            stack: Vec::new(),
+            visited: HashSet::new(),
+            pending_error: None,
+            started: false,
+            finished: false,
+            keys_walked: 0,
+            max_depth: MAX_KEY_DEPTH,
+            filter: move |key_node: &KeyNode| range.contains(key_node.last_written()),
+        }
+    }
+
+    // Function to walk a subtree like `walk`, starting from any key instead of the root, e.g. to
+    // look at one control set only. `path` is the start key's path, which is yielded first;
+    // paths below it are built on it, so they stay relative to the root if `path` is.
+    pub fn walk_from(&mut self, path: &str, key_node: &Located<KeyNode>) -> Walk<'_, R> {
+        Walk {
+            hive: self,
+            start: Some((path.to_string(), *key_node)),
+            stack: Vec::new(),
+            visited: HashSet::new(),
+            pending_error: None,
+            started: false,
+            finished: false,
+            keys_walked: 0,
+            max_depth: MAX_KEY_DEPTH,
+            filter: |_| true,
+        }
+    }
+
+    // Function to pick a walk back up after the key at `offset`, as if `walk` had just yielded
+    // it: the rest of the hive comes in the same order, starting with that key's first subkey.
+    // Walk order is fixed by the stored subkey lists, so a key's offset is enough to resume
+    // from. The key's ancestors are rebuilt from its parent offsets; an offset that isn't a key
+    // listed under its parent all the way up to the root fails with InvalidCheckpoint. Keys
+    // cross-linked to a part of the tree walked before the checkpoint aren't noticed.
+    pub fn walk_after(&mut self, offset: u32) -> Result<Walk<'_, R>, HiveError> {
+        let root_offset = self.root()?.offset;
+        let mut chain = vec![self.open_key_node_at(offset)?];
+        while chain[chain.len() - 1].offset != root_offset {
+            let parent_offset = chain[chain.len() - 1].parent;
+            if chain.len() > MAX_KEY_DEPTH {
+                return Err(HiveError::InvalidCheckpoint { offset });
+            }
+            let parent = self
+                .open_key_node_at(parent_offset)
+                .map_err(|_| HiveError::InvalidCheckpoint { offset })?;
+            chain.push(parent);
+        }
+        chain.reverse();
+
+        // One frame per ancestor, holding the subkeys after the one on the way down
+        let mut stack = Vec::new();
+        let mut visited = HashSet::new();
+        let mut path = String::new();
+        for (depth, pair) in chain.windows(2).enumerate() {
+            let (parent, child) = (&pair[0], &pair[1]);
+            let offsets = self.subkey_offsets(parent)?;
+            let Some(position) = offsets.iter().position(|&subkey_offset| subkey_offset == child.offset) else {
+                return Err(HiveError::InvalidCheckpoint { offset });
+            };
+            let mut offsets = offsets.into_iter();
+            offsets.nth(position);
+            visited.insert(parent.offset);
+            stack.push(WalkFrame {
+                path: path.clone(),
+                depth,
+                offsets,
+            });
+
+            let (child_name, _) =
+                read_named_key_node(&mut self.file, child.offset).map_err(HiveError::reading("nk", child.offset))?;
+            path = if path.is_empty() { child_name } else { format!("{}\\{}", path, child_name) };
+        }
+        visited.insert(offset);
+
+        let key_node = chain[chain.len() - 1];
+        let mut walk: Walk<'_, R> = Walk {
+            hive: self,
+            start: None,
+            stack,
+            visited,
+            pending_error: None,
+            started: true,
+            finished: false,
+            keys_walked: 0,
+            max_depth: MAX_KEY_DEPTH,
+            filter: |_| true,
+        };
+        walk.descend(&path, chain.len() - 1, &key_node);
+        Ok(walk)
+    }
+
+    // Function to stream every value in the hive as `(key path, value name, value)`, keys in
+    // `walk` order and values in stored order, decoding each value as it is reached. Only one
+    // key's value list is held at a time. With `skip_errors`, an unreadable key or value yields
+    // its error and the walk carries on; otherwise the first error ends it.
+    pub fn all_values(&mut self) -> AllValues<'_, R> {
+        AllValues {
+            walk: self.walk(),
+            key: None,
+            finished: false,
+            value_name: None,
+            filter: |_| true,
+        }
+    }
+
+    // Function to find every key in a subtree that has a value with a given name, yielding the
+    // key's path and the decoded value, e.g. the ImagePath of every service under
+    // ControlSet001\Services. `path` and `key_node` are the start key as for `walk_from`, which
+    // is searched too. Names are compared case-insensitively and "" is the default value; other
+    // values are skipped without reading their data. Errors are handled as by `all_values`.
+    pub fn find_value_in_subtree(
+        &mut self,
+        path: &str,
+        key_node: &Located<KeyNode>,
+        value_name: &str,
+    ) -> impl Iterator<Item = Result<(String, RegistryValue), HiveError>> + '_ {
+        let values = AllValues {
+            walk: self.walk_from(path, key_node),
+            key: None,
+            finished: false,
+            value_name: Some(value_name.to_string()),
+            filter: |_: &KeyValue| true,
+        };
+        values.map(|value| value.map(|(path, _, value)| (path, value)))
+    }
+
+    // Function to stream the values of one REG_* type across the hive like `all_values`, e.g.
+    // every REG_MULTI_SZ, further narrowed by the size of their data in bytes: pass `|_| true`
+    // for all of them or `|size| size > 1024` for the larger blobs. Other values are skipped
+    // without reading their data.
+    pub fn walk_values_of_type<F: FnMut(u32) -> bool>(
+        &mut self,
+        data_type: u32,
+        mut size_filter: F,
+    ) -> AllValues<'_, R, impl FnMut(&KeyValue) -> bool> {
+        AllValues {
+            walk: self.walk(),
+            key: None,
+            finished: false,
+            value_name: None,
+            filter: move |key_value: &KeyValue| key_value.data_type() == data_type && size_filter(key_value.data_len()),
+        }
+    }
+
+    // Function to walk every key in the hive like `walk`, collecting the keys along with the
+    // anomalies noticed in them and their values. With `skip_errors`, keys and values that can't
+    // be read are left out.
+    pub fn walk_with_report(&mut self) -> Result<(Vec<WalkEntry>, ParseReport), HiveError> {
+        let skip_errors = self.options.skip_errors;
+        let keys = self.walk().filter(|key| !skippable(skip_errors, key)).collect::<Result<Vec<_>, _>>()?;
+
+        let mut report = ParseReport::default();
+        for (path, key_node) in &keys {
+            self.check_subkey_count(path, key_node, &mut report)?;
+
+            let values = self
+                .values(key_node)
+                .filter(|value| !(skip_errors && value.is_err()))
+                .collect::<Result<Vec<_>, _>>()?;
+            for (value_name, key_value) in values {
+                let data = match self.value_data(&key_value) {
+                    Ok(data) => data,
+                    Err(_) if skip_errors => continue,
+                    Err(e) => return Err(e),
+                };
+                report::check_value(&mut report, path, &value_name, key_value.data_type(), &data);
+            }
+        }
+
+        Ok((keys, report))
+    }
+
+    // Function to record a key node whose subkey count disagrees with its subkey lists
+    fn check_subkey_count(&mut self, path: &str, key_node: &KeyNode, report: &mut ParseReport) -> Result<(), HiveError> {
+        let recorded = key_node.number_of_subkeys;
+        let found = match self.subkey_offsets(key_node) {
+            Ok(offsets) => offsets.len() as u32,
+            Err(_) if self.options.skip_errors => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        if found != recorded && key_node.subkeys_list_offset != 0xFFFFFFFF && key_node.predefined_handle().is_none() {
+            report.warn(path, WarningKind::SubkeyCountMismatch { recorded, found });
+        }
+        Ok(())
+    }
+
+    // Function to search key names, value names and value data for a pattern, in walk order.
+    // With `skip_errors`, keys and values that can't be read are left out of the search.
+    pub fn search(&mut self, pattern: &SearchPattern, options: SearchOptions) -> Result<Vec<SearchMatch>, HiveError> {
+        let skip_errors = self.options.skip_errors;
+        let keys = self.walk().filter(|key| !skippable(skip_errors, key)).collect::<Result<Vec<_>, _>>()?;
+        self.search_keys(pattern, options, keys)
+    }
+
+    // Function to search a subtree like `search`, starting from the key at `path` (relative to
+    // the root) instead of the root. The start key's own name is matched too.
+    pub fn search_from(
+        &mut self,
+        pattern: &SearchPattern,
+        options: SearchOptions,
+        path: &str,
+        key_node: &Located<KeyNode>,
+    ) -> Result<Vec<SearchMatch>, HiveError> {
+        let skip_errors = self.options.skip_errors;
+        let keys = self
+            .walk_from(path, key_node)
+            .filter(|key| !skippable(skip_errors, key))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.search_keys(pattern, options, keys)
+    }
+
+    // Function to search the names and values of walked keys
+    fn search_keys(
+        &mut self,
+        pattern: &SearchPattern,
+        options: SearchOptions,
+        keys: Vec<WalkEntry>,
+    ) -> Result<Vec<SearchMatch>, HiveError> {
+        let skip_errors = self.options.skip_errors;
+
+        let mut matches = Vec::new();
+        for (path, key_node) in keys {
+            // The root has no name of its own in a path, so it is never matched
+            if options.keys && !path.is_empty() {
+                match self.key_name(&key_node) {
+                    Ok(key_name) if pattern.is_match(&key_name) => matches.push(SearchMatch {
+                        path: path.clone(),
+                        value_name: None,
+                        text: key_name,
+                    }),
+                    Ok(_) => {}
+                    Err(_) if skip_errors => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            if !options.values {
+                continue;
+            }
+
+            let values = self
+                .values(&key_node)
+                .filter(|value| !(skip_errors && value.is_err()))
+                .collect::<Result<Vec<_>, _>>()?;
+            for (value_name, key_value) in values {
+                if pattern.is_match(&value_name) {
+                    matches.push(SearchMatch {
+                        path: path.clone(),
+                        value_name: Some(value_name.clone()),
+                        text: value_name.clone(),
+                    });
+                    continue;
+                }
+
+                let data = match self.value_data(&key_value) {
+                    Ok(data) => data,
+                    Err(_) if skip_errors => continue,
+                    Err(e) => return Err(e),
+                };
+                for text in search::searchable_data(key_value.data_type(), &data) {
+                    if pattern.is_match(&text) {
+                        matches.push(SearchMatch {
+                            path: path.clone(),
+                            value_name: Some(value_name.clone()),
+                            text,
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(matches)
+    }
+
+    // Function to open the key at a backslash-separated path relative to the root key, or a full
+    // path under `HiveOptions::root_label`. Like Windows, names are compared case-insensitively;
+    // when several subkeys differ only by case, the first in stored order is opened.
+    pub fn open_key(&mut self, path: &str) -> Result<Located<KeyNode>, HiveError> {
+        self.open_key_matching(path, names_match)
+    }
+
+    // Function to open a key comparing names case-insensitively, the same as `open_key`. For
+    // code that wants to say so next to uses of `open_key_exact`.
+    pub fn open_key_ci(&mut self, path: &str) -> Result<Located<KeyNode>, HiveError> {
+        self.open_key(path)
+    }
+
+    // Function to open a key comparing names byte for byte, so "Run" and "RUN" are different keys.
+    // Windows never creates sibling keys that differ only by case, but a hive edited offline can
+    // hold them to hide one from anything that resolves paths like Windows does. The root label
+    // is still matched case-insensitively.
+    pub fn open_key_exact(&mut self, path: &str) -> Result<Located<KeyNode>, HiveError> {
+        self.open_key_matching(path, |a, b| a == b)
+    }
+
+    // Function to follow a path of subkey names down from a key, e.g.
+    // `["ControlSet001", "Control", "Lsa", "JD"]`, returning the last key. Names are compared
+    // case-insensitively and an empty path returns the start key. A missing subkey fails with
+    // KeyNotFound naming the components, joined with backslashes.
+    pub fn find_subkey_path(&mut self, start: &Located<KeyNode>, components: &[&str]) -> Result<Located<KeyNode>, HiveError> {
+        self.find_subkey_path_matching(start, components, names_match)
+    }
+
+    // Function to open a key path from the root, comparing names with `names_equal`
+    fn open_key_matching(&mut self, path: &str, names_equal: fn(&str, &str) -> bool) -> Result<Located<KeyNode>, HiveError> {
+        let root = self.root()?;
+        let relative_path = self.strip_root_label(path).unwrap_or(path);
+        let components: Vec<&str> = relative_path.split('\\').filter(|component| !component.is_empty()).collect();
+
+        match self.find_subkey_path_matching(&root, &components, names_equal) {
+            Err(HiveError::KeyNotFound(_)) => Err(HiveError::KeyNotFound(path.to_string())),
+            result => result,
+        }
+    }
+
+    // Function to follow a path of subkey names down from a key, comparing names with `names_equal`
+    fn find_subkey_path_matching(
+        &mut self,
+        start: &Located<KeyNode>,
+        components: &[&str],
+        names_equal: fn(&str, &str) -> bool,
+    ) -> Result<Located<KeyNode>, HiveError> {
+        let mut key_node = *start;
+
+        for component in components {
+            key_node = match self.find_subkey_named(&key_node, component, names_equal)? {
+                Some(subkey_node) => subkey_node,
+                None => return Err(HiveError::KeyNotFound(components.join("\\"))),
+            };
+        }
+
+        Ok(key_node)
+    }
+
+    // Function to read one value by key path and value name, decoded according to its type, e.g.
+    // `hive.value("ControlSet001\\Control\\ComputerName\\ComputerName", "ComputerName")`.
+    // Names are compared case-insensitively and "" is the key's default value.
+    pub fn value(&mut self, key_path: &str, value_name: &str) -> Result<RegistryValue, HiveError> {
+        let key_node = self.open_key(key_path)?;
+        match self.value_data_named(&key_node, value_name)? {
+            Some((data_type, data)) => self.decode_value(data_type, &data),
+            None => Err(HiveError::ValueNotFound {
+                key_path: key_path.to_string(),
+                value_name: value_name.to_string(),
+            }),
+        }
+    }
+
+    // Function to get the REG_* type of one value by key path and value name, reading only its vk
+    // record. The data isn't read, so this is cheap even for big data values, and a value whose
+    // data cell is damaged still reports its type.
+    pub fn value_type(&mut self, key_path: &str, value_name: &str) -> Result<u32, HiveError> {
+        let key_node = self.open_key(key_path)?;
+        match self.value_opt(&key_node, value_name)? {
+            Some(key_value) => Ok(key_value.data_type()),
+            None => Err(HiveError::ValueNotFound {
+                key_path: key_path.to_string(),
+                value_name: value_name.to_string(),
+            }),
+        }
+    }
+
+    // Function to read a string value (REG_SZ, REG_EXPAND_SZ unexpanded, or REG_LINK) by key path
+    // and value name, failing with TypeMismatch for any other type
+    pub fn value_as_string(&mut self, key_path: &str, value_name: &str) -> Result<String, HiveError> {
+        match self.value(key_path, value_name)? {
+            RegistryValue::String(string) | RegistryValue::ExpandString(string) | RegistryValue::Link(string) => Ok(string),
+            value => Err(HiveError::TypeMismatch {
+                expected: "REG_SZ, REG_EXPAND_SZ or REG_LINK",
+                found: value.data_type(),
+            }),
+        }
+    }
+
+    // Function to read a REG_DWORD or REG_DWORD_BIG_ENDIAN value by key path and value name,
+    // failing with TypeMismatch for any other type
+    pub fn value_as_u32(&mut self, key_path: &str, value_name: &str) -> Result<u32, HiveError> {
+        match self.value(key_path, value_name)? {
+            RegistryValue::Dword(dword) | RegistryValue::DwordBigEndian(dword) => Ok(dword),
+            value => Err(HiveError::TypeMismatch {
+                expected: "REG_DWORD or REG_DWORD_BIG_ENDIAN",
+                found: value.data_type(),
+            }),
+        }
+    }
+
+    // Function to read a REG_QWORD value by key path and value name, failing with TypeMismatch for
+    // any other type, DWORDs included. Use `value_as_u32` for those.
+    pub fn value_as_u64(&mut self, key_path: &str, value_name: &str) -> Result<u64, HiveError> {
+        match self.value(key_path, value_name)? {
+            RegistryValue::Qword(qword) => Ok(qword),
+            value => Err(HiveError::TypeMismatch {
+                expected: "REG_QWORD",
+                found: value.data_type(),
+            }),
+        }
+    }
+
+    // Function to read a REG_BINARY (or REG_NONE) value by key path and value name, failing with
+    // TypeMismatch for any other type. Use `value_data` for the raw bytes of any value.
+    pub fn value_as_bytes(&mut self, key_path: &str, value_name: &str) -> Result<Vec<u8>, HiveError> {
+        match self.value(key_path, value_name)? {
+            RegistryValue::Binary(data) | RegistryValue::None(data) => Ok(data),
+            value => Err(HiveError::TypeMismatch {
+                expected: "REG_BINARY or REG_NONE",
+                found: value.data_type(),
+            }),
+        }
+    }
+
+    // Function to open the control set a SYSTEM hive boots with, as recorded by Select\Current.
+    // Offline there is no CurrentControlSet link, so this is how it is resolved.
+    pub fn current_control_set(&mut self) -> Result<Located<KeyNode>, HiveError> {
+        self.select_control_set("Current")
+    }
+
+    // Function to open the control set of the last boot known to be good, as recorded by
+    // Select\LastKnownGood. Comparing it with `current_control_set` shows recent changes to
+    // services and drivers.
+    pub fn last_known_good(&mut self) -> Result<Located<KeyNode>, HiveError> {
+        self.select_control_set("LastKnownGood")
+    }
+
+    // Function to open the ControlSetNNN key a DWORD value of the SYSTEM hive's Select key names
+    fn select_control_set(&mut self, entry: &str) -> Result<Located<KeyNode>, HiveError> {
+        let control_set_path = self.control_set_path(entry)?;
+        self.open_key(&control_set_path)
+    }
+
+    // Function to get the path ("ControlSet001", ...) of the control set a DWORD value of the
+    // SYSTEM hive's Select key names, such as "Current"
+    pub(crate) fn control_set_path(&mut self, entry: &str) -> Result<String, HiveError> {
+        match self.value("Select", entry)? {
+            RegistryValue::Dword(control_set) => Ok(format!("ControlSet{:03}", control_set)),
+            other => Err(HiveError::InvalidValueData(format!(
+                "Select\\{} should be a DWORD, found {:?}",
+                entry, other
+            ))),
+        }
+    }
+
+    // Function to reopen a key node at a cell offset recorded earlier (e.g. `Located::offset`),
+    // checking that the offset still holds an allocated "nk" cell
+    pub fn open_key_node_at(&mut self, offset: u32) -> Result<Located<KeyNode>, HiveError> {
+        // A free cell may still hold the record of a deleted key
+        let cell_header = read_cell_header(&mut self.file, offset).map_err(HiveError::reading("nk", offset))?;
+        if !cell_header.is_allocated() {
+            return Err(HiveError::UnexpectedCell { offset, expected: "nk" });
+        }
+
+        match self.parse_cell(offset)? {
+            Cell::KeyNode { key_node, .. } => Ok(Located { offset, node: key_node }),
+            _ => Err(HiveError::UnexpectedCell { offset, expected: "nk" }),
+        }
+    }
+
+    // Function to name the key node at a cell offset found some other way (a carved cell, a search
+    // hit), following its parent offsets up to the root. Returns None unless the offset is an
+    // allocated "nk" cell that each parent really lists as a subkey, so a deleted key or a stray
+    // record whose parent field happens to point at a live key isn't given a path.
+    pub fn path_of(&mut self, offset: u32) -> Option<String> {
+        let root_offset = self.root().ok()?.offset;
+        let mut names = Vec::new();
+        let mut offset = offset;
+        while offset != root_offset {
+            if names.len() >= MAX_KEY_DEPTH {
+                return None;
+            }
+            let key_node = self.open_key_node_at(offset).ok()?;
+            let (name, _) = read_named_key_node(&mut self.file, offset).ok()?;
+            let parent = self.open_key_node_at(key_node.parent).ok()?;
+            if !self.subkey_offsets(&parent).ok()?.contains(&offset) {
+                return None;
+            }
+            names.push(name);
+            offset = parent.offset;
+        }
+        names.reverse();
+        Some(names.join("\\"))
+    }
+
+    // Function to render a path relative to the root (as `walk`, `search` and `path_of` give them)
+    // under `HiveOptions::root_label`, e.g. "Select" as "HKEY_LOCAL_MACHINE\\SYSTEM\\Select" and the
+    // root itself as the label. Without a label the path is returned as it is.
+    pub fn full_path(&self, path: &str) -> String {
+        match (&self.options.root_label, path.is_empty()) {
+            (None, _) => path.to_string(),
+            (Some(root_label), true) => root_label.clone(),
+            (Some(root_label), false) => format!("{}\\{}", root_label, path),
+        }
+    }
+
+    // Function to strip `HiveOptions::root_label` (compared case-insensitively) from the front of
+    // a full path, giving the path relative to the root, or None if the path doesn't start with it
+    fn strip_root_label<'p>(&self, path: &'p str) -> Option<&'p str> {
+        let root_label = self.options.root_label.as_deref()?.trim_end_matches('\\');
+        let prefix = path.get(..root_label.len())?;
+        if root_label.is_empty() || !prefix.eq_ignore_ascii_case(root_label) {
+            return None;
+        }
+        let rest = &path[root_label.len()..];
+        if rest.is_empty() || rest.starts_with('\\') {
+            Some(rest)
+        } else {
+            None
+        }
+    }
+
+    // Function to check whether a key path exists, treating any error as absence (like
+    // `std::path::Path::exists`). Use `try_exists` to tell a missing key from a damaged hive.
+    pub fn exists(&mut self, path: &str) -> bool {
+        self.try_exists(path).unwrap_or(false)
+    }
+
+    // Function to check whether a key path exists. Only a missing key yields `Ok(false)`;
+    // I/O and corruption errors met on the way are returned.
+    pub fn try_exists(&mut self, path: &str) -> Result<bool, HiveError> {
+        match self.open_key(path) {
+            Ok(_) => Ok(true),
+            Err(HiveError::KeyNotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Function to estimate the number of keys in the hive, e.g. for sizing a progress bar.
+    // This is a linear scan over the hive bins counting allocated cells with an "nk" signature,
+    // which is much cheaper than a walk but only an estimate: allocated keys that are no longer
+    // reachable from the root (such as leftovers of deleted subtrees) are counted too.
+    pub fn estimate_key_count(&mut self) -> Result<u64, HiveError> {
+        let mut key_count = 0;
+        self.scan_cells(|_, cell_header, cell_data| {
+            if cell_header.is_allocated() && &cell_data[..2] == b"nk" {
+                key_count += 1;
+            }
+        })?;
+
+        Ok(key_count)
+    }
+
+    // Function to summarise the hive for triage: header fields, key and value counts, the deepest
+    // key, the largest value and free space. With `skip_errors`, unreadable keys and value lists
+    // are counted in `errors` instead of failing.
+    pub fn statistics(&mut self) -> Result<HiveStatistics, HiveError> {
+        self.statistics_of(None)
+    }
+
+    // Function to summarise a subtree like `statistics`, starting from the key at `path`
+    // (relative to the root) instead of the root. Key and value counts, depth and the largest
+    // value cover the subtree only; depths are still counted from the root, and the header
+    // fields and free space describe the whole hive.
+    pub fn statistics_from(&mut self, path: &str, key_node: &Located<KeyNode>) -> Result<HiveStatistics, HiveError> {
+        self.statistics_of(Some((path, key_node)))
+    }
+
+    // Function to compute statistics for the whole hive, or for the subtree below `start`
+    fn statistics_of(&mut self, start: Option<(&str, &Located<KeyNode>)>) -> Result<HiveStatistics, HiveError> {
+        let skip_errors = self.options.skip_errors;
+        let (major_version, minor_version) = self.version();
+        let mut statistics = HiveStatistics {
+            major_version,
+            minor_version,
+            file_name: self.file_name(),
+            dirty: self.is_dirty(),
+            boot_type: self.boot_type(),
+            boot_recover: self.boot_recover(),
+            ..HiveStatistics::default()
+        };
+
+        let walk = match start {
+            Some((path, key_node)) => self.walk_from(path, key_node),
+            None => self.walk(),
+        };
+        let mut keys = Vec::new();
+        for key in walk {
+            match key {
+                Ok(key) => keys.push(key),
+                Err(e) if skip_errors && !matches!(e, HiveError::BudgetExceeded { .. }) => statistics.errors += 1,
+                Err(e) => return Err(e),
+            }
+        }
+
+        for (path, key_node) in keys {
+            statistics.key_count += 1;
+            let depth = if path.is_empty() { 0 } else { path.split('\\').count() };
+            statistics.max_depth = statistics.max_depth.max(depth);
+
+            for value in self.values(&key_node) {
+                let (value_name, key_value) = match value {
+                    Ok(value) => value,
+                    Err(_) if skip_errors => {
+                        statistics.errors += 1;
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+                statistics.value_count += 1;
+
+                let size = key_value.data_len();
+                if statistics.largest_value.as_ref().is_none_or(|largest| size > largest.size) {
+                    statistics.largest_value = Some(LargestValue {
+                        path: path.clone(),
+                        name: value_name,
+                        size,
+                    });
+                }
+            }
+        }
+
+        let (mut free_cell_count, mut free_bytes) = (0, 0);
+        self.scan_cells(|_, cell_header, _| {
+            if !cell_header.is_allocated() {
+                free_cell_count += 1;
+                free_bytes += cell_header.size() as u64;
+            }
+        })?;
+        statistics.free_cell_count = free_cell_count;
+        statistics.free_bytes = free_bytes;
+
+        Ok(statistics)
+    }
+
+    // Function to check the whole hive for integrity problems: the base block checksum, every bin
+    // and cell header, subkey counts, offsets that point past the hive bins, and value data cells
+    // that overlap other cells. Unlike the rest of the API this doesn't stop at the first problem;
+    // everything found is listed in the report. Only I/O errors on the base block are returned.
+    pub fn validate(&mut self) -> Result<ValidationReport, HiveError> {
+        let mut report = ValidationReport::default();
+        let hive_bins_data_size = self.base_block.hive_bins_data_size;
+
+        if self.recovered() {
+            report.issues.push(ValidationIssue::HeaderRecovered);
+        }
+        // Check the base block as stored, since a recovered one is rebuilt with a valid checksum.
+        // One too damaged to read at all was rebuilt, which HeaderRecovered already reports.
+        match read_base_block(&mut self.file) {
+            Ok(stored_base_block) => {
+                let (stored, computed) = (stored_base_block.checksum, stored_base_block.computed_checksum());
+                if stored != computed {
+                    report.issues.push(ValidationIssue::ChecksumMismatch { stored, computed });
+                }
+                let (stored, found) = (stored_base_block.root_cell_offset, self.base_block.root_cell_offset);
+                if stored != found {
+                    report.issues.push(ValidationIssue::RootKeyRelocated { stored, found });
+                }
+            }
+            Err(_) if self.provenance.base_block_rebuilt => {}
+            Err(e) => return Err(HiveError::reading("base block", 0)(e)),
+        }
+
+        // Scan the bins, resuming at the next valid bin after a damaged one so that one bad header
+        // is reported once rather than hiding the rest of the hive
+        let mut allocated_cells = Vec::new();
+        let mut bin_offset = 0u32;
+        let mut resyncing = false;
+        while bin_offset < hive_bins_data_size {
+            let bin = match self.read_bin(bin_offset) {
+                Ok(bin) => bin,
+                Err(HiveError::InvalidBin { .. }) => {
+                    if !resyncing {
+                        report.issues.push(ValidationIssue::InvalidBin { offset: bin_offset });
+                    }
+                    resyncing = true;
+                    bin_offset = offset_add(bin_offset, 4096)?;
+                    continue;
+                }
+                // The hive is truncated: nothing further can be scanned
+                Err(_) => {
+                    report.issues.push(ValidationIssue::InvalidBin { offset: bin_offset });
+                    break;
+                }
+            };
+            resyncing = false;
+
+            let mut cell_position = mem::size_of::<HiveBinHeader>();
+            while cell_position + 4 <= bin.len() {
+                let cell_header = CellHeader::from_bytes([
+                    bin[cell_position],
+                    bin[cell_position + 1],
+                    bin[cell_position + 2],
+                    bin[cell_position + 3],
+                ]);
+                let cell_length = cell_header.size() as usize;
+                let cell_offset = offset_add(bin_offset, cell_position)?;
+                if cell_length < 8 || !cell_length.is_multiple_of(8) || cell_position + cell_length > bin.len() {
+                    report.issues.push(ValidationIssue::InvalidCell { offset: cell_offset });
+                    break;
+                }
+                if cell_header.is_allocated() {
+                    allocated_cells.push((cell_offset, cell_length as u32));
+                }
+                cell_position += cell_length;
+            }
+
+            bin_offset = offset_add(bin_offset, bin.len())?;
+        }
+        // The overlap checks below use the cells found here, which also covers damaged hives
+        // that `scan_cells` would give up on
+        self.allocated_cells = Some(allocated_cells);
+
+        let root_offset = self.base_block.root_cell_offset;
+        let mut visited = HashSet::from([root_offset]);
+        let mut stack = match self.read_root() {
+            Ok(_) => vec![(String::new(), root_offset, 0)],
+            Err(e) => {
+                report.issues.push(ValidationIssue::Unreadable {
+                    path: String::new(),
+                    error: e.to_string(),
+                });
+                Vec::new()
+            }
+        };
+        while let Some((path, offset, depth)) = stack.pop() {
+            let key_node = match read_named_key_node(&mut self.file, offset) {
+                Ok((_, key_node)) => key_node,
+                Err(e) => {
+                    report.issues.push(ValidationIssue::Unreadable {
+                        path,
+                        error: HiveError::reading("nk", offset)(e).to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            // A predefined-handle key's list offsets are meaningless, so they aren't checked
+            let has_lists = key_node.predefined_handle().is_none();
+            let key_offsets = [
+                ("subkey list offset", key_node.subkeys_list_offset, has_lists),
+                ("value list offset", key_node.key_values_list_offset, has_lists),
+                ("class name offset", key_node.class_name_offset, true),
+            ];
+            for (field, offset, checked) in key_offsets {
+                if checked && offset != 0xFFFFFFFF && offset >= hive_bins_data_size {
+                    report.issues.push(ValidationIssue::OffsetOutOfBounds {
+                        path: path.clone(),
+                        field,
+                        offset,
+                    });
+                }
+            }
+
+            match self.subkey_offsets(&key_node) {
+                Ok(subkey_offsets) => {
+                    let (recorded, found) = (key_node.number_of_subkeys, subkey_offsets.len() as u32);
+                    if found != recorded
+                        && key_node.subkeys_list_offset != 0xFFFFFFFF
+                        && key_node.predefined_handle().is_none()
+                    {
+                        report.issues.push(ValidationIssue::SubkeyCountMismatch {
+                            path: path.clone(),
+                            recorded,
+                            found,
+                        });
+                    }
+                    if depth < MAX_KEY_DEPTH {
+                        // Pushed in reverse so keys are checked in stored order
+                        for &subkey_offset in subkey_offsets.iter().rev() {
+                            if !visited.insert(subkey_offset) {
+                                report.issues.push(ValidationIssue::CrossLinkedKey {
+                                    path: path.clone(),
+                                    offset: subkey_offset,
+                                });
+                                continue;
+                            }
+                            match read_named_key_node(&mut self.file, subkey_offset) {
+                                Ok((name, _)) => {
+                                    let subkey_path =
+                                        if path.is_empty() { name } else { format!("{}\\{}", path, name) };
+                                    stack.push((subkey_path, subkey_offset, depth + 1));
+                                }
+                                Err(e) => report.issues.push(ValidationIssue::Unreadable {
+                                    path: path.clone(),
+                                    error: HiveError::reading("nk", subkey_offset)(e).to_string(),
+                                }),
+                            }
+                        }
+                    }
+                }
+                Err(e) => report.issues.push(ValidationIssue::Unreadable {
+                    path: path.clone(),
+                    error: e.to_string(),
+                }),
+            }
+
+            let value_offsets = match self.value_offsets(&key_node) {
+                Ok(value_offsets) => value_offsets,
+                Err(e) => {
+                    report.issues.push(ValidationIssue::Unreadable {
+                        path,
+                        error: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+            for value_offset in value_offsets {
+                let (value_name, key_value) = match read_named_key_value(&mut self.file, value_offset) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        report.issues.push(ValidationIssue::Unreadable {
+                            path: path.clone(),
+                            error: HiveError::reading("vk", value_offset)(e).to_string(),
+                        });
+                        continue;
+                    }
+                };
+
+                // Resident data lives in the data offset field itself
+                if key_value.is_resident() || key_value.data_len() == 0 {
+                    continue;
+                }
+                let data_offset = key_value.data_offset;
+                if data_offset >= hive_bins_data_size {
+                    report.issues.push(ValidationIssue::OffsetOutOfBounds {
+                        path: path.clone(),
+                        field: "value data offset",
+                        offset: data_offset,
+                    });
+                    continue;
+                }
+                if let Err(HiveError::OverlappingCell { a, b }) = self.check_value_data_cell(&key_value) {
+                    report.issues.push(ValidationIssue::OverlappingCell {
+                        path: path.clone(),
+                        value_name,
+                        a,
+                        b,
+                    });
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    // Function to visit every cell in the hive bins in file order with its offset, header and
+    // data, after the header
+    fn scan_cells<F: FnMut(u32, CellHeader, &[u8])>(&mut self, mut visit: F) -> Result<(), HiveError> {
+        let hive_bins_data_size = self.base_block.hive_bins_data_size;
+
+        let mut bin_offset = 0u32;
+        while bin_offset < hive_bins_data_size {
+            let bin = self.read_bin(bin_offset)?;
+
+            // Cells start after the 32-byte bin header and are laid out back to back
+            let mut cell_position = mem::size_of::<HiveBinHeader>();
+            while cell_position + 4 <= bin.len() {
+                let cell_header = CellHeader::from_bytes([
+                    bin[cell_position],
+                    bin[cell_position + 1],
+                    bin[cell_position + 2],
+                    bin[cell_position + 3],
+                ]);
+                let cell_length = cell_header.size() as usize;
+                if cell_length < 8 || !cell_length.is_multiple_of(8) || cell_position + cell_length > bin.len() {
+                    return Err(HiveError::InvalidCell {
+                        offset: offset_add(bin_offset, cell_position)?,
+                    });
+                }
+
+                let cell_offset = offset_add(bin_offset, cell_position)?;
+                visit(cell_offset, cell_header, &bin[cell_position + 4..cell_position + cell_length]);
+                cell_position += cell_length;
+            }
+
+            bin_offset = offset_add(bin_offset, bin.len())?;
+        }
+
+        Ok(())
+    }
+
+    // Function to check that the cell holding a key value's data doesn't run into another
+    // allocated cell. Resident data has no cell; for big data only the "db" record is checked.
+    fn check_value_data_cell(&mut self, key_value: &KeyValue) -> Result<(), HiveError> {
+        let data_size = key_value.data_len();
+        if key_value.is_resident() || data_size == 0 {
+            return Ok(());
+        }
+        let data_offset = key_value.data_offset;
+        let data_length = if self.is_big_data(data_size) { 8 } else { data_size };
+        // The cell header comes first, then the data
+        let data_end = data_offset as u64 + 4 + data_length as u64;
+
+        if self.allocated_cells.is_none() {
+            let mut allocated_cells = Vec::new();
+            self.scan_cells(|cell_offset, cell_header, _| {
+                if cell_header.is_allocated() {
+                    allocated_cells.push((cell_offset, cell_header.size()));
+                }
+            })?;
+            self.allocated_cells = Some(allocated_cells);
+        }
+        let allocated_cells = self.allocated_cells.as_deref().unwrap_or_default();
+
+        // Cells are in offset order; start from the last one beginning at or before the data cell
+        let first = allocated_cells.partition_point(|&(cell_offset, _)| cell_offset <= data_offset);
+        for &(cell_offset, cell_length) in &allocated_cells[first.saturating_sub(1)..] {
+            if cell_offset as u64 >= data_end {
+                break;
+            }
+            let cell_end = cell_offset as u64 + cell_length as u64;
+            if cell_offset != data_offset && cell_end > data_offset as u64 {
+                return Err(HiveError::OverlappingCell {
+                    a: data_offset,
+                    b: cell_offset,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Function to read the header of the cell at an offset in the hive bins data: its size and
+    // whether it is allocated or free. `parse_cell` parses a free cell just the same, so check
+    // this to tell live records from deleted ones.
+    pub fn cell_header(&mut self, offset: u32) -> Result<CellHeader, HiveError> {
+        read_cell_header(&mut self.file, offset).map_err(HiveError::reading("cell", offset))
+    }
+
+    // Function to read the cell at an offset in the hive bins data and parse it according to its
+    // signature. Key nodes and key values come with their decoded names.
+    pub fn parse_cell(&mut self, offset: u32) -> Result<Cell, HiveError> {
+        // The size includes the 4-byte header and may not reach past the hive bins data
+        let cell_length = self.cell_header(offset)?.size();
+        let remaining = self.base_block.hive_bins_data_size.saturating_sub(offset);
+        if cell_length < 8 || cell_length > remaining {
+            return Err(HiveError::InvalidCell { offset });
+        }
+        let data = read_bytes(&mut self.file, cell_length as usize - 4).map_err(HiveError::reading("cell", offset))?;
+
+        match data.get(..2) {
+            Some(b"nk") => {
+                let (name, key_node) =
+                    read_named_key_node(&mut self.file, offset).map_err(HiveError::reading("nk", offset))?;
+                Ok(Cell::KeyNode {
+                    name,
+                    key_node: key_node.node,
+                })
+            }
+            Some(b"vk") => {
+                let (name, key_value) =
+                    read_named_key_value(&mut self.file, offset).map_err(HiveError::reading("vk", offset))?;
+                Ok(Cell::KeyValue {
+                    name,
+                    key_value: key_value.node,
+                })
+            }
+            _ => cell::parse_cell_data(offset, &data),
+        }
+    }
+
+    // Function to get the header of the hive bin holding the cell at an offset in the hive bins
+    // data, e.g. to see which bin a carved record came from. The bin headers are read once, on
+    // the first call, and looked up from then on.
+    pub fn bin_at(&mut self, cell_offset: u32) -> Result<HiveBinHeader, HiveError> {
+        if self.bin_index.is_none() {
+            let hive_bins_data_size = self.base_block.hive_bins_data_size;
+            let mut bin_index = Vec::new();
+            let mut bin_offset = 0u32;
+            while bin_offset < hive_bins_data_size {
+                let header = self.read_bin_header(bin_offset)?;
+                bin_offset = offset_add(bin_offset, header.size() as usize)?;
+                bin_index.push(header);
+            }
+            self.bin_index = Some(bin_index);
+        }
+        let bin_index = self.bin_index.as_deref().unwrap_or_default();
+
+        // Bins are in offset order; the candidate is the last one starting at or before the cell,
+        // and cells only start after its header
+        let position = bin_index.partition_point(|header| header.offset() <= cell_offset);
+        match position.checked_sub(1).map(|position| bin_index[position]) {
+            Some(header)
+                if cell_offset >= header.offset() + mem::size_of::<HiveBinHeader>() as u32
+                    && cell_offset - header.offset() < header.size() =>
+            {
+                Ok(header)
+            }
+            _ => Err(HiveError::OffsetOutsideBins { offset: cell_offset }),
+        }
+    }
+
+    // Function to iterate over the cells of one hive bin (from `bin_at`) in file order, allocated
+    // and free alike, with each cell's offset, header and parsed contents. A cell that can't be
+    // parsed is an error and the next one follows; a cell size that would step outside the bin
+    // or not move forward ends the iteration with `HiveError::InvalidCell`.
+    pub fn cells_in_bin(&mut self, bin: &HiveBinHeader) -> BinCells<'_, R> {
+        let bin_offset = bin.offset();
+        let (bin, pending_error) = match self.read_bin(bin_offset) {
+            Ok(bin) => (bin, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        BinCells {
+            hive: self,
+            bin_offset,
+            bin,
+            position: mem::size_of::<HiveBinHeader>(),
+            pending_error,
+            finished: false,
+        }
+    }
+
+    // Function to read and validate the header of the hive bin at an offset, leaving the file
+    // positioned just after it
+    fn read_bin_header(&mut self, bin_offset: u32) -> Result<HiveBinHeader, HiveError> {
+        self.file
+            .seek(SeekFrom::Start(HIVE_BINS_OFFSET + bin_offset as u64))
+            .map_err(HiveError::reading("hbin", bin_offset))?;
+
+        let mut header_bytes = [0u8; mem::size_of::<HiveBinHeader>()];
+        self.file
+            .read_exact(&mut header_bytes)
+            .map_err(HiveError::reading("hbin", bin_offset))?;
+        let header: HiveBinHeader = unsafe { mem::transmute(header_bytes) };
+
+        // Bins are multiples of 4096 bytes, record their own offset and fit in the hive bins data
+        let bin_size = header.size();
+        let Some(remaining) = self.base_block.hive_bins_data_size.checked_sub(bin_offset) else {
+            return Err(HiveError::InvalidBin { offset: bin_offset });
+        };
+        if &header.signature != b"hbin"
+            || header.offset() != bin_offset
+            || bin_size < 4096
+            || !bin_size.is_multiple_of(4096)
+            || bin_size > remaining
+        {
+            return Err(HiveError::InvalidBin { offset: bin_offset });
+        }
+        Ok(header)
+    }
+
+    // Function to read a whole hive bin, header included, after validating its header
+    fn read_bin(&mut self, bin_offset: u32) -> Result<Vec<u8>, HiveError> {
+        let header = self.read_bin_header(bin_offset)?;
+        let bin_size = header.size();
+
+        let header_bytes: [u8; mem::size_of::<HiveBinHeader>()] = unsafe { mem::transmute(header) };
+        let mut bin = header_bytes.to_vec();
+        bin.extend(
+            read_bytes(&mut self.file, bin_size as usize - header_bytes.len()).map_err(HiveError::reading("hbin", bin_offset))?,
+        );
+        Ok(bin)
+    }
+
+    // Function to read the root key node and its name, checking that it is flagged as the hive's
+    // entry key so a bad root cell offset isn't mistaken for the root
+    fn read_root(&mut self) -> Result<(String, Located<KeyNode>), HiveError> {
+        if let Some(root) = &self.root {
+            return Ok(root.clone());
+        }
+
+        let root_cell_offset = self.base_block.root_cell_offset;
+        let (root_name, root_key_node) =
+            read_named_key_node(&mut self.file, root_cell_offset).map_err(HiveError::reading("nk", root_cell_offset))?;
+        if !root_key_node.flags().contains(KeyNodeFlags::HIVE_ENTRY) {
+            return Err(HiveError::InvalidRootKey { offset: root_cell_offset });
+        }
+
+        self.root = Some((root_name.clone(), root_key_node));
+        Ok((root_name, root_key_node))
+    }
+
+    // Function to find the first direct subkey whose name `names_equal` accepts
+    fn find_subkey_named(
+        &mut self,
+        key_node: &KeyNode,
+        subkey_name: &str,
+        names_equal: fn(&str, &str) -> bool,
+    ) -> Result<Option<Located<KeyNode>>, HiveError> {
+        for offset in self.subkey_offsets(key_node)? {
+            let (key_name, subkey_node) =
+                read_named_key_node(&mut self.file, offset).map_err(HiveError::reading("nk", offset))?;
+            if names_equal(&key_name, subkey_name) {
+                return Ok(Some(subkey_node));
+            }
+        }
+        Ok(None)
+    }
+
+    // Function to read the type and data of a key node's value by name, comparing
+    // case-insensitively, or None if the key has no such value. Values are read up to the first
+    // match. A value that can't be read fails the lookup, unless `skip_errors` is set (and
+    // `strict` isn't): then it is passed over and only fails the lookup when no other value
+    // matches, since the unreadable one might have been it.
+    pub(crate) fn value_data_named(
+        &mut self,
+        key_node: &KeyNode,
+        value_name: &str,
+    ) -> Result<Option<(u32, Vec<u8>)>, HiveError> {
+        let pass_over_errors = self.options.skip_errors && !self.options.strict;
+        let mut first_error = None;
+        for offset in self.value_offsets(key_node)? {
+            match read_named_key_value(&mut self.file, offset) {
+                Ok((name, key_value)) if names_match(&name, value_name) => {
+                    return Ok(Some((key_value.data_type(), self.value_data(&key_value)?)));
+                }
+                Ok(_) => {}
+                Err(e) if pass_over_errors => {
+                    first_error.get_or_insert(HiveError::reading("vk", offset)(e));
+                }
+                Err(e) => return Err(HiveError::reading("vk", offset)(e)),
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(None),
+        }
+    }
+
+    // Function to read the offsets of a key node's values. A zero count or a missing (0xFFFFFFFF)
+    // value list both mean no values, but a nonzero count without a list is corruption.
+    fn value_offsets(&mut self, key_node: &KeyNode) -> Result<Vec<u32>, HiveError> {
+        let number_of_key_values = key_node.number_of_key_values;
+        let list_offset = key_node.key_values_list_offset;
+        if key_node.predefined_handle().is_some() || number_of_key_values == 0 {
+            return Ok(Vec::new());
+        }
+        if list_offset == 0xFFFFFFFF {
+            return Err(HiveError::InconsistentValueList { number_of_key_values });
+        }
+        read_value_offsets(&mut self.file, key_node)
+    }
+
+    // Function to read the offsets of the key nodes below a key node. The subkey fields of a
+    // predefined-handle key are meaningless, so they are not followed. A list is read whenever
+    // one is recorded, even under a zero count, so the count checks can see the disagreement.
+    fn subkey_offsets(&mut self, key_node: &KeyNode) -> Result<Vec<u32>, HiveError> {
+        if key_node.subkeys_list_offset == 0xFFFFFFFF || key_node.predefined_handle().is_some() {
+            return Ok(Vec::new());
+        }
+        let list_offset = key_node.subkeys_list_offset;
+        read_subkey_offsets(&mut self.file, list_offset)
+    }
+}
+
+// Name access that borrows from the buffer of an in-memory hive instead of allocating. ASCII names
+// (the common case) are returned as borrowed slices; Latin-1 and UTF-16 names need transcoding
+// and are returned owned.
+impl<T: AsRef<[u8]>> Hive<Cursor<T>> {
+    // Function to list the names of a key node's direct subkeys in stored order
+    pub fn subkey_names_borrowed(&self, key_node: &KeyNode) -> Result<Vec<Cow<'_, str>>, HiveError> {
+        let bytes = self.file.get_ref().as_ref();
+        let list_offset = key_node.subkeys_list_offset;
+        if list_offset == 0xFFFFFFFF || key_node.predefined_handle().is_some() {
+            return Ok(Vec::new());
+        }
+        let offsets = read_subkey_offsets(&mut Cursor::new(bytes), list_offset)?;
+
+        offsets
+            .into_iter()
+            .map(|offset| {
+                let key_node_bytes = cell_slice(bytes, offset, 0, mem::size_of::<KeyNode>())?;
+                if &key_node_bytes[..2] != b"nk" {
+                    return Err(HiveError::InvalidCell { offset });
+                }
+                let flags = u16::from_le_bytes([key_node_bytes[2], key_node_bytes[3]]);
+                let name_length = u16::from_le_bytes([key_node_bytes[72], key_node_bytes[73]]) as usize;
+                let name = cell_slice(bytes, offset, mem::size_of::<KeyNode>(), name_length)?;
+                Ok(borrow_name(name, KeyNodeFlags(flags).contains(KeyNodeFlags::COMP_NAME)))
+            })
+            .collect()
+    }
+
+    // Function to list the names of a key node's values in stored order ("" is the default value)
+    pub fn value_names_borrowed(&self, key_node: &KeyNode) -> Result<Vec<Cow<'_, str>>, HiveError> {
+        let bytes = self.file.get_ref().as_ref();
+        let number_of_key_values = key_node.number_of_key_values;
+        let list_offset = key_node.key_values_list_offset;
+        if key_node.predefined_handle().is_some() || number_of_key_values == 0 {
+            return Ok(Vec::new());
+        }
+        if list_offset == 0xFFFFFFFF {
+            return Err(HiveError::InconsistentValueList { number_of_key_values });
+        }
+        let offsets = read_value_offsets(&mut Cursor::new(bytes), key_node)?;
+
+        offsets
+            .into_iter()
+            .map(|offset| {
+                let key_value_bytes = cell_slice(bytes, offset, 0, mem::size_of::<KeyValue>())?;
+                if &key_value_bytes[..2] != b"vk" {
+                    return Err(HiveError::InvalidCell { offset });
+                }
+                let name_length = u16::from_le_bytes([key_value_bytes[2], key_value_bytes[3]]) as usize;
+                let flags = u16::from_le_bytes([key_value_bytes[16], key_value_bytes[17]]);
+                let name = cell_slice(bytes, offset, mem::size_of::<KeyValue>(), name_length)?;
+                // Bit 0 of a key value's flags marks an ASCII (Latin-1) name
+                Ok(borrow_name(name, flags & 0x0001 != 0))
+            })
+            .collect()
+    }
+
+    // Function to read a key value's data, borrowed from the buffer when it is stored in a single
+    // data cell. Data held inside the key value itself or split into big data segments is copied.
+    pub fn value_data_borrowed(&mut self, key_value: &KeyValue) -> Result<Cow<'_, [u8]>, HiveError> {
+        let data_size = key_value.data_len();
+        if key_value.is_resident() || self.is_big_data(data_size) {
+            return Ok(Cow::Owned(self.value_data(key_value)?));
+        }
+
+        let bytes = self.file.get_ref().as_ref();
+        Ok(Cow::Borrowed(cell_slice(bytes, key_value.data_offset, 0, data_size as usize)?))
+    }
+}
+
+// Function to slice `length` bytes starting `start` bytes into a cell's data, checking bounds
+fn cell_slice(bytes: &[u8], cell_offset: u32, start: usize, length: usize) -> Result<&[u8], HiveError> {
+    // Computed in u64 and only then narrowed, so nothing can wrap on 32-bit targets
+    let data_start = cell_data_offset(cell_offset) + start as u64;
+    let data_end = data_start + length as u64;
+    let (Ok(data_start), Ok(data_end)) = (usize::try_from(data_start), usize::try_from(data_end)) else {
+        return Err(HiveError::OffsetOverflow { offset: cell_offset });
+    };
+    bytes
+        .get(data_start..data_end)
+        .ok_or(HiveError::InvalidCell { offset: cell_offset })
+}
+
+// Function to decode a stored name, borrowing it when it is plain ASCII
+fn borrow_name(name: &[u8], compressed: bool) -> Cow<'_, str> {
+    if !compressed {
+        let name_utf16: Vec<u16> = name
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect();
+        return Cow::Owned(String::from_utf16_lossy(&name_utf16));
+    }
+    match std::str::from_utf8(name) {
+        Ok(name) if name.is_ascii() => Cow::Borrowed(name),
+        // Compressed names are Latin-1, whose bytes map one-to-one onto the first 256 code points
+        _ => Cow::Owned(name.iter().map(|&byte| byte as char).collect()),
+    }
+}
+
+// Function to compare key names the way Windows does, ignoring case
+pub(crate) fn names_match(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b) || a.to_uppercase() == b.to_uppercase()
+}
+
+// Iterator over the direct subkeys of a key node
+pub struct Subkeys<'a, R> {
+    hive: &'a mut Hive<R>,
+    offsets: std::vec::IntoIter<u32>,
+    pending_error: Option<HiveError>,
+    finished: bool,
+}
+
+impl<R: Read + Seek> Iterator for Subkeys<'_, R> {
+    type Item = Result<(String, Located<KeyNode>), HiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The subkey list itself could not be read, so there are no siblings to carry on with
+        if let Some(e) = self.pending_error.take() {
+            self.finished = true;
+            return Some(Err(e));
+        }
+        if self.finished {
+            return None;
+        }
+
+        let offset = self.offsets.next()?;
+        match read_named_key_node(&mut self.hive.file, offset) {
+            Ok(subkey) => Some(Ok(subkey)),
+            Err(e) => {
+                if !self.hive.options.skip_errors {
+                    self.finished = true;
+                }
+                Some(Err(HiveError::reading("nk", offset)(e)))
+            }
+        }
+    }
+}
+
+// Function to check whether a cell offset holds a key node flagged as the hive's entry key
+fn is_root_key_at<R: Read + Seek>(reader: &mut R, offset: u32) -> bool {
+    read_named_key_node(reader, offset).is_ok_and(|(_, key_node)| key_node.flags().contains(KeyNodeFlags::HIVE_ENTRY))
+}
+
+// Iterator over the values of a key node
+pub struct Values<'a, R> {
+    hive: &'a mut Hive<R>,
+    offsets: std::vec::IntoIter<u32>,
+    pending_error: Option<HiveError>,
+    finished: bool,
+}
+
+impl<R: Read + Seek> Iterator for Values<'_, R> {
+    type Item = Result<(String, Located<KeyValue>), HiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The value list itself could not be read, so there are no other values to carry on with
+        if let Some(e) = self.pending_error.take() {
+            self.finished = true;
+            return Some(Err(e));
+        }
+        if self.finished {
+            return None;
+        }
+
+        let offset = self.offsets.next()?;
+        match read_named_key_value(&mut self.hive.file, offset) {
+            Ok(value) => Some(Ok(value)),
+            Err(e) => {
+                if !self.hive.options.skip_errors {
+                    self.finished = true;
+                }
+                Some(Err(HiveError::reading("vk", offset)(e)))
+            }
+        }
+    }
+}
+
+// Iterator over the cells of a hive bin, from `Hive::cells_in_bin`
+pub struct BinCells<'a, R> {
+    hive: &'a mut Hive<R>,
+    bin_offset: u32,
+    bin: Vec<u8>,
+    position: usize, // Of the next cell within the bin
+    pending_error: Option<HiveError>,
+    finished: bool,
+}
+
+impl<R: Read + Seek> Iterator for BinCells<'_, R> {
+    type Item = Result<(u32, CellHeader, Cell), HiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The bin itself could not be read
+        if let Some(e) = self.pending_error.take() {
+            self.finished = true;
+            return Some(Err(e));
+        }
+        if self.finished || self.position + 4 > self.bin.len() {
+            return None;
+        }
+
+        let position = self.position;
+        let cell_offset = match offset_add(self.bin_offset, position) {
+            Ok(cell_offset) => cell_offset,
+            Err(e) => {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        };
+        let cell_header = CellHeader::from_bytes([
+            self.bin[position],
+            self.bin[position + 1],
+            self.bin[position + 2],
+            self.bin[position + 3],
+        ]);
+        // Every cell is at least 8 bytes, so a smaller size would never reach the next one
+        let cell_length = cell_header.size() as usize;
+        if cell_length < 8 || !cell_length.is_multiple_of(8) || position + cell_length > self.bin.len() {
+            self.finished = true;
+            return Some(Err(HiveError::InvalidCell { offset: cell_offset }));
+        }
+        self.position += cell_length;
+
+        Some(self.hive.parse_cell(cell_offset).map(|cell| (cell_offset, cell_header, cell)))
+    }
+}
+
+// Struct representing a key whose subkeys are still being walked
+struct WalkFrame {
+    path: String,
+    depth: usize,
+    offsets: std::vec::IntoIter<u32>,
+}
+
+// Iterator over every key below the root, or those a filter on the key node keeps, depth-first
+// in stored order
+pub struct Walk<'a, R, F = fn(&KeyNode) -> bool> {
+    hive: &'a mut Hive<R>,
+    start: Option<WalkEntry>, // The key to start from, or None for the root
+    stack: Vec<WalkFrame>,
+    visited: HashSet<u32>, // Offsets of the key nodes yielded so far
+    pending_error: Option<HiveError>,
+    started: bool,
+    finished: bool,
+    keys_walked: u64, // Counted against `HiveOptions::max_keys`
+    max_depth: usize, // Deepest level whose subkeys are followed, so also the most frames on `stack`
+    filter: F,
+}
+
+impl<R: Read + Seek, F> Walk<'_, R, F> {
+    // Function to queue the subkeys of a key that has just been yielded
+    fn descend(&mut self, path: &str, depth: usize, key_node: &KeyNode) {
+        let offsets = match self.hive.subkey_offsets(key_node) {
+            Ok(offsets) => offsets,
+            Err(e) => {
+                self.pending_error = Some(e);
+                return;
+            }
+        };
+        if offsets.is_empty() {
+            return;
+        }
+
+        if depth >= self.max_depth {
+            self.pending_error = Some(HiveError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Key \"{}\" is nested deeper than {} levels", path, self.max_depth),
+            )));
+            return;
+        }
+
+        self.stack.push(WalkFrame {
+            path: path.to_string(),
+            depth,
+            offsets: offsets.into_iter(),
+        });
+    }
+
+    // Function to count one more key against `HiveOptions::max_keys`, ending the walk with
+    // BudgetExceeded once the limit has been reached
+    fn charge_key(&mut self) -> Result<(), HiveError> {
+        if let Some(max_keys) = self.hive.options.max_keys {
+            if self.keys_walked >= max_keys {
+                self.finished = true;
+                self.stack.clear();
+                return Err(HiveError::BudgetExceeded { max_keys });
+            }
+        }
+        self.keys_walked += 1;
+        Ok(())
+    }
+
+    // Function to surface an error, ending the walk unless errors are being skipped
+    fn fail(&mut self, e: HiveError) -> Result<(String, Located<KeyNode>), HiveError> {
+        if !self.hive.options.skip_errors {
+            self.finished = true;
+            self.stack.clear();
+        }
+        Err(e)
+    }
+}
+
+impl<R: Read + Seek, F: FnMut(&KeyNode) -> bool> Iterator for Walk<'_, R, F> {
+    type Item = Result<(String, Located<KeyNode>), HiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_key()? {
+                Ok((_, key_node)) if !(self.filter)(&key_node) => continue,
+                key => return Some(key),
+            }
+        }
+    }
+}
+
+impl<R: Read + Seek, F> Walk<'_, R, F> {
+    // Function to step to the next key in walk order, whether or not the filter keeps it
+    fn next_key(&mut self) -> Option<Result<(String, Located<KeyNode>), HiveError>> {
+        if let Some(e) = self.pending_error.take() {
+            return Some(self.fail(e));
+        }
+        if self.finished {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+            if let Err(e) = self.charge_key() {
+                return Some(Err(e));
+            }
+            if let Some((path, key_node)) = self.start.take() {
+                self.visited.insert(key_node.offset);
+                self.descend(&path, 0, &key_node);
+                return Some(Ok((path, key_node)));
+            }
+            return Some(match self.hive.read_root() {
+                Ok((_, root_key_node)) => {
+                    self.visited.insert(root_key_node.offset);
+                    self.descend("", 0, &root_key_node);
+                    Ok((String::new(), root_key_node))
+                }
+                Err(e) => {
+                    self.finished = true;
+                    Err(e)
+                }
+            });
+        }
+
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(offset) = frame.offsets.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let parent_path = frame.path.clone();
+            let depth = frame.depth + 1;
+            if let Err(e) = self.charge_key() {
+                return Some(Err(e));
+            }
+            // A key node can only have one parent; reaching one again means the subkey lists are
+            // cross-linked or cyclic, and following them could revisit the same keys endlessly
+            if !self.visited.insert(offset) {
+                return Some(self.fail(cross_linked_key(&parent_path, offset)));
+            }
+            return Some(match read_named_key_node(&mut self.hive.file, offset) {
+                Ok((key_name, key_node)) => {
+                    let path = if parent_path.is_empty() {
+                        key_name
+                    } else {
+                        format!("{}\\{}", parent_path, key_name)
+                    };
+                    self.descend(&path, depth, &key_node);
+                    Ok((path, key_node))
+                }
+                Err(e) => self.fail(HiveError::reading("nk", offset)(e)),
+            });
+        }
+    }
+}
+
+// Function to check whether a walked key's error is one `skip_errors` passes over. Running out
+// of `HiveOptions::max_keys` never is, so a capped walk can't pass for a complete one.
+fn skippable(skip_errors: bool, key: &Result<WalkEntry, HiveError>) -> bool {
+    skip_errors && matches!(key, Err(e) if !matches!(e, HiveError::BudgetExceeded { .. }))
+}
+
+// Function to build the error for a key node listed under more than one parent
+fn cross_linked_key(parent_path: &str, offset: u32) -> HiveError {
+    HiveError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Key node at {:#x} under \"{}\" is already part of the tree", offset, parent_path),
+    ))
+}
+
+// Iterator over the security descriptor list, from a key's own descriptor around the circle
+pub struct SecurityDescriptors<'a, R> {
+    hive: &'a mut Hive<R>,
+    start: u32,
+    next: Option<u32>,
+    visited: HashSet<u32>,
+}
+
+impl<R: Read + Seek> Iterator for SecurityDescriptors<'_, R> {
+    type Item = Result<SecurityDescriptor, HiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.next.take()?;
+        if !self.visited.insert(offset) {
+            return Some(Err(HiveError::SecurityListCycle { offset }));
+        }
+        let (flink, blink, reference_count, descriptor) = match self.hive.parse_cell(offset) {
+            Ok(Cell::SecurityDescriptor {
+                flink,
+                blink,
+                reference_count,
+                descriptor,
+            }) => (flink, blink, reference_count, descriptor),
+            Ok(_) => return Some(Err(HiveError::UnexpectedCell { offset, expected: "sk" })),
+            Err(e) => return Some(Err(e)),
+        };
+
+        // Back at the start: the circle is closed
+        if flink != self.start {
+            self.next = Some(flink);
+        }
+        Some(Ok(SecurityDescriptor {
+            offset,
+            flink,
+            blink,
+            reference_count,
+            descriptor,
+        }))
+    }
+}
+
+// Iterator over every value in the hive, or those a filter on the key value keeps, in walk order
+pub struct AllValues<'a, R, F = fn(&KeyValue) -> bool> {
+    walk: Walk<'a, R>,
+    key: Option<(String, std::vec::IntoIter<u32>)>, // Path and remaining value offsets of the current key
+    finished: bool,
+    value_name: Option<String>, // Only values with this name, for `find_value_in_subtree`
+    filter: F,
+}
+
+impl<R: Read + Seek, F: FnMut(&KeyValue) -> bool> AllValues<'_, R, F> {
+    // Function to surface an error, ending the iteration unless errors are being skipped
+    fn fail(&mut self, e: HiveError) -> Option<Result<(String, String, RegistryValue), HiveError>> {
+        if !self.walk.hive.options.skip_errors {
+            self.finished = true;
+        }
+        Some(Err(e))
+    }
+}
+
+impl<R: Read + Seek, F: FnMut(&KeyValue) -> bool> Iterator for AllValues<'_, R, F> {
+    type Item = Result<(String, String, RegistryValue), HiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            let Some((path, offsets)) = self.key.as_mut() else {
+                // Move on to the next key that has values
+                let (path, key_node) = match self.walk.next()? {
+                    Ok(key) => key,
+                    Err(e) => return self.fail(e),
+                };
+                match self.walk.hive.value_offsets(&key_node) {
+                    Ok(offsets) => self.key = Some((path, offsets.into_iter())),
+                    Err(e) => return self.fail(e),
+                }
+                continue;
+            };
+            let Some(offset) = offsets.next() else {
+                self.key = None;
+                continue;
+            };
+            let path = path.clone();
+
+            let hive = &mut *self.walk.hive;
+            let (value_name, key_value) = match read_named_key_value(&mut hive.file, offset) {
+                Ok(value) => value,
+                Err(e) => return self.fail(HiveError::reading("vk", offset)(e)),
+            };
+            if self.value_name.as_ref().is_some_and(|wanted| !names_match(&value_name, wanted)) || !(self.filter)(&key_value) {
+                continue;
+            }
+            let value = hive.read_value(&key_value).map(|value| (value_name, value));
+            return match value {
+                Ok((value_name, value)) => Some(Ok((path, value_name, value))),
+                Err(e) => self.fail(e),
+            };
+        }
+    }
+}
@@ -0,0 +1,1112 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    mem,
+    ops::Deref,
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+
+use cell::read_cell_header;
+use hive::names_match;
+use log::LogFormat;
+
+#[cfg(feature = "tokio")]
+mod async_hive;
+pub mod artifacts;
+mod capabilities;
+mod cell;
+mod diff;
+mod dot;
+mod error;
+mod hive;
+pub mod log;
+#[cfg(feature = "lsa")]
+pub mod lsa;
+mod offset_reader;
+mod provenance;
+mod recovery;
+mod reg;
+mod report;
+pub mod resource;
+pub mod search;
+mod security;
+mod shared_hive;
+mod stats;
+pub mod time;
+mod validate;
+mod value;
+mod value_reader;
+
+#[cfg(feature = "tokio")]
+pub use async_hive::AsyncHive;
+pub use capabilities::HiveCapabilities;
+pub use cell::{Cell, CellHeader};
+pub use diff::{DiffEntry, HiveDiff};
+pub use error::HiveError;
+pub use hive::{AllValues, BinCells, Hive, HiveOptions, SecurityDescriptors, Subkeys, Values, Walk, WalkEntry};
+pub use offset_reader::OffsetReader;
+pub use provenance::OpenManifest;
+pub use reg::RegFormat;
+pub use report::{ParseReport, Warning, WarningKind};
+pub use security::SecurityDescriptor;
+pub use shared_hive::{HiveView, SharedHive};
+pub use stats::{HeaderSummary, HiveStatistics, LargestValue};
+pub use validate::{ValidationIssue, ValidationReport};
+pub use value::RegistryValue;
+pub use value_reader::ValueReader;
+
+// Offset of the hive bins data, which follows the 4096-byte base block.
+// The base block and hive bins are sized and aligned in 4096-byte units whatever the sector
+// size, so the clustering factor never moves anything in a primary file; it only sets the
+// sector size used to lay out transaction logs.
+const HIVE_BINS_OFFSET: u64 = 4096;
+
+// Largest clustering factor accepted (32 KiB logical sectors); real hives almost always use 1
+pub(crate) const MAX_CLUSTERING_FACTOR: u32 = 64;
+
+// Largest amount of value data stored in one cell. Longer data is split into segments of this
+// size behind a "db" (big data) record, from hive version 1.4 on.
+pub(crate) const BIG_DATA_SEGMENT_SIZE: u32 = 16344;
+
+// Index roots normally point straight at leaves; allow a few levels of nesting for huge keys
+const MAX_INDEX_ROOT_DEPTH: usize = 8;
+
+// Struct representing the base block of a registry file.
+// Packed so the layout matches the on-disk 4096 bytes exactly (no padding before the timestamp).
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BaseBlock {
+    signature: [u8; 4],         // Offset 0:  "regf"
+    primary_seq_num: u32,       // Offset 4
+    secondary_seq_num: u32,     // Offset 8
+    last_written_timestamp: u64, // Offset 12
+    major_version: u32,         // Offset 20: 1
+    minor_version: u32,         // Offset 24: 3, 4, 5, or 6
+    file_type: u32,           // Offset 28: 0 means primary file
+    file_format: u32,          // Offset 32: 1 means direct memory load
+    root_cell_offset: u32,       // Offset 36: Offset of the root cell in the hive bins data
+    hive_bins_data_size: u32,      // Offset 40: Size of the hive bins data
+    clustering_factor: u32,      // Offset 44: Logical sector size / 512
+    file_name: [u16; 32],       // Offset 48
+    reserved1: [u8; 396],         // Offset 112
+    checksum: u32,           // Offset 508: XOR-32 checksum of the previous 508 bytes
+    reserved2: [u8; 3576],        // Offset 512
+    boot_type: u32,        // Offset 4088
+    boot_recover: u32        // Offset 4092
+}
+
+impl BaseBlock {
+    // Function to compute the XOR-32 checksum of the first 508 bytes, as stored at offset 508.
+    // Windows never stores 0 or 0xFFFFFFFF, substituting 1 and 0xFFFFFFFE.
+    pub(crate) fn computed_checksum(&self) -> u32 {
+        let base_block_bytes: &[u8; 4096] = unsafe { mem::transmute(self) };
+        let checksum = base_block_bytes[..508]
+            .chunks_exact(4)
+            .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+        match checksum {
+            0 => 1,
+            0xFFFFFFFF => 0xFFFFFFFE,
+            checksum => checksum,
+        }
+    }
+
+    // Function to get the logical sector size (clustering factor * 512), or None if the
+    // clustering factor is not a power of two between 1 and MAX_CLUSTERING_FACTOR
+    pub(crate) fn sector_size(&self) -> Option<u64> {
+        let clustering_factor = self.clustering_factor;
+        if clustering_factor == 0 || clustering_factor > MAX_CLUSTERING_FACTOR || !clustering_factor.is_power_of_two() {
+            return None;
+        }
+        Some(clustering_factor as u64 * 512)
+    }
+
+    // Function to classify the file by the base block's file type
+    pub(crate) fn hive_kind(&self) -> HiveKind {
+        match self.file_type {
+            0 => HiveKind::Primary,
+            1 | 2 => HiveKind::TransactionLog(LogFormat::Old),
+            6 => HiveKind::TransactionLog(LogFormat::New),
+            other => HiveKind::Other(other),
+        }
+    }
+}
+
+// Enum for the kinds of file that start with a "regf" base block, from its file type (offset 28).
+// Only primary hives are parsed. Logs are read with `log::TransactionLog`. Any other type, such as
+// the differencing hives Windows layers over a base hive for containers, is reported as Other:
+// their layered keys only make sense merged with the hives beneath them, which this crate doesn't do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HiveKind {
+    Primary,                   // File type 0
+    TransactionLog(LogFormat), // File type 1, 2 or 6
+    Other(u32),                // Carries the file type
+}
+
+// Struct representing a hive bin header
+// Packed so the layout matches the on-disk 32-byte header (no padding before the timestamp).
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)]
+pub struct HiveBinHeader {
+    signature: [u8; 4],
+    offset: u32,
+    size: u32,
+    reserved: [u8; 8],
+    timestamp: u64,
+    spare: u32,
+}
+
+impl HiveBinHeader {
+    // Function to get the bin's offset in the hive bins data, which is also where its header is
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    // Function to get the bin's size in bytes, header included (a multiple of 4096)
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    // Function to get the timestamp in the bin header. Windows only keeps it up to date in the
+    // first bin; in the others it is usually 0, which gives 1601-01-01.
+    pub fn last_written(&self) -> DateTime<Utc> {
+        time::filetime_to_datetime(self.timestamp)
+    }
+}
+
+// Struct representing a key node
+// Packed so the layout matches the on-disk 76-byte structure (no padding before the timestamp).
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+pub struct KeyNode {
+    signature: [u8; 2],
+    flags: u16,
+    last_written_timestamp: u64,
+    access_bits: u32,
+    parent: u32,
+    number_of_subkeys: u32,
+    number_of_volatile_subkeys: u32,
+    subkeys_list_offset: u32,
+    volatile_subkeys_list_offset: u32,
+    number_of_key_values: u32,
+    key_values_list_offset: u32,
+    key_security_offset: u32,
+    class_name_offset: u32,
+    largest_subkey_name_length: u32, //This field can be split
+    largest_subkey_class_name_length: u32,
+    largest_value_name_length: u32,
+    largest_value_data_size: u32,
+    workvar: u32,
+    key_name_length: u16,
+    class_name_length: u16,
+    // Key name string (variable length) - this is handled with an unsafe byte slice
+}
+
+impl KeyNode {
+    // Function to get the key node's flags
+    pub fn flags(&self) -> KeyNodeFlags {
+        KeyNodeFlags(self.flags)
+    }
+
+    // Function to get when the key, or the list of its values, was last written
+    pub fn last_written(&self) -> DateTime<Utc> {
+        time::filetime_to_datetime(self.last_written_timestamp)
+    }
+
+    // Function to get the handle a predefined-handle key stands for (e.g. 0x80000004 for
+    // HKEY_PERFORMANCE_DATA), or None for an ordinary key. Such keys reuse the value count field
+    // for the handle and have no values or subkeys stored in the hive.
+    pub fn predefined_handle(&self) -> Option<u32> {
+        if self.flags().contains(KeyNodeFlags::PREDEF_HANDLE) {
+            Some(self.number_of_key_values)
+        } else {
+            None
+        }
+    }
+
+    // Function to dump every field of the key node, one per line, for debugging: offsets in hex,
+    // flags by name and the last-written time as a date. Unused offsets show as "none".
+    pub fn describe(&self) -> String {
+        // Copy the fields out first, since references into a packed struct aren't allowed
+        let KeyNode {
+            signature,
+            flags,
+            last_written_timestamp,
+            access_bits,
+            parent,
+            number_of_subkeys,
+            number_of_volatile_subkeys,
+            subkeys_list_offset,
+            volatile_subkeys_list_offset,
+            number_of_key_values,
+            key_values_list_offset,
+            key_security_offset,
+            class_name_offset,
+            largest_subkey_name_length,
+            largest_subkey_class_name_length,
+            largest_value_name_length,
+            largest_value_data_size,
+            workvar,
+            key_name_length,
+            class_name_length,
+        } = *self;
+
+        let flag_names = KeyNodeFlags(flags).names();
+        let flags = if flag_names.is_empty() {
+            format!("{:#06x}", flags)
+        } else {
+            format!("{:#06x} ({})", flags, flag_names.join(" | "))
+        };
+        let lines = [
+            ("Signature:", String::from_utf8_lossy(&signature).into_owned()),
+            ("Flags:", flags),
+            (
+                "Last written:",
+                format!(
+                    "{} ({:#018x})",
+                    time::filetime_to_datetime(last_written_timestamp).format("%Y-%m-%d %H:%M:%S%.f UTC"),
+                    last_written_timestamp
+                ),
+            ),
+            ("Access bits:", format!("{:#x}", access_bits)),
+            ("Parent:", describe_offset(parent)),
+            ("Subkeys:", number_of_subkeys.to_string()),
+            ("Volatile subkeys:", number_of_volatile_subkeys.to_string()),
+            ("Subkey list:", describe_offset(subkeys_list_offset)),
+            ("Volatile subkey list:", describe_offset(volatile_subkeys_list_offset)),
+            ("Values:", number_of_key_values.to_string()),
+            ("Value list:", describe_offset(key_values_list_offset)),
+            ("Security:", describe_offset(key_security_offset)),
+            ("Class name:", describe_offset(class_name_offset)),
+            ("Largest subkey name length:", largest_subkey_name_length.to_string()),
+            ("Largest subkey class length:", largest_subkey_class_name_length.to_string()),
+            ("Largest value name length:", largest_value_name_length.to_string()),
+            ("Largest value data size:", largest_value_data_size.to_string()),
+            ("Workvar:", format!("{:#x}", workvar)),
+            ("Key name length:", key_name_length.to_string()),
+            ("Class name length:", class_name_length.to_string()),
+        ];
+        describe_lines(&lines)
+    }
+}
+
+// Function to lay out `describe` output as aligned "label value" lines
+fn describe_lines(lines: &[(&str, String)]) -> String {
+    let width = lines.iter().map(|(label, _)| label.len()).max().unwrap_or_default() + 1;
+    lines
+        .iter()
+        .map(|(label, value)| format!("{:<width$}{}", label, value, width = width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Function to render a cell offset for `describe`, where 0xFFFFFFFF means unused
+fn describe_offset(offset: u32) -> String {
+    if offset == 0xFFFFFFFF {
+        "none".to_string()
+    } else {
+        format!("{:#x}", offset)
+    }
+}
+
+// Struct representing the flags of a key node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyNodeFlags(pub u16);
+
+impl KeyNodeFlags {
+    pub const VOLATILE: KeyNodeFlags = KeyNodeFlags(0x0001); // Not stored on disk
+    pub const HIVE_EXIT: KeyNodeFlags = KeyNodeFlags(0x0002); // Mount point of another hive
+    pub const HIVE_ENTRY: KeyNodeFlags = KeyNodeFlags(0x0004); // Root key of the hive
+    pub const NO_DELETE: KeyNodeFlags = KeyNodeFlags(0x0008);
+    pub const SYM_LINK: KeyNodeFlags = KeyNodeFlags(0x0010);
+    pub const COMP_NAME: KeyNodeFlags = KeyNodeFlags(0x0020); // Name is stored as ASCII (Latin-1)
+    pub const PREDEF_HANDLE: KeyNodeFlags = KeyNodeFlags(0x0040);
+    pub const VIRTUAL_SOURCE: KeyNodeFlags = KeyNodeFlags(0x0080);
+    pub const VIRTUAL_TARGET: KeyNodeFlags = KeyNodeFlags(0x0100);
+    pub const VIRTUAL_STORE: KeyNodeFlags = KeyNodeFlags(0x0200);
+
+    // Names of the flags above, in bit order
+    const NAMES: [(KeyNodeFlags, &'static str); 10] = [
+        (KeyNodeFlags::VOLATILE, "VOLATILE"),
+        (KeyNodeFlags::HIVE_EXIT, "HIVE_EXIT"),
+        (KeyNodeFlags::HIVE_ENTRY, "HIVE_ENTRY"),
+        (KeyNodeFlags::NO_DELETE, "NO_DELETE"),
+        (KeyNodeFlags::SYM_LINK, "SYM_LINK"),
+        (KeyNodeFlags::COMP_NAME, "COMP_NAME"),
+        (KeyNodeFlags::PREDEF_HANDLE, "PREDEF_HANDLE"),
+        (KeyNodeFlags::VIRTUAL_SOURCE, "VIRTUAL_SOURCE"),
+        (KeyNodeFlags::VIRTUAL_TARGET, "VIRTUAL_TARGET"),
+        (KeyNodeFlags::VIRTUAL_STORE, "VIRTUAL_STORE"),
+    ];
+
+    // Function to check whether every flag in `other` is set
+    pub fn contains(self, other: KeyNodeFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    // Function to list the names of the set flags, in bit order. Unknown bits are left out.
+    pub fn names(self) -> Vec<&'static str> {
+        KeyNodeFlags::NAMES
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+// Struct representing a key value
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct KeyValue {
+    signature: [u8; 2],
+    name_length: u16,
+    data_size: u32,
+    data_offset: u32,
+    data_type: u32,
+    flags: u16,
+    spare: u16
+    // Value name string (variable length) - this is handled with an unsafe byte slice
+}
+
+impl KeyValue {
+    // Function to get the REG_* type of the value's data
+    pub fn data_type(&self) -> u32 {
+        self.data_type
+    }
+
+    // Function to check whether the data (at most 4 bytes) is held in the data offset field
+    // itself rather than in a cell of its own, which the top bit of the data size marks
+    pub fn is_resident(&self) -> bool {
+        self.data_size & 0x80000000 != 0
+    }
+
+    // Function to get the length of the value's data in bytes, without the resident bit
+    pub fn data_len(&self) -> u32 {
+        self.data_size & 0x7FFFFFFF
+    }
+
+    // Function to dump every field of the key value, one per line, for debugging: the data type
+    // by name, whether the data is resident in the data offset field, and offsets in hex
+    pub fn describe(&self) -> String {
+        let data_type = match value::type_name(self.data_type) {
+            Some(type_name) => format!("{} ({})", type_name, self.data_type),
+            None => format!("unknown ({:#x})", self.data_type),
+        };
+        let resident = self.is_resident();
+        let data_offset = if resident {
+            format!("{:#010x} (resident data)", self.data_offset)
+        } else {
+            describe_offset(self.data_offset)
+        };
+        // Bit 0 of the flags says the name is stored as ASCII (Latin-1) rather than UTF-16
+        let flags = if self.flags & 0x0001 != 0 {
+            format!("{:#06x} (COMP_NAME)", self.flags)
+        } else {
+            format!("{:#06x}", self.flags)
+        };
+        let lines = [
+            ("Signature:", String::from_utf8_lossy(&self.signature).into_owned()),
+            ("Name length:", self.name_length.to_string()),
+            (
+                "Data size:",
+                format!("{}{}", self.data_len(), if resident { " (resident)" } else { "" }),
+            ),
+            ("Data offset:", data_offset),
+            ("Data type:", data_type),
+            ("Flags:", flags),
+            ("Spare:", format!("{:#x}", self.spare)),
+        ];
+        describe_lines(&lines)
+    }
+}
+
+// Struct representing a record together with the cell offset (relative to the hive bins data)
+// it was read from, so reports can cite where it came from and the raw cell can be re-read
+#[derive(Debug, Clone, Copy)]
+pub struct Located<T> {
+    pub offset: u32,
+    pub node: T,
+}
+
+impl<T> Deref for Located<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.node
+    }
+}
+
+// Enum for subkey list type
+#[derive(Debug, PartialEq)]
+enum SubkeyListType {
+    IndexLeaf,
+    FastLeaf,
+    HashLeaf,
+    IndexRoot,
+    // The subkey list offset points straight at the only subkey's key node, with no list cell
+    KeyNode,
+    Unknown,
+}
+
+// Function to translate a cell offset (relative to the hive bins data) into the file offset of
+// the cell's data, skipping the 4-byte cell size header. The sum is done in u64, where even
+// u32::MAX plus the base cannot wrap, so file positions past 4 GiB come out right.
+pub(crate) fn cell_data_offset(cell_offset: u32) -> u64 {
+    HIVE_BINS_OFFSET + cell_offset as u64 + 4
+}
+
+// Function to read `length` bytes from the current position. The buffer grows with the bytes
+// actually read rather than being sized from `length` up front, so a corrupt size or count field
+// in a small or truncated hive fails with UnexpectedEof instead of allocating gigabytes.
+pub(crate) fn read_bytes<R: Read>(file: &mut R, length: usize) -> Result<Vec<u8>, std::io::Error> {
+    let mut bytes = Vec::new();
+    file.take(length as u64).read_to_end(&mut bytes)?;
+    if bytes.len() < length {
+        return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "failed to fill whole buffer"));
+    }
+    Ok(bytes)
+}
+
+// Function to advance an offset within the hive bins data by a byte count, failing with
+// OffsetOverflow instead of wrapping past u32::MAX
+pub(crate) fn offset_add(offset: u32, length: usize) -> Result<u32, HiveError> {
+    u32::try_from(length)
+        .ok()
+        .and_then(|length| offset.checked_add(length))
+        .ok_or(HiveError::OffsetOverflow { offset })
+}
+
+// Function to read a key node and its name from a cell offset
+pub(crate) fn read_named_key_node<R: Read + Seek>(
+    file: &mut R,
+    cell_offset: u32,
+) -> Result<(String, Located<KeyNode>), std::io::Error> {
+    let key_node = read_key_node(file, cell_data_offset(cell_offset))?;
+    let key_name = read_key_name(file, &key_node)?;
+    Ok((key_name, Located { offset: cell_offset, node: key_node }))
+}
+
+// Function to read a key value and its name from a cell offset
+pub(crate) fn read_named_key_value<R: Read + Seek>(
+    file: &mut R,
+    cell_offset: u32,
+) -> Result<(String, Located<KeyValue>), std::io::Error> {
+    let key_value = read_key_value(file, cell_data_offset(cell_offset))?;
+    let value_name = read_key_value_name(file, &key_value)?;
+    Ok((value_name, Located { offset: cell_offset, node: key_value }))
+}
+
+// Function to read the key value offsets from a key node's value list cell. The cell has to hold
+// the whole declared count, so a corrupt count can't run on through the cells that follow.
+pub(crate) fn read_value_offsets<R: Read + Seek>(
+    file: &mut R,
+    key_node: &KeyNode,
+) -> Result<Vec<u32>, HiveError> {
+    let (number_of_key_values, list_offset) = (key_node.number_of_key_values, key_node.key_values_list_offset);
+    if number_of_key_values == 0 || list_offset == 0xFFFFFFFF {
+        return Ok(Vec::new());
+    }
+    check_list_capacity(file, list_offset, 0, number_of_key_values as usize, 4)?;
+
+    let offsets_bytes = file.seek(SeekFrom::Start(cell_data_offset(list_offset)))
+        .and_then(|_| read_bytes(file, number_of_key_values as usize * 4))
+        .map_err(HiveError::reading("value list", list_offset))?;
+
+    Ok(offsets_bytes
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect())
+}
+
+// Function to check that the list cell at `list_offset` has room for `count` elements of
+// `element_size` bytes after a `header_size`-byte header (signature and count). This moves the
+// file cursor.
+pub(crate) fn check_list_capacity<R: Read + Seek>(
+    file: &mut R,
+    list_offset: u32,
+    header_size: usize,
+    count: usize,
+    element_size: usize,
+) -> Result<(), HiveError> {
+    let capacity = read_cell_header(file, list_offset)
+        .map_err(HiveError::reading("cell", list_offset))?
+        .data_size();
+    if header_size as u64 + count as u64 * element_size as u64 > capacity as u64 {
+        return Err(HiveError::CountExceedsCell {
+            offset: list_offset,
+            count: count as u32,
+            capacity,
+        });
+    }
+    Ok(())
+}
+
+// Function to read a key node's class name, or None if it has none
+pub(crate) fn read_class_name<R: Read + Seek>(
+    file: &mut R,
+    key_node: &KeyNode,
+) -> Result<Option<String>, std::io::Error> {
+    let Some(class_name_bytes) = read_class_name_bytes(file, key_node)? else {
+        return Ok(None);
+    };
+    let class_name_utf16: Vec<u16> = class_name_bytes
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .collect();
+    Ok(Some(String::from_utf16_lossy(&class_name_utf16)))
+}
+
+// Function to read the raw bytes of a key node's class name, or None if it has none. The class
+// name cell is checked to hold all `class_name_length` bytes so a bad length can't read into the
+// next cell.
+pub(crate) fn read_class_name_bytes<R: Read + Seek>(
+    file: &mut R,
+    key_node: &KeyNode,
+) -> Result<Option<Vec<u8>>, std::io::Error> {
+    let (class_name_offset, class_name_length) = (key_node.class_name_offset, key_node.class_name_length);
+    if class_name_offset == 0xFFFFFFFF || class_name_length == 0 {
+        return Ok(None);
+    }
+
+    let cell_data_length = read_cell_header(file, class_name_offset)?.data_size();
+    if class_name_length as u32 > cell_data_length {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Class name of {} bytes doesn't fit its cell of {} bytes at offset {:#x}",
+                class_name_length, cell_data_length, class_name_offset
+            ),
+        ));
+    }
+
+    let mut class_name_bytes = vec![0u8; class_name_length as usize];
+    file.read_exact(&mut class_name_bytes)?;
+    Ok(Some(class_name_bytes))
+}
+
+// Function to read the key node offsets referenced by a subkey list (li, lf, lh or ri). Each
+// list cell has to hold the element count it declares.
+pub(crate) fn read_subkey_offsets<R: Read + Seek>(
+    file: &mut R,
+    subkeys_list_offset: u32,
+) -> Result<Vec<u32>, HiveError> {
+    read_subkey_offsets_at_depth(file, subkeys_list_offset, 0)
+}
+
+fn read_subkey_offsets_at_depth<R: Read + Seek>(
+    file: &mut R,
+    subkeys_list_offset: u32,
+    depth: usize,
+) -> Result<Vec<u32>, HiveError> {
+    let reading = || HiveError::reading("subkey list", subkeys_list_offset);
+    let subkey_list_type = get_subkey_list_type(file, subkeys_list_offset).map_err(reading())?;
+    if subkey_list_type == SubkeyListType::KeyNode {
+        return Ok(vec![subkeys_list_offset]);
+    }
+
+    let mut num_elements_bytes = [0u8; 2];
+    file.read_exact(&mut num_elements_bytes).map_err(reading())?;
+    let num_elements = u16::from_le_bytes(num_elements_bytes) as usize;
+
+    // Fast and hash leaves pair every key node offset with a 4-byte name hint or hash
+    let element_size = match subkey_list_type {
+        SubkeyListType::IndexLeaf | SubkeyListType::IndexRoot => 4,
+        SubkeyListType::FastLeaf | SubkeyListType::HashLeaf => 8,
+        SubkeyListType::KeyNode | SubkeyListType::Unknown => {
+            return Err(reading()(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Unknown subkey list signature at offset {:#x}", subkeys_list_offset),
+            )))
+        }
+    };
+
+    // The elements follow the 2-byte signature and 2-byte count
+    check_list_capacity(file, subkeys_list_offset, 4, num_elements, element_size)?;
+    let elements = file.seek(SeekFrom::Start(cell_data_offset(subkeys_list_offset) + 4))
+        .and_then(|_| read_bytes(file, num_elements * element_size))
+        .map_err(reading())?;
+    let offsets: Vec<u32> = elements
+        .chunks_exact(element_size)
+        .map(|element| u32::from_le_bytes([element[0], element[1], element[2], element[3]]))
+        .collect();
+
+    if subkey_list_type != SubkeyListType::IndexRoot {
+        return Ok(offsets);
+    }
+
+    // An index root lists further subkey lists rather than key nodes; these are usually leaves
+    // but may themselves be index roots
+    check_index_root_depth(subkeys_list_offset, depth).map_err(reading())?;
+    let mut subkey_offsets = Vec::new();
+    for list_offset in offsets {
+        subkey_offsets.extend(read_subkey_offsets_at_depth(file, list_offset, depth + 1)?);
+    }
+    Ok(subkey_offsets)
+}
+
+// Function to reject index roots nested deeper than MAX_INDEX_ROOT_DEPTH, which only a
+// corrupt or cyclic hive would have
+fn check_index_root_depth(index_root_offset: u32, depth: usize) -> Result<(), std::io::Error> {
+    if depth >= MAX_INDEX_ROOT_DEPTH {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Index root at offset {:#x} is nested deeper than {} levels",
+                index_root_offset, MAX_INDEX_ROOT_DEPTH
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// Function to render a key or value name for display, escaping NULs and other control
+// characters as `\xNN`. Names can legitimately contain these (and malware uses them to hide
+// keys from regedit), and printed raw they can corrupt a terminal. Keep the raw name for
+// comparisons and lookups; this is for output only.
+pub fn display_name(name: &str) -> String {
+    let mut displayed = String::with_capacity(name.len());
+    for character in name.chars() {
+        if character.is_control() {
+            displayed.push_str(&format!("\\x{:02x}", character as u32));
+        } else {
+            displayed.push(character);
+        }
+    }
+    displayed
+}
+
+// Function to extract the syskey from the registry hive
+pub fn extract_syskey(hive_path: &Path) -> Result<Vec<u8>, HiveError> {
+    // Open the hive file
+    let mut file = File::open(hive_path)?;
+    extract_syskey_from_reader(&mut file)
+}
+
+// Function to tell what kind of file a reader holds from its base block, without opening it as a
+// hive. The checksum isn't checked, so this also classifies files `Hive::open` rejects.
+pub fn hive_kind<R: Read + Seek>(file: &mut R) -> Result<HiveKind, HiveError> {
+    let base_block = read_base_block(file).map_err(HiveError::reading("base block", 0))?;
+    Ok(base_block.hive_kind())
+}
+
+// Function to extract the syskey from a registry hive held in any seekable reader
+pub fn extract_syskey_from_reader<R: Read + Seek>(file: &mut R) -> Result<Vec<u8>, HiveError> {
+    // Read base block
+    let base_block = read_base_block(file)?;
+
+    // Only primary hives have keys laid out as the lookups below expect
+    if base_block.hive_kind() != HiveKind::Primary {
+        return Err(HiveError::UnsupportedHiveType(base_block.hive_kind()));
+    }
+
+    //Check file format, ensure it's 1 (direct memory load)
+    if base_block.file_format != 1 {
+        return Err(HiveError::UnsupportedFileFormat(base_block.file_format));
+    }
+
+    // Find the root key node
+    let root_cell_offset = base_block.root_cell_offset;
+    let root_key_node = read_key_node(file, cell_data_offset(root_cell_offset))?;
+
+    // Find CurrentControlSet subkey
+    let current_control_set_key =
+        find_subkey(file, &root_key_node, "CurrentControlSet")?;
+
+    // Find Control subkey
+    let control_key = find_subkey(file, &current_control_set_key, "Control")?;
+
+    // Find Lsa subkey
+    let lsa_key = find_subkey(file, &control_key, "Lsa")?;
+
+    // Find JD key value
+    let jd_key_value = find_key_value(file, &lsa_key, "JD")?;
+
+
+    // Extract Syskey
+     let syskey = extract_key_value_data(file, &jd_key_value, base_block.minor_version)?;
+
+
+    Ok(syskey)
+}
+
+// Function to read and validate the base block at the start of a hive or log file
+pub(crate) fn read_base_block<R: Read + Seek>(file: &mut R) -> Result<BaseBlock, std::io::Error> {
+    file.seek(SeekFrom::Start(0))?;
+
+    let mut base_block_bytes = [0u8; 4096];
+    file.read_exact(&mut base_block_bytes)?;
+    parse_base_block(&base_block_bytes)
+}
+
+// Function to interpret raw bytes as a base block and validate its signature
+pub(crate) fn parse_base_block(base_block_bytes: &[u8; 4096]) -> Result<BaseBlock, std::io::Error> {
+    let base_block: &BaseBlock = unsafe { mem::transmute(base_block_bytes) };
+
+    // Validate signature
+    if &base_block.signature != b"regf" {
+      return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid hive signature"))
+    }
+
+    Ok(*base_block)
+}
+
+// Function to read a key node from the file
+fn read_key_node<R: Read + Seek>(file: &mut R, offset: u64) -> Result<KeyNode, std::io::Error> {
+    file.seek(SeekFrom::Start(offset))?;
+
+    let mut key_node_bytes = [0u8; mem::size_of::<KeyNode>()];
+    file.read_exact(&mut key_node_bytes)?;
+
+    let key_node: &KeyNode = unsafe { mem::transmute(&key_node_bytes) };
+
+    //Validate key node signature
+    if &key_node.signature != b"nk" {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Invalid key node signature",
+        ));
+    }
+
+
+    Ok(*key_node)
+}
+
+// Function to read a key value from the file
+fn read_key_value<R: Read + Seek>(file: &mut R, offset: u64) -> Result<KeyValue, std::io::Error> {
+    file.seek(SeekFrom::Start(offset))?;
+
+    let mut key_value_bytes = [0u8; mem::size_of::<KeyValue>()];
+    file.read_exact(&mut key_value_bytes)?;
+
+    let key_value: &KeyValue = unsafe { mem::transmute(&key_value_bytes) };
+
+    if &key_value.signature != b"vk" {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Invalid key value signature",
+        ));
+    }
+
+    Ok(*key_value)
+}
+
+// Function to find a subkey with a given name
+fn find_subkey<R: Read + Seek>(
+    file: &mut R,
+    parent_key_node: &KeyNode,
+    subkey_name: &str,
+) -> Result<Located<KeyNode>, std::io::Error> {
+    if parent_key_node.subkeys_list_offset == 0xFFFFFFFF {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Subkey list is not present for the parent key node".to_string(),
+        ));
+    }
+    // The signature is read once here; the finders carry on from just after it
+    let subkeys_list_type = get_subkey_list_type(file, parent_key_node.subkeys_list_offset)?;
+
+    match subkeys_list_type {
+        SubkeyListType::IndexLeaf | SubkeyListType::FastLeaf | SubkeyListType::HashLeaf => {
+          let subkey_offset = find_subkey_in_list(file, parent_key_node.subkeys_list_offset, subkey_name, subkeys_list_type)?;
+
+          let subkey_node = read_key_node(file, cell_data_offset(subkey_offset))?;
+          Ok(Located { offset: subkey_offset, node: subkey_node })
+        },
+        SubkeyListType::IndexRoot => {
+          let subkey_offset = find_subkey_in_index_root(file, parent_key_node.subkeys_list_offset, subkey_name, 0)?;
+
+          let subkey_node = read_key_node(file, cell_data_offset(subkey_offset))?;
+          Ok(Located { offset: subkey_offset, node: subkey_node })
+
+        }
+        SubkeyListType::KeyNode => {
+          let subkey_offset = parent_key_node.subkeys_list_offset;
+          let subkey_node = read_key_node(file, cell_data_offset(subkey_offset))?;
+          if !names_match(&read_key_name(file, &subkey_node)?, subkey_name) {
+              return Err(std::io::Error::new(
+                  std::io::ErrorKind::NotFound,
+                  format!("Subkey with name \"{}\" not found", subkey_name),
+              ));
+          }
+          Ok(Located { offset: subkey_offset, node: subkey_node })
+        }
+      _ => Err(std::io::Error::other(
+          format!("Unsupported subkey list type: {:?}", subkeys_list_type),
+      )),
+    }
+}
+
+// Function to search an index root for a subkey, returning the key node's cell offset.
+// The file cursor must be just past the "ri" signature, as get_subkey_list_type leaves it.
+fn find_subkey_in_index_root<R: Read + Seek>(file: &mut R, index_root_offset: u32, subkey_name: &str, depth: usize) -> Result<u32, std::io::Error>{
+    check_index_root_depth(index_root_offset, depth)?;
+
+    let mut num_elements_bytes = [0u8; 2];
+    file.read_exact(&mut num_elements_bytes)?;
+    let num_elements = u16::from_le_bytes(num_elements_bytes) as usize;
+    check_list_capacity(file, index_root_offset, 4, num_elements, 4)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    file.seek(SeekFrom::Start(cell_data_offset(index_root_offset) + 4))?;
+
+    // Read every element up front, as searching a list moves the file cursor
+    let mut elements = vec![0u8; num_elements * 4];
+    file.read_exact(&mut elements)?;
+    let subkeys_list_offsets: Vec<u32> = elements
+        .chunks_exact(4)
+        .map(|element| u32::from_le_bytes([element[0], element[1], element[2], element[3]]))
+        .collect();
+
+    for subkeys_list_offset in subkeys_list_offsets {
+        let subkey_list_type = get_subkey_list_type(file, subkeys_list_offset)?;
+        let subkey_offset = if subkey_list_type == SubkeyListType::IndexRoot {
+            find_subkey_in_index_root(file, subkeys_list_offset, subkey_name, depth + 1)
+        } else {
+            find_subkey_in_list(file, subkeys_list_offset, subkey_name, subkey_list_type)
+        };
+        // Only a list that simply doesn't hold the name moves on to the next one; a damaged
+        // list is an error, not a reason to report the subkey missing
+        match subkey_offset {
+          Ok(offset) => return Ok(offset),
+          Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+          Err(e) => return Err(e),
+        }
+    }
+    Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Subkey with name \"{}\" not found in Index Root", subkey_name),
+        ))
+
+}
+
+// Function to search the leaf list at `list_offset` for a subkey, returning the key node's cell
+// offset. The file cursor must be just past the list signature, as get_subkey_list_type leaves it.
+// All the elements are read before any key node, since reading one moves the cursor.
+fn find_subkey_in_list<R: Read + Seek>(file: &mut R, list_offset: u32, subkey_name: &str, subkey_list_type: SubkeyListType) -> Result<u32, std::io::Error>{
+    // Fast and hash leaves pair every key node offset with a 4-byte name hint or hash
+    let element_size = match subkey_list_type {
+        SubkeyListType::IndexLeaf => 4,
+        SubkeyListType::FastLeaf | SubkeyListType::HashLeaf => 8,
+        _ => return Err(std::io::Error::other(format!("Subkey list type {:?} is not supported", subkey_list_type))),
+    };
+
+    let mut num_elements_bytes = [0u8; 2];
+    file.read_exact(&mut num_elements_bytes)?;
+    let num_elements = u16::from_le_bytes(num_elements_bytes) as usize;
+    check_list_capacity(file, list_offset, 4, num_elements, element_size)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    file.seek(SeekFrom::Start(cell_data_offset(list_offset) + 4))?;
+    let elements = read_bytes(file, num_elements * element_size)?;
+
+    for element in elements.chunks_exact(element_size) {
+        let subkey_offset = u32::from_le_bytes([element[0], element[1], element[2], element[3]]);
+
+        //Read the key node and compare the name, ignoring case as Windows does
+        let key_node = read_key_node(file, cell_data_offset(subkey_offset))?;
+        let key_name = read_key_name(file, &key_node)?;
+        if names_match(&key_name, subkey_name) {
+            return Ok(subkey_offset);
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("Subkey with name \"{}\" not found", subkey_name),
+    ))
+
+}
+
+// Function to read the signature of a subkey list, leaving the cursor just past it
+fn get_subkey_list_type<R: Read + Seek>(file: &mut R, subkeys_list_offset: u32) -> Result<SubkeyListType, std::io::Error>{
+    file.seek(SeekFrom::Start(cell_data_offset(subkeys_list_offset)))?;
+    let mut signature = [0u8; 2];
+    file.read_exact(&mut signature)?;
+
+    match &signature {
+      b"li" => Ok(SubkeyListType::IndexLeaf),
+      b"lf" => Ok(SubkeyListType::FastLeaf),
+      b"lh" => Ok(SubkeyListType::HashLeaf),
+      b"ri" => Ok(SubkeyListType::IndexRoot),
+      b"nk" => Ok(SubkeyListType::KeyNode),
+      _ => Ok(SubkeyListType::Unknown)
+    }
+}
+
+// Function to read the name string of a key node.
+// The name directly follows the key node structure, so this must be called right after read_key_node.
+fn read_key_name<R: Read + Seek>(file: &mut R, key_node: &KeyNode) -> Result<String, std::io::Error> {
+    let key_name_offset = file.stream_position()?;
+    let mut name_bytes = vec![0u8; key_node.key_name_length as usize];
+    file.read_exact(&mut name_bytes)?;
+
+    let name_string = if key_node.flags().contains(KeyNodeFlags::COMP_NAME) {
+        //ASCII or Extended ASCII string
+        String::from_utf8(name_bytes).map_err(|_| {
+          std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid UTF-8 data")
+        })?
+    } else {
+        // UTF-16LE string
+         let name_utf16: Vec<u16> = name_bytes
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect();
+
+         String::from_utf16(&name_utf16).map_err(|_| {
+          std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid UTF-16 data")
+        })?
+    };
+
+
+    // Return file cursor to the location it was at
+    file.seek(SeekFrom::Start(key_name_offset))?;
+
+
+    Ok(name_string)
+}
+
+
+// Function to find a key value with a given name
+fn find_key_value<R: Read + Seek>(
+    file: &mut R,
+    key_node: &KeyNode,
+    value_name: &str,
+) -> Result<KeyValue, HiveError> {
+     if key_node.key_values_list_offset == 0xFFFFFFFF {
+        return Err(HiveError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Key Value list not present for key node".to_string(),
+        )));
+    }
+
+    // The list and the key values are cells, so both offsets go through the cell header
+    for key_value_offset in read_value_offsets(file, key_node)? {
+        let (value_name_string, key_value) = read_named_key_value(file, key_value_offset)?;
+        if value_name_string == value_name {
+            return Ok(key_value.node);
+        }
+    }
+    Err(HiveError::Io(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("Key value with name \"{}\" not found", value_name),
+    )))
+}
+
+// Function to read the name of a key value
+// The name directly follows the key value structure, so this must be called right after read_key_value.
+fn read_key_value_name<R: Read + Seek>(file: &mut R, key_value: &KeyValue) -> Result<String, std::io::Error>{
+
+    let value_name_offset = file.stream_position()?;
+
+    let mut name_bytes = vec![0u8; key_value.name_length as usize];
+    file.read_exact(&mut name_bytes)?;
+     let name_string = if key_value.flags & 0x0001 == 0x0001 {
+        //ASCII or Extended ASCII string
+          String::from_utf8(name_bytes).map_err(|_| {
+          std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid UTF-8 data")
+        })?
+    } else {
+        // UTF-16LE string
+         let name_utf16: Vec<u16> = name_bytes
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect();
+
+        String::from_utf16(&name_utf16).map_err(|_| {
+          std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid UTF-16 data")
+        })?
+    };
+
+
+    // Return file cursor to the location it was at
+    file.seek(SeekFrom::Start(value_name_offset))?;
+
+    Ok(name_string)
+
+
+}
+
+
+// Function to extract the data of a key value.
+pub(crate) fn extract_key_value_data<R: Read + Seek>(
+  file: &mut R,
+  key_value: &KeyValue,
+  minor_version: u32
+) -> Result<Vec<u8>, HiveError> {
+  let data_size = key_value.data_len();
+  let data_offset = key_value.data_offset;
+
+    if key_value.is_resident() {
+        // Data is stored in the Data Offset field itself (up to 4 bytes)
+        let data_bytes = data_offset.to_le_bytes();
+        Ok(data_bytes[..(data_size as usize).min(data_bytes.len())].to_vec())
+
+    } else if data_size <= BIG_DATA_SEGMENT_SIZE || !HiveCapabilities::from_minor_version(minor_version).big_data {
+        // The data follows the cell's 4-byte size header
+        file.seek(SeekFrom::Start(cell_data_offset(data_offset)))
+            .and_then(|_| read_bytes(file, data_size as usize))
+            .map_err(HiveError::reading("value data", data_offset))
+
+    } else {
+        // Data is stored as Big Data structure. The buffer grows as segments are read, like
+        // read_bytes, so a data size claimed by a corrupt vk isn't allocated up front.
+        let mut data = Vec::new();
+        for (segment_offset, segment_length) in read_big_data_segments(file, data_offset, data_size)? {
+            let segment_bytes = file.seek(SeekFrom::Start(cell_data_offset(segment_offset)))
+                .and_then(|_| read_bytes(file, segment_length as usize))
+                .map_err(HiveError::reading("big data segment", segment_offset))?;
+            data.extend(segment_bytes);
+        }
+        Ok(data)
+    }
+}
+
+// Function to list the segments of a big data value as (cell offset, length) pairs, from the
+// "db" record at `cell_offset`. The segment list has a cell of its own, which has to be large
+// enough for every segment offset; anything past it belongs to other cells. Every segment is
+// full except the last, which holds the remainder of `data_size`.
+pub(crate) fn read_big_data_segments<R: Read + Seek>(
+    file: &mut R,
+    cell_offset: u32,
+    data_size: u32,
+) -> Result<Vec<(u32, u32)>, HiveError> {
+    let mut big_data_bytes = [0u8; 8];
+    file.seek(SeekFrom::Start(cell_data_offset(cell_offset)))
+        .and_then(|_| file.read_exact(&mut big_data_bytes))
+        .map_err(HiveError::reading("db", cell_offset))?;
+    if &big_data_bytes[..2] != b"db" {
+        return Err(HiveError::UnexpectedCell { offset: cell_offset, expected: "db" });
+    }
+    let segment_count = u16::from_le_bytes([big_data_bytes[2], big_data_bytes[3]]);
+    let segment_list_offset = u32::from_le_bytes([big_data_bytes[4], big_data_bytes[5], big_data_bytes[6], big_data_bytes[7]]);
+    if (segment_count as u64) * (BIG_DATA_SEGMENT_SIZE as u64) < data_size as u64 {
+        return Err(HiveError::InvalidValueData(format!(
+            "{} big data segments can't hold {} bytes",
+            segment_count, data_size
+        )));
+    }
+
+    let capacity = read_cell_header(file, segment_list_offset)
+        .map_err(HiveError::reading("big data segment list", segment_list_offset))?
+        .data_size();
+    if (capacity as u64) < segment_count as u64 * 4 {
+        return Err(HiveError::TruncatedSegmentList {
+            offset: segment_list_offset,
+            segment_count,
+            capacity,
+        });
+    }
+    let segment_list_bytes = read_bytes(file, segment_count as usize * 4)
+        .map_err(HiveError::reading("big data segment list", segment_list_offset))?;
+
+    let mut remaining = data_size;
+    let segments = segment_list_bytes
+        .chunks_exact(4)
+        .map(|segment_offset| {
+            let segment_offset = u32::from_le_bytes([segment_offset[0], segment_offset[1], segment_offset[2], segment_offset[3]]);
+            let segment_length = remaining.min(BIG_DATA_SEGMENT_SIZE);
+            remaining -= segment_length;
+            (segment_offset, segment_length)
+        })
+        .collect();
+    Ok(segments)
+}
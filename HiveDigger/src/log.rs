@@ -0,0 +1,290 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use crate::{parse_base_block, read_bytes, BaseBlock, MAX_CLUSTERING_FACTOR};
+
+// A log file only stores the first sector of the base block, and every base block field lives
+// in its first 512 bytes. Log entries (new format) and the dirty vector (old format) both start
+// in the second sector, whose size comes from the clustering factor.
+const BASE_BLOCK_HEADER_SIZE: usize = 512;
+
+// Size of the fixed part of a new format log entry, before the dirty page references
+const LOG_ENTRY_HEADER_SIZE: usize = 40;
+
+// Old format logs track dirty hive bins data in 512-byte pages
+const OLD_FORMAT_PAGE_SIZE: usize = 512;
+
+// Enum for the layout of a transaction log file, derived from the base block file type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    // File type 1 or 2: base block, "DIRT" dirty vector, then dirty pages (before Windows 8.1)
+    Old,
+    // File type 6: base block followed by "HvLE" log entries (Windows 8.1 and later)
+    New,
+}
+
+// Struct representing a dirty page carried by a log entry
+#[derive(Debug, Clone)]
+pub struct DirtyPage {
+    pub offset: u32, // Offset of the page relative to the start of the hive bins data
+    pub data: Vec<u8>,
+}
+
+// Struct representing a single log entry and the dirty pages it carries
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub file_offset: u64, // Where the entry starts in the log file
+    pub size: u32,        // Bytes occupied by the entry in the log file
+    pub sequence_number: u32,
+    pub flags: u32,
+    pub hive_bins_data_size: u32,
+    pub dirty_pages: Vec<DirtyPage>,
+}
+
+// Struct representing a .LOG/.LOG1/.LOG2 transaction log opened on its own
+pub struct TransactionLog {
+    file: File,
+    base_block: BaseBlock,
+    format: LogFormat,
+    sector_size: u64,
+}
+
+impl TransactionLog {
+    // Function to open a transaction log and validate that its base block describes a log
+    pub fn open(log_path: &Path) -> Result<TransactionLog, std::io::Error> {
+        let mut file = File::open(log_path)?;
+
+        let mut base_block_bytes = [0u8; 4096];
+        file.read_exact(&mut base_block_bytes[..BASE_BLOCK_HEADER_SIZE])?;
+        let base_block = parse_base_block(&base_block_bytes)?;
+
+        let sector_size = base_block.sector_size().ok_or_else(|| {
+            let clustering_factor = base_block.clustering_factor;
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Clustering factor {} is outside the supported range 1-{}",
+                    clustering_factor, MAX_CLUSTERING_FACTOR
+                ),
+            )
+        })?;
+
+        let format = match base_block.file_type {
+            1 | 2 => LogFormat::Old,
+            6 => LogFormat::New,
+            other => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("File type {} is not a transaction log", other),
+                ))
+            }
+        };
+
+        Ok(TransactionLog {
+            file,
+            base_block,
+            format,
+            sector_size,
+        })
+    }
+
+    pub fn format(&self) -> LogFormat {
+        self.format
+    }
+
+    pub fn primary_sequence_number(&self) -> u32 {
+        self.base_block.primary_seq_num
+    }
+
+    pub fn secondary_sequence_number(&self) -> u32 {
+        self.base_block.secondary_seq_num
+    }
+
+    pub fn hive_bins_data_size(&self) -> u32 {
+        self.base_block.hive_bins_data_size
+    }
+
+    // Function to iterate over the log entries in file order.
+    // New format logs are read until the first slot that is not a valid "HvLE" entry or whose
+    // sequence number does not follow the previous one (stale data from an earlier cycle).
+    // Old format logs yield a single entry built from the dirty vector.
+    pub fn entries(&mut self) -> LogEntries<'_> {
+        LogEntries {
+            next_offset: self.sector_size,
+            log: self,
+            expected_sequence_number: None,
+            finished: false,
+        }
+    }
+}
+
+// Iterator over the entries of a transaction log
+pub struct LogEntries<'a> {
+    log: &'a mut TransactionLog,
+    next_offset: u64,
+    expected_sequence_number: Option<u32>,
+    finished: bool,
+}
+
+impl Iterator for LogEntries<'_> {
+    type Item = Result<LogEntry, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let entry = match self.log.format {
+            LogFormat::Old => {
+                self.finished = true;
+                read_dirty_vector_entry(&mut self.log.file, &self.log.base_block, self.log.sector_size)
+            }
+            LogFormat::New => read_log_entry(&mut self.log.file, self.next_offset),
+        };
+
+        match entry {
+            Ok(Some(entry)) => {
+                if let Some(expected) = self.expected_sequence_number {
+                    if entry.sequence_number != expected {
+                        self.finished = true;
+                        return None;
+                    }
+                }
+                self.expected_sequence_number = Some(entry.sequence_number.wrapping_add(1));
+                self.next_offset = entry.file_offset + entry.size as u64;
+                Some(Ok(entry))
+            }
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// Function to read a new format ("HvLE") log entry, returning None when no entry starts at the offset
+fn read_log_entry(file: &mut File, offset: u64) -> Result<Option<LogEntry>, std::io::Error> {
+    file.seek(SeekFrom::Start(offset))?;
+
+    let mut header = [0u8; LOG_ENTRY_HEADER_SIZE];
+    match file.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    if &header[0..4] != b"HvLE" {
+        return Ok(None);
+    }
+
+    let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+    let flags = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+    let sequence_number = u32::from_le_bytes([header[12], header[13], header[14], header[15]]);
+    let hive_bins_data_size = u32::from_le_bytes([header[16], header[17], header[18], header[19]]);
+    let dirty_pages_count = u32::from_le_bytes([header[20], header[21], header[22], header[23]]) as usize;
+
+    // The entry size is a multiple of 512 and must hold the header and the page references
+    let references_size = dirty_pages_count.saturating_mul(8);
+    if !size.is_multiple_of(512) || size < LOG_ENTRY_HEADER_SIZE.saturating_add(references_size) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Invalid log entry size {:#x} at offset {:#x}", size, offset),
+        ));
+    }
+
+    // Both sizes come from the log, so the body is only as big as what the file actually holds
+    let body = read_bytes(file, size - LOG_ENTRY_HEADER_SIZE)?;
+
+    let (references, mut page_data) = body.split_at(references_size);
+    let mut dirty_pages = Vec::with_capacity(references.len() / 8);
+    for reference in references.chunks_exact(8) {
+        let page_offset = u32::from_le_bytes([reference[0], reference[1], reference[2], reference[3]]);
+        let page_size = u32::from_le_bytes([reference[4], reference[5], reference[6], reference[7]]) as usize;
+
+        if page_size > page_data.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Dirty page at {:#x} overruns the log entry at offset {:#x}", page_offset, offset),
+            ));
+        }
+        let (data, rest) = page_data.split_at(page_size);
+        dirty_pages.push(DirtyPage {
+            offset: page_offset,
+            data: data.to_vec(),
+        });
+        page_data = rest;
+    }
+
+    Ok(Some(LogEntry {
+        file_offset: offset,
+        size: size as u32,
+        sequence_number,
+        flags,
+        hive_bins_data_size,
+        dirty_pages,
+    }))
+}
+
+// Function to read the old format dirty vector and the dirty pages following it as a single entry
+fn read_dirty_vector_entry(
+    file: &mut File,
+    base_block: &BaseBlock,
+    sector_size: u64,
+) -> Result<Option<LogEntry>, std::io::Error> {
+    file.seek(SeekFrom::Start(sector_size))?;
+
+    let mut signature = [0u8; 4];
+    file.read_exact(&mut signature)?;
+    if &signature != b"DIRT" {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Invalid dirty vector signature",
+        ));
+    }
+
+    // One bit per 512-byte page of hive bins data
+    let hive_bins_data_size = base_block.hive_bins_data_size;
+    let mut bitmap = vec![0u8; hive_bins_data_size as usize / 4096];
+    file.read_exact(&mut bitmap)?;
+
+    // Dirty pages start at the sector following the dirty vector
+    let vector_end = sector_size + 4 + bitmap.len() as u64;
+    let pages_offset = vector_end.div_ceil(sector_size) * sector_size;
+    file.seek(SeekFrom::Start(pages_offset))?;
+
+    let mut dirty_pages = Vec::new();
+    for (byte_index, byte) in bitmap.iter().enumerate() {
+        for bit in 0..8 {
+            if byte & (1 << bit) == 0 {
+                continue;
+            }
+            let page_index = byte_index * 8 + bit;
+            let mut data = vec![0u8; OLD_FORMAT_PAGE_SIZE];
+            file.read_exact(&mut data)?;
+            dirty_pages.push(DirtyPage {
+                offset: (page_index * OLD_FORMAT_PAGE_SIZE) as u32,
+                data,
+            });
+        }
+    }
+
+    let pages_end = pages_offset + (dirty_pages.len() * OLD_FORMAT_PAGE_SIZE) as u64;
+    let size = u32::try_from(pages_end - sector_size).map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "Dirty pages extend past 4 GiB")
+    })?;
+    Ok(Some(LogEntry {
+        file_offset: sector_size,
+        size,
+        sequence_number: base_block.primary_seq_num,
+        flags: 0,
+        hive_bins_data_size,
+        dirty_pages,
+    }))
+}
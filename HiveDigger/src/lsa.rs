@@ -0,0 +1,59 @@
+use md4::{Digest, Md4};
+
+// Size of the LSA_SECRET_BLOB header: the secret's length followed by 12 unused bytes
+const SECRET_BLOB_HEADER_SIZE: usize = 16;
+
+// Enum for the decrypted value of an LSA secret (SECURITY\Policy\Secrets\<name>\CurrVal or
+// OldVal), parsed according to the secret's name where its layout is known
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LsaSecret {
+    // DefaultPassword: the cleartext autologon password
+    DefaultPassword(String),
+    // $MACHINE.ACC: the computer account's password, which is random bytes rather than text, and
+    // its NT hash, which is what the domain controller actually checks
+    MachineAccount { password: Vec<u8>, nt_hash: [u8; 16] },
+    // DPAPI_SYSTEM: the machine and user DPAPI keys of the SYSTEM account
+    DpapiSystem { machine_key: [u8; 20], user_key: [u8; 20] },
+    // Any other secret, or a known one whose data doesn't have the expected layout
+    Raw(Vec<u8>),
+}
+
+// Function to parse a decrypted LSA secret. `blob` is the LSA_SECRET_BLOB the value decrypts to:
+// a u32 length, 12 unused bytes, then the secret itself. Blobs whose length doesn't fit are
+// returned whole as `Raw`.
+pub fn parse_secret(name: &str, blob: &[u8]) -> LsaSecret {
+    let secret = blob
+        .get(..4)
+        .map(|length| u32::from_le_bytes([length[0], length[1], length[2], length[3]]) as usize)
+        .and_then(|length| blob.get(SECRET_BLOB_HEADER_SIZE..SECRET_BLOB_HEADER_SIZE.checked_add(length)?));
+    let Some(secret) = secret else {
+        return LsaSecret::Raw(blob.to_vec());
+    };
+
+    match name {
+        "DefaultPassword" if secret.len().is_multiple_of(2) => LsaSecret::DefaultPassword(decode_utf16(secret)),
+        "$MACHINE.ACC" => LsaSecret::MachineAccount {
+            password: secret.to_vec(),
+            nt_hash: Md4::digest(secret).into(),
+        },
+        // A version number, then the machine key and the user key
+        "DPAPI_SYSTEM" if secret.len() == 44 => {
+            let mut machine_key = [0u8; 20];
+            let mut user_key = [0u8; 20];
+            machine_key.copy_from_slice(&secret[4..24]);
+            user_key.copy_from_slice(&secret[24..44]);
+            LsaSecret::DpapiSystem { machine_key, user_key }
+        }
+        _ => LsaSecret::Raw(secret.to_vec()),
+    }
+}
+
+// Function to decode UTF-16LE text up to its NUL terminator, if it has one
+fn decode_utf16(data: &[u8]) -> String {
+    let string_utf16: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .take_while(|&character| character != 0)
+        .collect();
+    String::from_utf16_lossy(&string_utf16)
+}
@@ -0,0 +1,48 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+// Struct representing a reader over a hive embedded at an offset in a larger source, such as a
+// disk image or a memory dump. Positions are the hive's own: 0 is its base block. Reads run on to
+// the end of the source, and seeking from the end is relative to the end of the source.
+#[derive(Debug, Clone)]
+pub struct OffsetReader<R> {
+    inner: R,
+    base_offset: u64,
+}
+
+impl<R> OffsetReader<R> {
+    // Function to wrap a reader whose hive starts `base_offset` bytes in
+    pub fn new(inner: R, base_offset: u64) -> OffsetReader<R> {
+        OffsetReader { inner, base_offset }
+    }
+
+    // Function to get where the hive starts in the underlying reader
+    pub fn base_offset(&self) -> u64 {
+        self.base_offset
+    }
+
+    // Function to get the underlying reader back
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for OffsetReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for OffsetReader<R> {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        let position = match position {
+            SeekFrom::Start(offset) => SeekFrom::Start(self.base_offset.checked_add(offset).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "Seek position overflows the underlying reader")
+            })?),
+            relative => relative,
+        };
+        let inner_position = self.inner.seek(position)?;
+        inner_position.checked_sub(self.base_offset).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Seek to before the start of the embedded hive")
+        })
+    }
+}
@@ -0,0 +1,37 @@
+// Struct representing what happened while a hive was opened, from `Hive::provenance`, so a
+// report can show how the data it quotes was obtained. The checksum and sequence numbers are
+// those of the base block as stored; both are None when the file doesn't start with a "regf"
+// block at all. Transaction logs are never replayed at open, so a dirty hive is read as it is on
+// disk and its newest changes may be missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenManifest {
+    pub checksum_valid: Option<bool>,
+    pub dirty: Option<bool>,      // The stored sequence numbers differ, see `Hive::is_dirty`
+    pub recovery_enabled: bool,   // `HiveOptions::recovery` was set
+    pub base_block_rebuilt: bool, // The base block was damaged and rebuilt by scanning the bins
+    pub root_relocated: bool,     // The stored root cell offset was wrong and the root was found by scanning
+    pub strict: bool,
+    pub skip_errors: bool,
+}
+
+impl OpenManifest {
+    // Function to check whether the hive is read with anything other than the stored base block
+    pub fn recovered(&self) -> bool {
+        self.base_block_rebuilt || self.root_relocated
+    }
+
+    // Function to render the manifest as JSON for embedding in a report; fields that couldn't be
+    // determined are null
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "checksum_valid": self.checksum_valid,
+            "dirty": self.dirty,
+            "logs_applied": [],
+            "recovery_enabled": self.recovery_enabled,
+            "base_block_rebuilt": self.base_block_rebuilt,
+            "root_relocated": self.root_relocated,
+            "strict": self.strict,
+            "skip_errors": self.skip_errors,
+        })
+    }
+}
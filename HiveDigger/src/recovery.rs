@@ -0,0 +1,127 @@
+use std::{
+    io::{Read, Seek, SeekFrom},
+    mem,
+};
+
+use crate::{BaseBlock, CellHeader, HiveBinHeader, HiveError, KeyNodeFlags, HIVE_BINS_OFFSET};
+
+// Minor version assumed for a recovered hive; 5 (Windows XP and later) enables big data
+const RECOVERED_MINOR_VERSION: u32 = 5;
+
+// Function to rebuild a base block for a hive whose header is damaged, as a best-effort salvage:
+// hive bins are located by scanning 4096-byte boundaries for "hbin" signatures, and the root
+// cell is the first allocated key node flagged as the hive's entry key
+pub(crate) fn recover_base_block<R: Read + Seek>(reader: &mut R) -> Result<BaseBlock, HiveError> {
+    let file_size = reader.seek(SeekFrom::End(0))?;
+
+    let mut hive_bins_data_size = 0u32;
+    let mut root_cell_offset = None;
+
+    let mut bin_position = HIVE_BINS_OFFSET;
+    while bin_position + 4096 <= file_size {
+        let Some(bin) = read_candidate_bin(reader, bin_position, file_size)? else {
+            bin_position += 4096;
+            continue;
+        };
+        // Cell offsets are u32, so bins ending past 4 GiB of hive bins data can't be addressed
+        let bin_end = bin_position + bin.len() as u64;
+        let Ok(bins_size) = u32::try_from(bin_end - HIVE_BINS_OFFSET) else {
+            break;
+        };
+        let bin_offset = bins_size - bin.len() as u32;
+
+        if root_cell_offset.is_none() {
+            root_cell_offset = find_root_cell(&bin).map(|cell_position| bin_offset + cell_position as u32);
+        }
+        bin_position = bin_end;
+        hive_bins_data_size = bins_size;
+    }
+
+    if hive_bins_data_size == 0 {
+        return Err(HiveError::RecoveryFailed("No hive bins found"));
+    }
+    let Some(root_cell_offset) = root_cell_offset else {
+        return Err(HiveError::RecoveryFailed("No root key node found"));
+    };
+
+    // Everything not recovered is left zeroed; the fields set are all that reading needs
+    let mut base_block_bytes = [0u8; 4096];
+    base_block_bytes[..4].copy_from_slice(b"regf");
+    let base_block: &mut BaseBlock = unsafe { mem::transmute(&mut base_block_bytes) };
+    base_block.major_version = 1;
+    base_block.minor_version = RECOVERED_MINOR_VERSION;
+    base_block.file_format = 1;
+    base_block.root_cell_offset = root_cell_offset;
+    base_block.hive_bins_data_size = hive_bins_data_size;
+    base_block.clustering_factor = 1;
+    base_block.checksum = base_block.computed_checksum();
+    Ok(*base_block)
+}
+
+// Function to find the root key for a hive whose base block is intact except for the root cell
+// offset, as in some carved hives: the first allocated key node flagged as the hive's entry key
+// in the bins the base block covers
+pub(crate) fn find_root_cell_offset<R: Read + Seek>(reader: &mut R, hive_bins_data_size: u32) -> Result<Option<u32>, HiveError> {
+    let file_size = reader.seek(SeekFrom::End(0))?;
+    let hive_bins_end = (HIVE_BINS_OFFSET + hive_bins_data_size as u64).min(file_size);
+
+    let mut bin_position = HIVE_BINS_OFFSET;
+    while bin_position + 4096 <= hive_bins_end {
+        let bin_offset = (bin_position - HIVE_BINS_OFFSET) as u32;
+        let Some(bin) = read_candidate_bin(reader, bin_position, hive_bins_end)? else {
+            bin_position += 4096;
+            continue;
+        };
+        if let Some(cell_position) = find_root_cell(&bin) {
+            return Ok(Some(bin_offset + cell_position as u32));
+        }
+        bin_position += bin.len() as u64;
+    }
+    Ok(None)
+}
+
+// Function to read the bin starting at a file position, or None if there is no plausible bin there
+fn read_candidate_bin<R: Read + Seek>(reader: &mut R, bin_position: u64, file_size: u64) -> Result<Option<Vec<u8>>, HiveError> {
+    reader.seek(SeekFrom::Start(bin_position))?;
+    let mut header_bytes = [0u8; mem::size_of::<HiveBinHeader>()];
+    reader.read_exact(&mut header_bytes)?;
+    let header: &HiveBinHeader = unsafe { mem::transmute(&header_bytes) };
+
+    let bin_size = header.size as u64;
+    if &header.signature != b"hbin" || bin_size < 4096 || !bin_size.is_multiple_of(4096) || bin_position + bin_size > file_size {
+        return Ok(None);
+    }
+
+    let mut bin = vec![0u8; bin_size as usize];
+    bin[..header_bytes.len()].copy_from_slice(&header_bytes);
+    reader.read_exact(&mut bin[header_bytes.len()..])?;
+    Ok(Some(bin))
+}
+
+// Function to find the position within a bin of an allocated key node flagged as the hive's root
+fn find_root_cell(bin: &[u8]) -> Option<usize> {
+    let mut cell_position = mem::size_of::<HiveBinHeader>();
+    while cell_position + 8 <= bin.len() {
+        let cell_header = CellHeader::from_bytes([
+            bin[cell_position],
+            bin[cell_position + 1],
+            bin[cell_position + 2],
+            bin[cell_position + 3],
+        ]);
+        let cell_length = cell_header.size() as usize;
+        if cell_length < 8 || cell_position + cell_length > bin.len() {
+            // The rest of this bin can't be followed
+            return None;
+        }
+
+        let cell_data = &bin[cell_position + 4..cell_position + cell_length];
+        if cell_header.is_allocated() && cell_data.len() >= 4 && &cell_data[..2] == b"nk" {
+            let flags = KeyNodeFlags(u16::from_le_bytes([cell_data[2], cell_data[3]]));
+            if flags.contains(KeyNodeFlags::HIVE_ENTRY) {
+                return Some(cell_position);
+            }
+        }
+        cell_position += cell_length;
+    }
+    None
+}
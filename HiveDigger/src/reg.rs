@@ -0,0 +1,147 @@
+// Formatting of keys and values in the syntax regedit uses for .reg files
+
+// regedit wraps hex data once a line reaches this many characters
+const MAX_HEX_LINE_LENGTH: usize = 77;
+
+const REG_SZ: u32 = 1;
+const REG_EXPAND_SZ: u32 = 2;
+const REG_BINARY: u32 = 3;
+const REG_DWORD: u32 = 4;
+const REG_MULTI_SZ: u32 = 7;
+
+// Enum for the flavours of .reg file regedit can write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RegFormat {
+    // "Windows Registry Editor Version 5.00": UTF-16LE with a BOM, string data kept as UTF-16
+    #[default]
+    Utf16V5,
+    // "REGEDIT4": 8-bit text (written as UTF-8), string data stored as 8-bit strings.
+    // REGEDIT4 has no syntax for newer types such as QWORD (nor does version 5, which has no
+    // `qword:`), so QWORDs are written as raw `hex(b):` bytes here too, as regedit does when
+    // exporting in this format. Importers store such bytes verbatim under the given type.
+    RegEdit4,
+}
+
+impl RegFormat {
+    // Function to get the first line of a .reg file in this format
+    pub(crate) fn header(self) -> &'static str {
+        match self {
+            RegFormat::Utf16V5 => "Windows Registry Editor Version 5.00",
+            RegFormat::RegEdit4 => "REGEDIT4",
+        }
+    }
+
+    // Function to encode .reg text for writing, with the BOM in front of UTF-16 output
+    pub(crate) fn encode(self, text: &str, first_chunk: bool) -> Vec<u8> {
+        match self {
+            RegFormat::Utf16V5 => {
+                let mut bytes = if first_chunk { vec![0xFF, 0xFE] } else { Vec::new() };
+                bytes.extend(text.encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+                bytes
+            }
+            RegFormat::RegEdit4 => text.as_bytes().to_vec(),
+        }
+    }
+}
+
+// Function to format one value as a .reg line, e.g. `"Start"=dword:00000003`.
+// Long hex data is wrapped onto indented continuation lines joined with `\` and CRLF.
+pub(crate) fn format_value(value_name: &str, data_type: u32, data: &[u8], format: RegFormat) -> String {
+    let mut line = format_value_name(value_name);
+    line.push('=');
+
+    match data_type {
+        // A quoted string ends at the terminator, so data hidden after it is kept by writing hex
+        REG_SZ if only_nuls_after_terminator(data) => {
+            if let Some(string) = decode_reg_sz(data) {
+                line.push('"');
+                line.push_str(&escape(&string));
+                line.push('"');
+                return line;
+            }
+        }
+        REG_DWORD if data.len() == 4 => {
+            let dword = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+            line.push_str(&format!("dword:{:08x}", dword));
+            return line;
+        }
+        _ => {}
+    }
+
+    // Everything else, including strings and DWORDs that don't decode cleanly, is written as hex
+    if data_type == REG_BINARY {
+        line.push_str("hex:");
+    } else {
+        line.push_str(&format!("hex({:x}):", data_type));
+    }
+    match (format, data_type) {
+        // REGEDIT4 stores string types as 8-bit strings rather than UTF-16
+        (RegFormat::RegEdit4, REG_SZ | REG_EXPAND_SZ | REG_MULTI_SZ) if data.len().is_multiple_of(2) => {
+            push_hex_data(&mut line, &narrow_string_data(data));
+        }
+        _ => push_hex_data(&mut line, data),
+    }
+    line
+}
+
+// Function to check that nothing but NULs follows the first NUL terminator in UTF-16LE string data
+fn only_nuls_after_terminator(data: &[u8]) -> bool {
+    data.chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .skip_while(|&character| character != 0)
+        .all(|character| character == 0)
+}
+
+// Function to convert UTF-16LE string data (including any embedded and trailing NULs) to UTF-8
+fn narrow_string_data(data: &[u8]) -> Vec<u8> {
+    let string_utf16: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .collect();
+    String::from_utf16_lossy(&string_utf16).into_bytes()
+}
+
+// Function to write bytes as comma-separated hex pairs, wrapping lines the way regedit does
+fn push_hex_data(line: &mut String, data: &[u8]) {
+    let mut line_length = line.len();
+    for (index, byte) in data.iter().enumerate() {
+        line.push_str(&format!("{:02x}", byte));
+        if index + 1 == data.len() {
+            break;
+        }
+        line.push(',');
+        line_length += 3;
+
+        if line_length >= MAX_HEX_LINE_LENGTH {
+            line.push_str("\\\r\n  ");
+            line_length = 2;
+        }
+    }
+}
+
+// Function to format a value name, using `@` for the default (unnamed) value
+fn format_value_name(value_name: &str) -> String {
+    if value_name.is_empty() {
+        "@".to_string()
+    } else {
+        format!("\"{}\"", escape(value_name))
+    }
+}
+
+// Function to escape backslashes and quotes inside a quoted .reg string
+fn escape(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Function to decode REG_SZ data up to its NUL terminator, or None if it isn't valid UTF-16LE
+pub(crate) fn decode_reg_sz(data: &[u8]) -> Option<String> {
+    if !data.len().is_multiple_of(2) {
+        return None;
+    }
+    let string_utf16: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .take_while(|&character| character != 0)
+        .collect();
+    String::from_utf16(&string_utf16).ok()
+}
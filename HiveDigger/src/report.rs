@@ -0,0 +1,112 @@
+use std::fmt;
+
+const REG_SZ: u32 = 1;
+const REG_EXPAND_SZ: u32 = 2;
+const REG_DWORD: u32 = 4;
+const REG_DWORD_BIG_ENDIAN: u32 = 5;
+const REG_LINK: u32 = 6;
+const REG_MULTI_SZ: u32 = 7;
+const REG_QWORD: u32 = 11;
+// Highest REG_* type Windows defines (REG_QWORD)
+const MAX_KNOWN_DATA_TYPE: u32 = 11;
+
+// Struct representing the non-fatal anomalies noticed while reading a hive, as a signal of how
+// cleanly it parsed. Returned alongside the result by `Hive::walk_with_report` and
+// `Hive::export_reg_with_report`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    pub warnings: Vec<Warning>,
+}
+
+impl ParseReport {
+    // Function to check whether the parse noticed nothing unusual
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub(crate) fn warn(&mut self, path: &str, kind: WarningKind) {
+        self.warnings.push(Warning {
+            path: path.to_string(),
+            kind,
+        });
+    }
+}
+
+// Struct representing one anomaly and the key it was found in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub path: String, // Key path relative to the root
+    pub kind: WarningKind,
+}
+
+// Enum for the kinds of anomaly a `ParseReport` records
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    // The key node's subkey count disagrees with the number of entries in its subkey lists
+    SubkeyCountMismatch { recorded: u32, found: u32 },
+    // String data isn't valid UTF-16, so decoding it substitutes replacement characters
+    LossyString { value_name: String },
+    // The value's type is none of the REG_* types Windows defines
+    UnknownValueType { value_name: String, data_type: u32 },
+    // A DWORD or QWORD value whose data isn't 4 or 8 bytes, so it can't be decoded as an integer
+    IntegerSizeMismatch { value_name: String, data_type: u32, size: usize },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The root key has an empty path
+        let key_path = if self.path.is_empty() { "(root)" } else { self.path.as_str() };
+        match &self.kind {
+            WarningKind::SubkeyCountMismatch { recorded, found } => {
+                write!(f, "{}: key records {} subkeys but lists {}", key_path, recorded, found)
+            }
+            WarningKind::LossyString { value_name } => {
+                write!(f, "{}\\{}: string data is not valid UTF-16", key_path, value_name)
+            }
+            WarningKind::UnknownValueType { value_name, data_type } => {
+                write!(f, "{}\\{}: unknown value type {}", key_path, value_name, data_type)
+            }
+            WarningKind::IntegerSizeMismatch { value_name, data_type, size } => {
+                let expected = if *data_type == REG_QWORD { 8 } else { 4 };
+                write!(f, "{}\\{}: {}-byte integer value holds {} bytes", key_path, value_name, expected, size)
+            }
+        }
+    }
+}
+
+// Function to record the anomalies in one value's data
+pub(crate) fn check_value(report: &mut ParseReport, path: &str, value_name: &str, data_type: u32, data: &[u8]) {
+    match data_type {
+        REG_SZ | REG_EXPAND_SZ | REG_LINK | REG_MULTI_SZ => {
+            let string_utf16 = data.chunks_exact(2).map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]));
+            // Single strings end at their NUL terminator; whatever follows is slack
+            let lossy = if data_type == REG_MULTI_SZ {
+                char::decode_utf16(string_utf16).any(|character| character.is_err())
+            } else {
+                char::decode_utf16(string_utf16.take_while(|&character| character != 0)).any(|character| character.is_err())
+            };
+            if lossy {
+                report.warn(path, WarningKind::LossyString {
+                    value_name: value_name.to_string(),
+                });
+            }
+        }
+        REG_DWORD | REG_DWORD_BIG_ENDIAN | REG_QWORD => {
+            let expected = if data_type == REG_QWORD { 8 } else { 4 };
+            if data.len() != expected {
+                report.warn(path, WarningKind::IntegerSizeMismatch {
+                    value_name: value_name.to_string(),
+                    data_type,
+                    size: data.len(),
+                });
+            }
+        }
+        data_type if data_type > MAX_KNOWN_DATA_TYPE => {
+            report.warn(path, WarningKind::UnknownValueType {
+                value_name: value_name.to_string(),
+                data_type,
+            });
+        }
+        _ => {}
+    }
+}
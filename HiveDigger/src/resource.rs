@@ -0,0 +1,209 @@
+use crate::HiveError;
+
+// CM_PARTIAL_RESOURCE_DESCRIPTOR is 20 bytes on 64-bit Windows (8-byte interrupt affinity) and
+// 16 bytes on 32-bit Windows. The registry stores whichever the writing system used.
+const PARTIAL_DESCRIPTOR_SIZES: [usize; 2] = [20, 16];
+
+// Size of CM_FULL_RESOURCE_DESCRIPTOR up to its first partial descriptor
+const FULL_DESCRIPTOR_HEADER_SIZE: usize = 16;
+
+// Flags selecting the length scaling of a CmResourceTypeMemoryLarge descriptor
+const CM_RESOURCE_MEMORY_LARGE_40: u16 = 0x0200;
+const CM_RESOURCE_MEMORY_LARGE_48: u16 = 0x0400;
+const CM_RESOURCE_MEMORY_LARGE_64: u16 = 0x0800;
+
+// Struct representing a decoded REG_RESOURCE_LIST (CM_RESOURCE_LIST)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceList {
+    pub full_descriptors: Vec<FullResourceDescriptor>,
+}
+
+// Struct representing a decoded REG_FULL_RESOURCE_DESCRIPTOR (CM_FULL_RESOURCE_DESCRIPTOR)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullResourceDescriptor {
+    pub interface_type: u32, // INTERFACE_TYPE, e.g. 1 = Isa, 5 = PCIBus
+    pub bus_number: u32,
+    pub version: u16,
+    pub revision: u16,
+    pub partial_descriptors: Vec<PartialResourceDescriptor>,
+}
+
+// Struct representing a single CM_PARTIAL_RESOURCE_DESCRIPTOR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialResourceDescriptor {
+    pub share_disposition: u8,
+    pub flags: u16,
+    pub resource: Resource,
+}
+
+// Enum for the typed contents of a partial resource descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resource {
+    Null,
+    Port { start: u64, length: u32 },
+    Interrupt { level: u16, group: u16, vector: u32, affinity: u64 },
+    Memory { start: u64, length: u64 },
+    Dma { channel: u32, port: u32 },
+    DeviceSpecific { data: Vec<u8> },
+    BusNumber { start: u32, length: u32 },
+    // Types without a decoder keep their raw union bytes
+    Other { resource_type: u8, raw: Vec<u8> },
+}
+
+// Function to decode REG_RESOURCE_LIST (type 8) data
+pub fn decode_resource_list(bytes: &[u8]) -> Result<ResourceList, HiveError> {
+    let count = read_u32(bytes, 0)? as usize;
+
+    let mut last_error = None;
+    for descriptor_size in PARTIAL_DESCRIPTOR_SIZES {
+        let mut full_descriptors = Vec::new();
+        let mut position = 4;
+        let parsed = (0..count).try_for_each(|_| {
+            let (full_descriptor, consumed) = parse_full_descriptor(&bytes[position..], descriptor_size)?;
+            full_descriptors.push(full_descriptor);
+            position += consumed;
+            Ok(())
+        });
+
+        match parsed {
+            Ok(()) if position == bytes.len() => return Ok(ResourceList { full_descriptors }),
+            Ok(()) => last_error = Some(trailing_bytes_error(bytes.len() - position)),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| trailing_bytes_error(bytes.len())))
+}
+
+// Function to decode REG_FULL_RESOURCE_DESCRIPTOR (type 9) data
+pub fn decode_full_resource_descriptor(bytes: &[u8]) -> Result<FullResourceDescriptor, HiveError> {
+    let mut last_error = None;
+    for descriptor_size in PARTIAL_DESCRIPTOR_SIZES {
+        match parse_full_descriptor(bytes, descriptor_size) {
+            Ok((full_descriptor, consumed)) if consumed == bytes.len() => return Ok(full_descriptor),
+            Ok((_, consumed)) => last_error = Some(trailing_bytes_error(bytes.len() - consumed)),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| trailing_bytes_error(bytes.len())))
+}
+
+// Function to parse one full descriptor, returning it and the number of bytes it occupies
+fn parse_full_descriptor(bytes: &[u8], descriptor_size: usize) -> Result<(FullResourceDescriptor, usize), HiveError> {
+    let interface_type = read_u32(bytes, 0)?;
+    let bus_number = read_u32(bytes, 4)?;
+    let version = read_u16(bytes, 8)?;
+    let revision = read_u16(bytes, 10)?;
+    let count = read_u32(bytes, 12)? as usize;
+
+    let mut partial_descriptors = Vec::new();
+    let mut position = FULL_DESCRIPTOR_HEADER_SIZE;
+    for _ in 0..count {
+        let descriptor = bytes
+            .get(position..position + descriptor_size)
+            .ok_or_else(|| truncated_error(position))?;
+        position += descriptor_size;
+
+        let resource_type = descriptor[0];
+        let share_disposition = descriptor[1];
+        let flags = u16::from_le_bytes([descriptor[2], descriptor[3]]);
+        let union = &descriptor[4..];
+
+        let resource = match resource_type {
+            0 => Resource::Null,
+            1 => Resource::Port {
+                start: read_u64(union, 0)?,
+                length: read_u32(union, 8)?,
+            },
+            2 => Resource::Interrupt {
+                level: read_u16(union, 0)?,
+                group: read_u16(union, 2)?,
+                vector: read_u32(union, 4)?,
+                affinity: if union.len() >= 16 {
+                    read_u64(union, 8)?
+                } else {
+                    read_u32(union, 8)? as u64
+                },
+            },
+            3 => Resource::Memory {
+                start: read_u64(union, 0)?,
+                length: read_u32(union, 8)? as u64,
+            },
+            4 => Resource::Dma {
+                channel: read_u32(union, 0)?,
+                port: read_u32(union, 4)?,
+            },
+            5 => {
+                // Device specific data follows the descriptor itself
+                let data_size = read_u32(union, 0)? as usize;
+                let data = bytes
+                    .get(position..position + data_size)
+                    .ok_or_else(|| truncated_error(position))?;
+                position += data_size;
+                Resource::DeviceSpecific { data: data.to_vec() }
+            }
+            6 => Resource::BusNumber {
+                start: read_u32(union, 0)?,
+                length: read_u32(union, 4)?,
+            },
+            7 => {
+                let length = read_u32(union, 8)? as u64;
+                let shift = match flags & (CM_RESOURCE_MEMORY_LARGE_40 | CM_RESOURCE_MEMORY_LARGE_48 | CM_RESOURCE_MEMORY_LARGE_64) {
+                    CM_RESOURCE_MEMORY_LARGE_40 => 8,
+                    CM_RESOURCE_MEMORY_LARGE_48 => 16,
+                    CM_RESOURCE_MEMORY_LARGE_64 => 32,
+                    _ => 0,
+                };
+                Resource::Memory {
+                    start: read_u64(union, 0)?,
+                    length: length << shift,
+                }
+            }
+            _ => Resource::Other {
+                resource_type,
+                raw: union.to_vec(),
+            },
+        };
+
+        partial_descriptors.push(PartialResourceDescriptor {
+            share_disposition,
+            flags,
+            resource,
+        });
+    }
+
+    Ok((
+        FullResourceDescriptor {
+            interface_type,
+            bus_number,
+            version,
+            revision,
+            partial_descriptors,
+        },
+        position,
+    ))
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, HiveError> {
+    let field = bytes.get(offset..offset + 2).ok_or_else(|| truncated_error(offset))?;
+    Ok(u16::from_le_bytes([field[0], field[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, HiveError> {
+    let field = bytes.get(offset..offset + 4).ok_or_else(|| truncated_error(offset))?;
+    Ok(u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, HiveError> {
+    let field = bytes.get(offset..offset + 8).ok_or_else(|| truncated_error(offset))?;
+    let mut buffer = [0u8; 8];
+    buffer.copy_from_slice(field);
+    Ok(u64::from_le_bytes(buffer))
+}
+
+fn truncated_error(offset: usize) -> HiveError {
+    HiveError::InvalidValueData(format!("Resource descriptor data truncated at byte {}", offset))
+}
+
+fn trailing_bytes_error(trailing: usize) -> HiveError {
+    HiveError::InvalidValueData(format!("{} unexpected trailing bytes after resource descriptors", trailing))
+}
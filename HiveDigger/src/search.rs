@@ -0,0 +1,89 @@
+use regex::{Regex, RegexBuilder};
+
+const REG_SZ: u32 = 1;
+const REG_EXPAND_SZ: u32 = 2;
+const REG_DWORD: u32 = 4;
+const REG_DWORD_BIG_ENDIAN: u32 = 5;
+const REG_LINK: u32 = 6;
+const REG_MULTI_SZ: u32 = 7;
+const REG_QWORD: u32 = 11;
+
+// Enum for the ways `Hive::search` can match text
+#[derive(Debug, Clone)]
+pub enum SearchPattern {
+    // Plain substring; when ignoring case the needle is stored lowercased
+    Substring { needle: String, ignore_case: bool },
+    Regex(Regex),
+}
+
+impl SearchPattern {
+    // Function to build a plain substring pattern
+    pub fn substring(pattern: &str, ignore_case: bool) -> SearchPattern {
+        let needle = if ignore_case { pattern.to_lowercase() } else { pattern.to_string() };
+        SearchPattern::Substring { needle, ignore_case }
+    }
+
+    // Function to build a regular expression pattern
+    pub fn regex(pattern: &str, ignore_case: bool) -> Result<SearchPattern, regex::Error> {
+        let regex = RegexBuilder::new(pattern).case_insensitive(ignore_case).build()?;
+        Ok(SearchPattern::Regex(regex))
+    }
+
+    // Function to check whether the pattern occurs anywhere in a piece of text
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            SearchPattern::Substring { needle, ignore_case: true } => text.to_lowercase().contains(needle.as_str()),
+            SearchPattern::Substring { needle, ignore_case: false } => text.contains(needle.as_str()),
+            SearchPattern::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+// Struct representing what `Hive::search` looks at
+#[derive(Debug, Clone, Copy)]
+pub struct SearchOptions {
+    pub keys: bool,   // Key names
+    pub values: bool, // Value names and value data that reads as text or a number
+}
+
+impl Default for SearchOptions {
+    fn default() -> SearchOptions {
+        SearchOptions { keys: true, values: true }
+    }
+}
+
+// Struct representing one hit from `Hive::search`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub path: String,               // Key path relative to the root
+    pub value_name: Option<String>, // Set when a value's name or data matched ("" is the default value)
+    pub text: String,               // The key name, value name or data text that matched
+}
+
+// Function to render value data as the strings a search should see: string types as text (one
+// entry per MULTI_SZ element), integer types in decimal. Other types are not searched.
+pub(crate) fn searchable_data(data_type: u32, data: &[u8]) -> Vec<String> {
+    match data_type {
+        REG_SZ | REG_EXPAND_SZ | REG_LINK | REG_MULTI_SZ => {
+            let string_utf16: Vec<u16> = data
+                .chunks_exact(2)
+                .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+                .collect();
+            String::from_utf16_lossy(&string_utf16)
+                .split('\0')
+                .filter(|string| !string.is_empty())
+                .map(str::to_string)
+                .collect()
+        }
+        REG_DWORD if data.len() == 4 => vec![u32::from_le_bytes([data[0], data[1], data[2], data[3]]).to_string()],
+        REG_DWORD_BIG_ENDIAN if data.len() == 4 => {
+            vec![u32::from_be_bytes([data[0], data[1], data[2], data[3]]).to_string()]
+        }
+        REG_QWORD if data.len() == 8 => {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(data);
+            vec![u64::from_le_bytes(bytes).to_string()]
+        }
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,11 @@
+// Struct representing one "sk" record. Security descriptors are shared: every key with the same
+// permissions points at one record, which counts the keys referencing it. All records in a
+// hive are chained into a circular doubly linked list through `flink` and `blink`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityDescriptor {
+    pub offset: u32,          // Cell offset of the record, shared by every key using it
+    pub flink: u32,           // Next record in the list
+    pub blink: u32,           // Previous record in the list
+    pub reference_count: u32, // Number of keys using the record
+    pub descriptor: Vec<u8>,  // Self-relative SECURITY_DESCRIPTOR
+}
@@ -0,0 +1,83 @@
+use std::{fs, io::Cursor, path::Path, sync::Arc};
+
+use crate::{Hive, HiveError, HiveOptions, KeyNode, KeyValue, Located, RegistryValue};
+
+// In-memory hive handle for reading from several threads at once
+pub type HiveView = Hive<Cursor<Arc<[u8]>>>;
+
+// Struct representing a read-only hive held in a shared buffer.
+//
+// A `Hive` reads through a cursor, so even lookups take `&mut self`. `SharedHive` keeps the bytes
+// in an `Arc<[u8]>` and hands every query its own cursor over them, so it is `Send + Sync`, can be
+// queried through `&self` from many threads, and clones by bumping a reference count. The common
+// lookups are available directly; for the rest of the `Hive` API, take a `view`.
+#[derive(Clone)]
+pub struct SharedHive {
+    hive: HiveView,
+}
+
+impl SharedHive {
+    // Function to read a hive file into memory with the default options
+    pub fn open(hive_path: &Path) -> Result<SharedHive, HiveError> {
+        SharedHive::open_with_options(hive_path, HiveOptions::default())
+    }
+
+    // Function to read a hive file into memory with the given options
+    pub fn open_with_options(hive_path: &Path, options: HiveOptions) -> Result<SharedHive, HiveError> {
+        SharedHive::from_bytes_with_options(fs::read(hive_path)?, options)
+    }
+
+    // Function to use an in-memory hive with the default options
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Result<SharedHive, HiveError> {
+        SharedHive::from_bytes_with_options(bytes, HiveOptions::default())
+    }
+
+    // Function to use an in-memory hive with the given options
+    pub fn from_bytes_with_options(bytes: impl Into<Arc<[u8]>>, options: HiveOptions) -> Result<SharedHive, HiveError> {
+        let mut hive = Hive::from_reader_with_options(Cursor::new(bytes.into()), options)?;
+        // Cache the root so views don't each read it; an error resurfaces when a view needs it
+        hive.root().ok();
+        Ok(SharedHive { hive })
+    }
+
+    // Function to get a hive handle with its own cursor over the shared buffer, for the parts of
+    // the `Hive` API not offered here. Views are cheap and independent of each other.
+    pub fn view(&self) -> HiveView {
+        self.hive.clone()
+    }
+
+    // Function to get the root key node
+    pub fn root(&self) -> Result<Located<KeyNode>, HiveError> {
+        self.view().root()
+    }
+
+    // Function to open the key at a backslash-separated path relative to the root key
+    pub fn open_key(&self, path: &str) -> Result<Located<KeyNode>, HiveError> {
+        self.view().open_key(path)
+    }
+
+    // Function to check whether a key path exists, treating any error as absence
+    pub fn exists(&self, path: &str) -> bool {
+        self.view().exists(path)
+    }
+
+    // Function to list the names of the direct subkeys of a key node in stored order
+    pub fn subkey_names(&self, key_node: &KeyNode) -> Result<Vec<String>, HiveError> {
+        self.view().subkey_names(key_node)
+    }
+
+    // Function to list the values of a key node in stored order with their names
+    pub fn values(&self, key_node: &KeyNode) -> Result<Vec<(String, Located<KeyValue>)>, HiveError> {
+        self.view().values(key_node).collect()
+    }
+
+    // Function to read the raw data of a key value
+    pub fn value_data(&self, key_value: &KeyValue) -> Result<Vec<u8>, HiveError> {
+        self.view().value_data(key_value)
+    }
+
+    // Function to read a key value's data decoded according to its type
+    pub fn read_value(&self, key_value: &KeyValue) -> Result<RegistryValue, HiveError> {
+        self.view().read_value(key_value)
+    }
+}
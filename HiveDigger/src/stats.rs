@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+
+// Struct representing the fields of a hive's base block (the "regf" header), from
+// `Hive::header_summary`. Offsets are relative to the hive bins data.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeaderSummary {
+    pub major_version: u32,
+    pub minor_version: u32,
+    pub primary_seq_num: u32,
+    pub secondary_seq_num: u32,
+    pub dirty: bool, // The sequence numbers differ, see `Hive::is_dirty`
+    pub last_written: DateTime<Utc>,
+    pub root_cell_offset: u32,
+    pub hive_bins_data_size: u32,
+    pub checksum: u32,        // As stored at offset 508 of the file
+    pub checksum_valid: bool, // The stored checksum matches the one computed over the stored base block
+    pub file_name: String,
+    pub boot_type: u32,    // See `Hive::boot_type`
+    pub boot_recover: u32, // See `Hive::boot_recover`
+}
+
+// Struct representing a summary of a hive's header and contents, from `Hive::statistics`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HiveStatistics {
+    pub major_version: u32,
+    pub minor_version: u32,
+    pub file_name: String, // Base block file name (the last 31 characters of the hive's path at most)
+    pub dirty: bool,       // See `Hive::is_dirty`
+    pub boot_type: u32,    // See `Hive::boot_type`
+    pub boot_recover: u32, // See `Hive::boot_recover`
+    pub key_count: u64,
+    pub value_count: u64,
+    pub max_depth: usize, // Of the deepest key, counting the root's subkeys as depth 1
+    pub largest_value: Option<LargestValue>,
+    pub free_cell_count: u64,
+    pub free_bytes: u64,
+    pub errors: u64, // Keys and value lists that couldn't be read and were skipped (`skip_errors` only)
+}
+
+// Struct representing the value with the most data in a hive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LargestValue {
+    pub path: String, // Key path relative to the root
+    pub name: String,
+    pub size: u32, // Data size in bytes, as recorded in the key value
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, NaiveDate, ParseError, Utc};
+
+use crate::HiveError;
+
+// Registry artifacts store timestamps in two epochs, and applying the wrong conversion gives
+// dates that look plausible but are badly off:
+//
+// FILETIME (100ns intervals since 1601-01-01, 8 bytes), use `filetime_to_datetime`:
+//   - key last-written times in every nk record and in the base block
+//   - Microsoft\Windows NT\CurrentVersion\InstallTime (REG_QWORD)
+//   - UserAssist last-run times and AppCompatCache (ShimCache) modification times
+//
+// Unix epoch (seconds since 1970-01-01, 4 bytes), use `unix_epoch_to_datetime`:
+//   - Microsoft\Windows NT\CurrentVersion\InstallDate (REG_DWORD)
+//   - Tcpip\Parameters\Interfaces\{...}\LeaseObtainedTime / LeaseTerminatesTime (DHCP leases)
+//
+// NetworkList profile dates are neither: they are 16-byte SYSTEMTIME structures in the machine's
+// local time; see `systemtime_to_datetime`. Shell items (shellbags) carry FAT-style DOS dates and
+// times; see `dos_datetime_to_datetime`.
+
+// Seconds between the FILETIME epoch (1601-01-01) and the Unix epoch (1970-01-01)
+const FILETIME_UNIX_EPOCH_DIFFERENCE: i64 = 11_644_473_600;
+
+// FILETIME ticks per second
+const FILETIME_TICKS_PER_SECOND: u64 = 10_000_000;
+
+// Function to convert a FILETIME to a UTC date and time
+pub fn filetime_to_datetime(filetime: u64) -> DateTime<Utc> {
+    let seconds = (filetime / FILETIME_TICKS_PER_SECOND) as i64 - FILETIME_UNIX_EPOCH_DIFFERENCE;
+    let nanoseconds = (filetime % FILETIME_TICKS_PER_SECOND) as u32 * 100;
+    // Every u64 FILETIME (up to year ~60056) is within chrono's range
+    DateTime::from_timestamp(seconds, nanoseconds).unwrap_or_default()
+}
+
+// Function to convert 32-bit Unix epoch seconds to a UTC date and time
+pub fn unix_epoch_to_datetime(secs: u32) -> DateTime<Utc> {
+    DateTime::from_timestamp(secs as i64, 0).unwrap_or_default()
+}
+
+// Function to convert a DOS date and time (as in FAT directory entries and shell items, in UTC
+// and to 2-second precision) to a UTC date and time. None for 0 (not set) or an invalid date.
+pub fn dos_datetime_to_datetime(date: u16, time: u16) -> Option<DateTime<Utc>> {
+    if date == 0 && time == 0 {
+        return None;
+    }
+    let (year, month, day) = (1980 + (date >> 9) as i32, ((date >> 5) & 0x0F) as u32, (date & 0x1F) as u32);
+    let (hours, minutes, seconds) = ((time >> 11) as u32, ((time >> 5) & 0x3F) as u32, (time & 0x1F) as u32 * 2);
+    let datetime = NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hours, minutes, seconds)?;
+    Some(datetime.and_utc())
+}
+
+// Function to convert a 16-byte SYSTEMTIME (year, month, day of week, day, hour, minute, second
+// and milliseconds as little-endian u16s) to a date and time, checking every field is in range.
+// A SYSTEMTIME carries no time zone: NetworkList and most other writers store local time, which
+// comes back labelled UTC, so take `naive_utc()` of the result where the zone isn't known.
+// All-zero data (not set) is an error like any other invalid date.
+pub fn systemtime_to_datetime(bytes: &[u8; 16]) -> Result<DateTime<Utc>, HiveError> {
+    let mut fields = [0u16; 8];
+    for (field, chunk) in fields.iter_mut().zip(bytes.chunks_exact(2)) {
+        *field = u16::from_le_bytes([chunk[0], chunk[1]]);
+    }
+    let [year, month, day_of_week, day, hour, minute, second, milliseconds] = fields;
+    let out_of_range = |name: &str, value: u16| HiveError::InvalidValueData(format!("SYSTEMTIME {} {} is out of range", name, value));
+
+    // The day of the week (0 is Sunday) is only range-checked, since it is redundant with the date
+    for (name, value, range) in [
+        ("year", year, 1601..=30827),
+        ("month", month, 1..=12),
+        ("day of week", day_of_week, 0..=6),
+        ("hour", hour, 0..=23),
+        ("minute", minute, 0..=59),
+        ("second", second, 0..=59),
+        ("milliseconds", milliseconds, 0..=999),
+    ] {
+        if !range.contains(&value) {
+            return Err(out_of_range(name, value));
+        }
+    }
+    let date = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32).ok_or_else(|| out_of_range("day", day))?;
+    let datetime = date
+        .and_hms_milli_opt(hour as u32, minute as u32, second as u32, milliseconds as u32)
+        .ok_or_else(|| out_of_range("hour", hour))?;
+    Ok(datetime.and_utc())
+}
+
+// Struct representing a window of time, e.g. an incident's, to narrow keys down by last-written
+// time. Either end may be left open; `since` is inclusive and `until` exclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    // Function to build a time range from RFC 3339 bounds such as "2024-03-01T00:00:00Z"
+    pub fn parse(since: Option<&str>, until: Option<&str>) -> Result<TimeRange, ParseError> {
+        let parse = |bound: &str| DateTime::parse_from_rfc3339(bound).map(|bound| bound.with_timezone(&Utc));
+        Ok(TimeRange {
+            since: since.map(parse).transpose()?,
+            until: until.map(parse).transpose()?,
+        })
+    }
+
+    // Function to check whether a time falls within the range
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.since.is_none_or(|since| time >= since) && self.until.is_none_or(|until| time < until)
+    }
+}
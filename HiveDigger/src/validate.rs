@@ -0,0 +1,85 @@
+use std::fmt;
+
+// Struct representing the result of `Hive::validate`: every integrity problem found, not just
+// the first. An empty report means the header, bins, cells and key tree all check out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    // Function to check whether no issues were found
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+// Enum for the problems `Hive::validate` reports. Offsets are relative to the hive bins data
+// and paths are key paths relative to the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    // The base block's stored checksum doesn't match its contents
+    ChecksumMismatch { stored: u32, computed: u32 },
+    // The base block was damaged and rebuilt under `HiveOptions::recovery`
+    HeaderRecovered,
+    // The base block's root cell offset doesn't lead to the root key, which `HiveOptions::recovery`
+    // found at `found` by scanning the bins instead
+    RootKeyRelocated { stored: u32, found: u32 },
+    // A hive bin header is missing or inconsistent; scanning resumed at the next valid bin
+    InvalidBin { offset: u32 },
+    // A cell size would step outside its bin; the rest of the bin was skipped
+    InvalidCell { offset: u32 },
+    // A key node's subkey count disagrees with its subkey lists
+    SubkeyCountMismatch { path: String, recorded: u32, found: u32 },
+    // A key node or key value points past the end of the hive bins data
+    OffsetOutOfBounds { path: String, field: &'static str, offset: u32 },
+    // The data cell at `a` of a value runs into the allocated cell at `b`
+    OverlappingCell { path: String, value_name: String, a: u32, b: u32 },
+    // A subkey list of the key at `path` names a key node already reached through another list
+    CrossLinkedKey { path: String, offset: u32 },
+    // A key, subkey list, value list or value couldn't be read at all
+    Unreadable { path: String, error: String },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::ChecksumMismatch { stored, computed } => {
+                write!(f, "Base block checksum {:#010x} doesn't match computed {:#010x}", stored, computed)
+            }
+            ValidationIssue::HeaderRecovered => write!(f, "Base block was damaged and rebuilt"),
+            ValidationIssue::RootKeyRelocated { stored, found } => {
+                write!(f, "Root cell offset {:#x} doesn't lead to the root key, found at {:#x}", stored, found)
+            }
+            ValidationIssue::InvalidBin { offset } => write!(f, "Invalid hive bin at offset {:#x}", offset),
+            ValidationIssue::InvalidCell { offset } => write!(f, "Invalid cell size at offset {:#x}", offset),
+            ValidationIssue::SubkeyCountMismatch { path, recorded, found } => {
+                write!(f, "{}: key records {} subkeys but lists {}", key_path(path), recorded, found)
+            }
+            ValidationIssue::OffsetOutOfBounds { path, field, offset } => {
+                write!(f, "{}: {} {:#x} is past the end of the hive bins", key_path(path), field, offset)
+            }
+            ValidationIssue::OverlappingCell { path, value_name, a, b } => write!(
+                f,
+                "{}\\{}: data cell at {:#x} overlaps allocated cell at {:#x}",
+                key_path(path),
+                value_name,
+                a,
+                b
+            ),
+            ValidationIssue::CrossLinkedKey { path, offset } => {
+                write!(f, "{}: subkey at {:#x} is already listed under another key", key_path(path), offset)
+            }
+            ValidationIssue::Unreadable { path, error } => write!(f, "{}: {}", key_path(path), error),
+        }
+    }
+}
+
+// Function to show the root key, whose path is empty, as "(root)"
+fn key_path(path: &str) -> &str {
+    if path.is_empty() {
+        "(root)"
+    } else {
+        path
+    }
+}
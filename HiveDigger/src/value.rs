@@ -0,0 +1,227 @@
+use std::{collections::HashMap, fmt};
+
+const REG_NONE: u32 = 0;
+const REG_SZ: u32 = 1;
+const REG_EXPAND_SZ: u32 = 2;
+const REG_BINARY: u32 = 3;
+const REG_DWORD: u32 = 4;
+const REG_DWORD_BIG_ENDIAN: u32 = 5;
+const REG_LINK: u32 = 6;
+const REG_MULTI_SZ: u32 = 7;
+const REG_RESOURCE_LIST: u32 = 8;
+const REG_FULL_RESOURCE_DESCRIPTOR: u32 = 9;
+const REG_RESOURCE_REQUIREMENTS_LIST: u32 = 10;
+// Also known as REG_QWORD_LITTLE_ENDIAN; there is no big-endian QWORD type
+const REG_QWORD: u32 = 11;
+
+// Number of bytes of binary data shown by Display before it is elided
+const BINARY_PREVIEW_LENGTH: usize = 16;
+
+// Enum for value data decoded according to its REG_* type.
+// `Debug` shows the variant and all of its data; `Display` is a short human-readable rendering
+// for CLI output and logs (strings as-is, integers as hex and decimal, binary as a preview).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryValue {
+    None(Vec<u8>),
+    String(String),
+    ExpandString(String),
+    Binary(Vec<u8>),
+    Dword(u32),
+    // REG_DWORD_BIG_ENDIAN: exactly 4 bytes, most significant first. The bytes are kept in stored
+    // order even when resident in the data offset field.
+    DwordBigEndian(u32),
+    // REG_LINK: the UTF-16 target of a symbolic link key (its SymbolicLinkValue), a native path
+    // such as \Registry\Machine\System\ControlSet001. Windows stores it without a terminator;
+    // one is trimmed if present.
+    Link(String),
+    MultiString(Vec<String>),
+    // Little-endian, from exactly 8 bytes of data. QWORDs never fit in the 4-byte data offset
+    // field, so the data is always in its own cell.
+    Qword(u64),
+    // Resource lists, and integer types whose data has the wrong size
+    Other { data_type: u32, data: Vec<u8> },
+    // A type outside the documented REG_* set, from a vendor or from corruption, with its data
+    // left as stored (rejected instead with `HiveOptions::strict`)
+    Unknown { type_id: u32, raw: Vec<u8> },
+}
+
+impl RegistryValue {
+    // Function to decode raw value data of the given REG_* type
+    pub fn decode(data_type: u32, data: &[u8]) -> RegistryValue {
+        match data_type {
+            REG_NONE => RegistryValue::None(data.to_vec()),
+            REG_SZ => RegistryValue::String(decode_string(data)),
+            REG_EXPAND_SZ => RegistryValue::ExpandString(decode_string(data)),
+            REG_BINARY => RegistryValue::Binary(data.to_vec()),
+            REG_DWORD if data.len() == 4 => {
+                RegistryValue::Dword(u32::from_le_bytes([data[0], data[1], data[2], data[3]]))
+            }
+            REG_DWORD_BIG_ENDIAN if data.len() == 4 => {
+                RegistryValue::DwordBigEndian(u32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+            }
+            REG_LINK => RegistryValue::Link(decode_string(data)),
+            REG_MULTI_SZ => RegistryValue::MultiString(decode_multi_string(data)),
+            REG_QWORD if data.len() == 8 => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(data);
+                RegistryValue::Qword(u64::from_le_bytes(bytes))
+            }
+            _ if type_name(data_type).is_none() => RegistryValue::Unknown {
+                type_id: data_type,
+                raw: data.to_vec(),
+            },
+            _ => RegistryValue::Other {
+                data_type,
+                data: data.to_vec(),
+            },
+        }
+    }
+
+    // Function to expand the `%NAME%` references in REG_EXPAND_SZ text with the given variables,
+    // e.g. from ControlSet00N\Control\Session Manager\Environment. Names are matched
+    // case-insensitively, like Windows does, and unknown variables are left as they are.
+    // Returns None for any other type.
+    pub fn expand_with(&self, environment: &HashMap<String, String>) -> Option<String> {
+        let RegistryValue::ExpandString(string) = self else {
+            return None;
+        };
+
+        let variables: HashMap<String, &str> = environment
+            .iter()
+            .map(|(variable, value)| (variable.to_lowercase(), value.as_str()))
+            .collect();
+        let mut expanded = String::with_capacity(string.len());
+        let mut rest = string.as_str();
+        while let Some(start) = rest.find('%') {
+            expanded.push_str(&rest[..start]);
+            let after_start = &rest[start + 1..];
+            let Some(end) = after_start.find('%') else {
+                // An unpaired '%' is literal text
+                rest = &rest[start..];
+                break;
+            };
+
+            let name = &after_start[..end];
+            let value = variables.get(&name.to_lowercase()).filter(|_| !name.is_empty());
+            match value {
+                Some(value) => expanded.push_str(value),
+                None => expanded.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &after_start[end + 1..];
+        }
+        expanded.push_str(rest);
+        Some(expanded)
+    }
+
+    // Function to get the REG_* type the value was decoded from
+    pub fn data_type(&self) -> u32 {
+        match self {
+            RegistryValue::None(_) => REG_NONE,
+            RegistryValue::String(_) => REG_SZ,
+            RegistryValue::ExpandString(_) => REG_EXPAND_SZ,
+            RegistryValue::Binary(_) => REG_BINARY,
+            RegistryValue::Dword(_) => REG_DWORD,
+            RegistryValue::DwordBigEndian(_) => REG_DWORD_BIG_ENDIAN,
+            RegistryValue::Link(_) => REG_LINK,
+            RegistryValue::MultiString(_) => REG_MULTI_SZ,
+            RegistryValue::Qword(_) => REG_QWORD,
+            RegistryValue::Other { data_type, .. } => *data_type,
+            RegistryValue::Unknown { type_id, .. } => *type_id,
+        }
+    }
+
+    // Function to render the value as JSON: `{"type": "REG_SZ", "data": ...}` with strings as
+    // strings, integers as numbers, REG_MULTI_SZ as an array and anything else as lowercase hex.
+    // Unknown types are given by number.
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        let data_type = match type_name(self.data_type()) {
+            Some(type_name) => serde_json::json!(type_name),
+            None => serde_json::json!(self.data_type()),
+        };
+        let data = match self {
+            RegistryValue::String(string) | RegistryValue::ExpandString(string) | RegistryValue::Link(string) => {
+                serde_json::json!(string)
+            }
+            RegistryValue::Dword(dword) | RegistryValue::DwordBigEndian(dword) => serde_json::json!(dword),
+            RegistryValue::Qword(qword) => serde_json::json!(qword),
+            RegistryValue::MultiString(strings) => serde_json::json!(strings),
+            RegistryValue::None(data)
+            | RegistryValue::Binary(data)
+            | RegistryValue::Other { data, .. }
+            | RegistryValue::Unknown { raw: data, .. } => {
+                serde_json::json!(data.iter().map(|byte| format!("{:02x}", byte)).collect::<String>())
+            }
+        };
+        serde_json::json!({ "type": data_type, "data": data })
+    }
+}
+
+// Function to get the name of a REG_* type, or None for types this crate doesn't know
+pub(crate) fn type_name(data_type: u32) -> Option<&'static str> {
+    match data_type {
+        REG_NONE => Some("REG_NONE"),
+        REG_SZ => Some("REG_SZ"),
+        REG_EXPAND_SZ => Some("REG_EXPAND_SZ"),
+        REG_BINARY => Some("REG_BINARY"),
+        REG_DWORD => Some("REG_DWORD"),
+        REG_DWORD_BIG_ENDIAN => Some("REG_DWORD_BIG_ENDIAN"),
+        REG_LINK => Some("REG_LINK"),
+        REG_MULTI_SZ => Some("REG_MULTI_SZ"),
+        REG_RESOURCE_LIST => Some("REG_RESOURCE_LIST"),
+        REG_FULL_RESOURCE_DESCRIPTOR => Some("REG_FULL_RESOURCE_DESCRIPTOR"),
+        REG_RESOURCE_REQUIREMENTS_LIST => Some("REG_RESOURCE_REQUIREMENTS_LIST"),
+        REG_QWORD => Some("REG_QWORD"),
+        _ => None,
+    }
+}
+
+impl fmt::Display for RegistryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryValue::String(string) | RegistryValue::ExpandString(string) | RegistryValue::Link(string) => {
+                write!(f, "{}", string)
+            }
+            RegistryValue::Dword(dword) | RegistryValue::DwordBigEndian(dword) => write!(f, "0x{:08x} ({})", dword, dword),
+            RegistryValue::Qword(qword) => write!(f, "0x{:016x} ({})", qword, qword),
+            RegistryValue::MultiString(strings) => write!(f, "{}", strings.join(", ")),
+            RegistryValue::None(data)
+            | RegistryValue::Binary(data)
+            | RegistryValue::Other { data, .. }
+            | RegistryValue::Unknown { raw: data, .. } => {
+                let mut preview: Vec<String> = data
+                    .iter()
+                    .take(BINARY_PREVIEW_LENGTH)
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect();
+                if data.len() > BINARY_PREVIEW_LENGTH {
+                    preview.push("...".to_string());
+                }
+                preview.push(format!("({} bytes)", data.len()));
+                write!(f, "{}", preview.join(" "))
+            }
+        }
+    }
+}
+
+// Function to decode UTF-16LE string data up to its NUL terminator, replacing invalid code units
+fn decode_string(data: &[u8]) -> String {
+    let string_utf16: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .take_while(|&character| character != 0)
+        .collect();
+    String::from_utf16_lossy(&string_utf16)
+}
+
+// Function to decode REG_MULTI_SZ data into its strings, stopping at the empty string that ends the list
+fn decode_multi_string(data: &[u8]) -> Vec<String> {
+    let string_utf16: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .collect();
+    string_utf16
+        .split(|&character| character == 0)
+        .take_while(|string| !string.is_empty())
+        .map(String::from_utf16_lossy)
+        .collect()
+}
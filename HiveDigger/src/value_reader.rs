@@ -0,0 +1,49 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+// Struct representing a streaming reader over a key value's data, returned by
+// `Hive::value_reader`. Big data is read one segment at a time, so a multi-megabyte value can be
+// hashed or copied without holding all of it in memory.
+pub struct ValueReader<'a, R> {
+    file: &'a mut R,
+    resident: VecDeque<u8>,        // Data stored in the key value itself (at most 4 bytes)
+    segments: VecDeque<(u64, u32)>, // File position and length of each stretch of data left to read
+}
+
+impl<'a, R: Read + Seek> ValueReader<'a, R> {
+    pub(crate) fn new(file: &'a mut R, resident: &[u8], segments: Vec<(u64, u32)>) -> ValueReader<'a, R> {
+        ValueReader {
+            file,
+            resident: resident.iter().copied().collect(),
+            segments: segments.into(),
+        }
+    }
+}
+
+impl<R: Read + Seek> Read for ValueReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.resident.is_empty() {
+            return self.resident.read(buf);
+        }
+
+        while let Some((position, length)) = self.segments.front_mut() {
+            if *length == 0 {
+                self.segments.pop_front();
+                continue;
+            }
+
+            let read_length = buf.len().min(*length as usize);
+            self.file.seek(SeekFrom::Start(*position))?;
+            let read = self.file.read(&mut buf[..read_length])?;
+            if read == 0 && read_length > 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Value data is truncated"));
+            }
+            *position += read as u64;
+            *length -= read as u32;
+            return Ok(read);
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveOptions, RegistryValue};
+
+#[test]
+fn all_values_yields_every_value_counted_by_statistics() {
+    for name in ["SOFTWARE", "SAM", "NTUSER.DAT"] {
+        let mut hive = Hive::open(&fixture_path(name)).unwrap();
+        let value_count = hive.statistics().unwrap().value_count;
+        let values: Vec<_> = hive.all_values().collect::<Result<_, _>>().unwrap();
+        assert_eq!(values.len() as u64, value_count, "{}", name);
+    }
+}
+
+#[test]
+fn all_values_pairs_values_with_their_key_paths() {
+    let options = HiveOptions {
+        skip_errors: true,
+        ..HiveOptions::default()
+    };
+    let mut hive = Hive::open_with_options(&fixture_path("SYSTEM"), options).unwrap();
+    let values: Vec<_> = hive.all_values().filter_map(Result::ok).collect();
+
+    assert!(values.contains(&(
+        "ControlSet001\\Control\\ComputerName\\ComputerName".to_string(),
+        "ComputerName".to_string(),
+        RegistryValue::String("WORKSTATION-01".to_string()),
+    )));
+    assert!(values.contains(&("Select".to_string(), "Current".to_string(), RegistryValue::Dword(1))));
+}
+
+#[test]
+fn walk_values_of_type_yields_only_that_type() {
+    const REG_DWORD: u32 = 4;
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let values: Vec<_> = hive.walk_values_of_type(REG_DWORD, |_| true).collect::<Result<_, _>>().unwrap();
+
+    assert_eq!(values.len(), 11);
+    assert!(values.iter().all(|(_, _, value)| matches!(value, RegistryValue::Dword(_))));
+    assert!(values.contains(&("Select".to_string(), "LastKnownGood".to_string(), RegistryValue::Dword(2))));
+}
+
+#[test]
+fn walk_values_of_type_applies_the_size_filter() {
+    const REG_BINARY: u32 = 3;
+    let mut hive = Hive::open(&fixture_path("SAM")).unwrap();
+    let paths: Vec<_> = hive
+        .walk_values_of_type(REG_BINARY, |size| size > 0x80)
+        .map(|value| {
+            let (path, value_name, _) = value.unwrap();
+            format!("{}\\{}", path, value_name)
+        })
+        .collect();
+
+    assert_eq!(
+        paths,
+        [
+            "SAM\\Domains\\Account\\Users\\000001F4\\V",
+            "SAM\\Domains\\Account\\Users\\000001F5\\V"
+        ]
+    );
+}
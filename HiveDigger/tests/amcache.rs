@@ -0,0 +1,45 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::fixtures::fixture_path;
+use hivedigger::{
+    artifacts::{parse_inventory_application_file, AmcacheFile},
+    Hive, HiveError,
+};
+
+#[test]
+fn inventory_application_file_entries_are_parsed() {
+    let mut hive = Hive::open(&fixture_path("Amcache.hve")).unwrap();
+    let files = parse_inventory_application_file(&mut hive).unwrap();
+
+    assert_eq!(
+        files,
+        [
+            AmcacheFile {
+                key_name: "notepad.exe|8f1e3c2d5a6b7c9e".to_string(),
+                path: Some("c:\\windows\\system32\\notepad.exe".to_string()),
+                sha1: Some("f1c4d5e8a3b6c9d2e7f0a1b4c7d0e3f6a9b2c5d8".to_string()),
+                link_date: Some(Utc.with_ymd_and_hms(2019, 4, 17, 18, 40, 0).unwrap()),
+                publisher: Some("microsoft corporation".to_string()),
+                last_written: Utc.with_ymd_and_hms(2019, 4, 17, 18, 40, 0).unwrap(),
+            },
+            AmcacheFile {
+                key_name: "dropper.exe|1a2b3c4d5e6f7a8b".to_string(),
+                path: Some("c:\\users\\analyst\\downloads\\dropper.exe".to_string()),
+                sha1: Some("5e884898da28047151d0e56f8dc6292773603d0d".to_string()),
+                link_date: None,
+                publisher: None,
+                last_written: Utc.with_ymd_and_hms(2023, 6, 17, 9, 15, 0).unwrap(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn other_hives_have_no_inventory() {
+    let mut hive = Hive::open(&fixture_path("SOFTWARE")).unwrap();
+    assert!(matches!(
+        parse_inventory_application_file(&mut hive),
+        Err(HiveError::KeyNotFound(_))
+    ));
+}
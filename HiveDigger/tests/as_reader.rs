@@ -0,0 +1,43 @@
+mod common;
+
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+use common::fixtures::fixture_path;
+use hivedigger::Hive;
+
+// Function to read `length` bytes at a position through a hive's reader
+fn read_at<R: Read + Seek>(reader: &mut R, position: u64, length: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; length];
+    reader.seek(SeekFrom::Start(position)).unwrap();
+    reader.read_exact(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn signature_reads_through_the_handle_of_a_hive_at_offset_zero() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    assert_eq!(read_at(hive.as_reader(), 0, 4), b"regf");
+    assert_eq!(read_at(hive.as_reader(), 4096, 4), b"hbin");
+
+    // The hive seeks before reading, so moving the handle doesn't disturb it
+    hive.as_reader().seek(SeekFrom::End(0)).unwrap();
+    assert!(hive.open_key("Select").is_ok());
+}
+
+#[test]
+fn positions_are_relative_to_an_embedded_hive() {
+    // The SYSTEM fixture 1234 bytes into a larger image
+    let mut image = vec![0xccu8; 1234];
+    image.extend(std::fs::read(fixture_path("SYSTEM")).unwrap());
+    let mut hive = Hive::from_reader_at(Cursor::new(image.as_slice()), 1234).unwrap();
+
+    assert_eq!(read_at(hive.as_reader(), 0, 4), b"regf");
+    // A cell offset from the API is 4096 + 4 bytes from the cell's data, as in a standalone hive
+    let select = hive.open_key("Select").unwrap();
+    assert_eq!(read_at(hive.as_reader(), 4096 + select.offset as u64 + 4, 2), b"nk");
+    assert_eq!(hive.as_reader().base_offset(), 1234);
+    assert_eq!(hive.as_reader().seek(SeekFrom::End(0)).unwrap(), image.len() as u64 - 1234);
+
+    // Seeking before the hive is refused
+    assert!(hive.as_reader().seek(SeekFrom::Current(-(image.len() as i64))).is_err());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use std::io::SeekFrom;
+
+use common::fixtures::{fixture_path, FIXTURES};
+use hivedigger::{AsyncHive, Hive, HiveError};
+use tokio::io::AsyncSeekExt;
+
+// Function to list every key path of a hive in walk order
+fn key_paths<R: std::io::Read + std::io::Seek>(hive: &mut Hive<R>) -> Vec<String> {
+    hive.walk().map(|key| key.unwrap().0).collect()
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap()
+}
+
+#[test]
+fn hives_opened_concurrently_match_the_sync_path() {
+    let tasks_output = runtime().block_on(async {
+        let tasks: Vec<_> = FIXTURES
+            .iter()
+            .map(|fixture| {
+                tokio::spawn(async move {
+                    let mut hive = AsyncHive::open(fixture_path(fixture.name)).await.unwrap();
+                    (fixture.name, key_paths(&mut hive))
+                })
+            })
+            .collect();
+
+        let mut tasks_output = Vec::new();
+        for task in tasks {
+            tasks_output.push(task.await.unwrap());
+        }
+        tasks_output
+    });
+
+    for (name, async_paths) in tasks_output {
+        let mut hive = Hive::open(&fixture_path(name)).unwrap();
+        assert_eq!(async_paths, key_paths(&mut hive), "{name}");
+    }
+}
+
+#[test]
+fn reader_is_buffered_from_its_start() {
+    runtime().block_on(async {
+        let mut file = tokio::fs::File::open(fixture_path("SYSTEM")).await.unwrap();
+        file.seek(SeekFrom::Start(4096)).await.unwrap();
+        let mut hive = AsyncHive::from_reader(file).await.unwrap();
+
+        assert!(hive.open_key("ControlSet001\\Control\\Lsa").is_ok());
+        assert!(hive.into_inner().open_key("Select").is_ok());
+    });
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    let result = runtime().block_on(AsyncHive::open(fixture_path("NO_SUCH_HIVE")));
+    assert!(matches!(result, Err(HiveError::Io(_))));
+}
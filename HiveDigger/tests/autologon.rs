@@ -0,0 +1,87 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{
+    builder::{HiveBuilder, Key},
+    fixtures::fixture_path,
+};
+use hivedigger::{
+    artifacts::{autologon_info, AutoLogon},
+    Hive,
+};
+
+// Function to build a SOFTWARE hive with the given Winlogon key
+fn software_with_winlogon(winlogon: Key) -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Microsoft").subkey(Key::new("Windows NT").subkey(Key::new("CurrentVersion").subkey(winlogon))),
+    );
+    Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap()
+}
+
+#[test]
+fn autologon_with_a_cleartext_password() {
+    let mut hive = software_with_winlogon(
+        Key::new("Winlogon")
+            .sz("AutoAdminLogon", "1")
+            .sz("DefaultUserName", "kiosk")
+            .sz("DefaultDomainName", "CORP")
+            .sz("DefaultPassword", "Summer2024!"),
+    );
+
+    assert_eq!(
+        autologon_info(&mut hive).unwrap(),
+        Some(AutoLogon {
+            enabled: true,
+            user_name: Some("kiosk".to_string()),
+            domain_name: Some("CORP".to_string()),
+            password: Some("Summer2024!".to_string()),
+        })
+    );
+}
+
+#[test]
+fn autologon_flag_as_a_dword_and_empty_strings_as_missing() {
+    let mut hive = software_with_winlogon(
+        Key::new("Winlogon")
+            .dword("AutoAdminLogon", 1)
+            .sz("DefaultUserName", "kiosk")
+            .sz("DefaultDomainName", "")
+            .sz("DefaultPassword", ""),
+    );
+
+    assert_eq!(
+        autologon_info(&mut hive).unwrap(),
+        Some(AutoLogon {
+            enabled: true,
+            user_name: Some("kiosk".to_string()),
+            domain_name: None,
+            password: None,
+        })
+    );
+}
+
+#[test]
+fn password_left_behind_with_autologon_off_is_still_reported() {
+    let mut hive = software_with_winlogon(
+        Key::new("Winlogon")
+            .sz("AutoAdminLogon", "0")
+            .sz("DefaultUserName", "kiosk")
+            .sz("DefaultPassword", "old"),
+    );
+
+    let autologon = autologon_info(&mut hive).unwrap().unwrap();
+    assert!(!autologon.enabled);
+    assert_eq!(autologon.password.as_deref(), Some("old"));
+}
+
+#[test]
+fn autologon_not_configured_is_none() {
+    // AutoAdminLogon "0" and no password, as in the fixture
+    let mut hive = Hive::open(&fixture_path("SOFTWARE")).unwrap();
+    assert_eq!(autologon_info(&mut hive).unwrap(), None);
+
+    // No Winlogon key at all
+    let mut hive = software_with_winlogon(Key::new("Other"));
+    assert_eq!(autologon_info(&mut hive).unwrap(), None);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::{fixture_path, FIXTURES};
+use hivedigger::{Hive, HiveOptions, ValidationIssue};
+
+fn recovery() -> HiveOptions {
+    HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    }
+}
+
+// Function to list every key path of a hive in walk order
+fn key_paths<R: std::io::Read + std::io::Seek>(hive: &mut Hive<R>) -> Vec<String> {
+    hive.walk().map(|key| key.unwrap().0).collect()
+}
+
+#[test]
+fn zeroed_base_block_is_rebuilt_from_the_bins() {
+    for fixture in FIXTURES {
+        let mut hive_file = std::fs::read(fixture_path(fixture.name)).unwrap();
+        let expected = key_paths(&mut Hive::from_bytes(&hive_file).unwrap());
+        hive_file[..4096].fill(0);
+
+        assert!(Hive::from_reader(Cursor::new(hive_file.clone())).is_err(), "{}", fixture.name);
+        let mut hive = Hive::from_reader_with_options(Cursor::new(hive_file), recovery()).unwrap();
+        assert!(hive.provenance().base_block_rebuilt, "{}", fixture.name);
+        assert_eq!(key_paths(&mut hive), expected, "{}", fixture.name);
+    }
+}
+
+#[test]
+fn rebuilt_hive_reads_values_and_reports_the_rebuild() {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    hive_file[..4096].fill(0);
+    let mut hive = Hive::from_reader_with_options(Cursor::new(hive_file), recovery()).unwrap();
+
+    assert_eq!(hive.value("Select", "Current").unwrap().to_string(), "0x00000001 (1)");
+    assert!(hive.open_key("ControlSet001\\Control\\Lsa").is_ok());
+
+    // Nothing is known of the header on disk beyond its being unreadable
+    assert_eq!(hive.provenance().checksum_valid, None);
+    assert_eq!(hive.provenance().dirty, None);
+
+    let report = hive.validate().unwrap();
+    assert!(report.issues.contains(&ValidationIssue::HeaderRecovered));
+}
+
+#[test]
+fn file_without_bins_cannot_be_recovered() {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    hive_file[..4096].fill(0);
+    hive_file.truncate(4096 + 2048);
+    assert!(Hive::from_reader_with_options(Cursor::new(hive_file), recovery()).is_err());
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{
+    artifacts::{parse_bcd, BcdElementValue},
+    Hive, RegistryValue,
+};
+
+const BOOTMGR: &str = "{9dea862c-5cdd-4e70-acc1-f32b344d4795}";
+const OS_LOADER: &str = "{c2d1e4a6-3b5f-11ee-9a2c-806e6f6e6963}";
+const DEVICE: &[u8] = &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0x48, 0, 0, 0];
+
+// Function to lay out a BCD object key: its type under Description and the element keys, named
+// by element type and holding the data in an "Element" value, under Elements
+fn object(id: &str, object_type: Option<u32>, elements: Vec<Key>) -> Key {
+    let mut key = Key::new(id);
+    if let Some(object_type) = object_type {
+        key = key.subkey(Key::new("Description").dword("Type", object_type));
+    }
+    key.subkey(elements.into_iter().fold(Key::new("Elements"), Key::subkey))
+}
+
+// A BCD store as Windows Setup writes it for a UEFI install: the boot manager and one loader
+fn bcd_hive() -> Vec<u8> {
+    let bootmgr = object(
+        BOOTMGR,
+        Some(0x10100002),
+        vec![
+            Key::new("11000001").binary("Element", DEVICE),
+            Key::new("12000004").sz("Element", "Windows Boot Manager"),
+            Key::new("23000006").sz("Element", OS_LOADER),
+            Key::new("24000001").multi_sz("Element", &[OS_LOADER]),
+            Key::new("25000004").binary("Element", &30u64.to_le_bytes()),
+        ],
+    );
+    let os_loader = object(
+        OS_LOADER,
+        Some(0x10200003),
+        vec![
+            Key::new("12000002").sz("Element", "\\Windows\\system32\\winload.efi"),
+            Key::new("16000009").binary("Element", &[1]),
+            Key::new("22000002").sz("Element", "\\Windows"),
+            // Some integer elements are stored in fewer than 8 bytes
+            Key::new("250000c2").binary("Element", &[1, 0, 0, 0]),
+            // An integer too long for its format, and a key that isn't an element type
+            Key::new("25000080").binary("Element", &[0; 10]),
+            Key::new("Extra").sz("Element", "ignored"),
+        ],
+    );
+    let root = Key::new("ROOT")
+        .subkey(Key::new("Description").sz("KeyName", "BCD00000000"))
+        .subkey(Key::new("Objects").subkey(bootmgr).subkey(os_loader).subkey(object("{untyped}", None, vec![])));
+    HiveBuilder::new().build(&root)
+}
+
+#[test]
+fn objects_and_their_elements_are_decoded() {
+    let mut hive = Hive::from_reader(Cursor::new(bcd_hive())).unwrap();
+    let objects = parse_bcd(&mut hive).unwrap();
+
+    let ids: Vec<&str> = objects.iter().map(|object| object.id.as_str()).collect();
+    assert_eq!(ids, [BOOTMGR, OS_LOADER, "{untyped}"]);
+    let kinds: Vec<Option<&str>> = objects.iter().map(|object| object.kind()).collect();
+    assert_eq!(kinds, [Some("bootmgr"), Some("osloader"), None]);
+
+    let bootmgr = &objects[0];
+    let elements: Vec<_> = bootmgr.elements.iter().map(|element| (element.name, &element.value)).collect();
+    assert_eq!(
+        elements,
+        [
+            (Some("device"), &BcdElementValue::Device(DEVICE.to_vec())),
+            (Some("description"), &BcdElementValue::String("Windows Boot Manager".to_string())),
+            (Some("default"), &BcdElementValue::Object(OS_LOADER.to_string())),
+            (Some("displayorder"), &BcdElementValue::ObjectList(vec![OS_LOADER.to_string()])),
+            (Some("timeout"), &BcdElementValue::Integer(30)),
+        ]
+    );
+}
+
+#[test]
+fn loader_flags_and_malformed_elements() {
+    let mut hive = Hive::from_reader(Cursor::new(bcd_hive())).unwrap();
+    let objects = parse_bcd(&mut hive).unwrap();
+    let os_loader = &objects[1];
+
+    assert_eq!(
+        os_loader.element(0x12000002).unwrap().value,
+        BcdElementValue::String("\\Windows\\system32\\winload.efi".to_string())
+    );
+    assert_eq!(os_loader.element(0x16000009).unwrap().value, BcdElementValue::Boolean(true));
+    assert_eq!(os_loader.element(0x22000002).unwrap().name, Some("systemroot"));
+    assert_eq!(os_loader.element(0x250000c2).unwrap().value, BcdElementValue::Integer(1));
+    assert_eq!(
+        os_loader.element(0x25000080).unwrap().value,
+        BcdElementValue::Other(RegistryValue::Binary(vec![0; 10]))
+    );
+    assert_eq!(os_loader.elements.len(), 5);
+
+    assert_eq!(objects[2].object_type, None);
+    assert!(objects[2].elements.is_empty());
+}
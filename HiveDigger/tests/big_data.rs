@@ -0,0 +1,92 @@
+mod common;
+
+use std::io::{Cursor, Read};
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError, RegistryValue};
+
+const REG_BINARY: u32 = 3;
+
+// Twenty segments, the last one partly filled
+const DATA_SIZE: usize = 19 * 16344 + 1000;
+
+fn big_data() -> Vec<u8> {
+    (0..DATA_SIZE).map(|position| (position % 253) as u8).collect()
+}
+
+// Function to build a hive with one big data value, returning it and the offset of the value's
+// "db" record in the file
+fn build_hive() -> (Vec<u8>, usize) {
+    let root = Key::new("ROOT").binary("Blob", &big_data());
+    let hive_file = HiveBuilder::new().build(&root);
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    let root = hive.root().unwrap();
+    let (_, key_value) = hive.values(&root).next().unwrap().unwrap();
+    // The data offset field sits 8 bytes into the vk record, after the cell header
+    let field = 4096 + key_value.offset as usize + 4 + 8;
+    let big_data_offset = u32::from_le_bytes(hive_file[field..field + 4].try_into().unwrap());
+    (hive_file, 4096 + big_data_offset as usize + 4)
+}
+
+#[test]
+fn big_data_with_many_segments_reads_in_full() {
+    let (hive_file, _) = build_hive();
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let root = hive.root().unwrap();
+    let (_, key_value) = hive.values(&root).next().unwrap().unwrap();
+
+    assert_eq!(hive.value_data(&key_value).unwrap(), big_data());
+    assert_eq!(hive.read_value(&key_value).unwrap(), RegistryValue::Binary(big_data()));
+    let mut streamed = Vec::new();
+    hive.value_reader(&key_value).unwrap().read_to_end(&mut streamed).unwrap();
+    assert_eq!(streamed, big_data());
+}
+
+#[test]
+fn segment_count_beyond_the_list_cell_is_rejected() {
+    let (mut hive_file, big_data_record) = build_hive();
+    // The list cell has room for 21 offsets (84 bytes); claim 30 segments
+    hive_file[big_data_record + 2..big_data_record + 4].copy_from_slice(&30u16.to_le_bytes());
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let root = hive.root().unwrap();
+    let (_, key_value) = hive.values(&root).next().unwrap().unwrap();
+
+    assert!(matches!(
+        hive.value_data(&key_value),
+        Err(HiveError::TruncatedSegmentList { segment_count: 30, capacity: 84, .. })
+    ));
+    assert!(matches!(
+        hive.value_reader(&key_value),
+        Err(HiveError::TruncatedSegmentList { segment_count: 30, capacity: 84, .. })
+    ));
+}
+
+#[test]
+fn data_size_beyond_the_segments_is_rejected() {
+    let (mut hive_file, _) = build_hive();
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    let root = hive.root().unwrap();
+    let (_, key_value) = hive.values(&root).next().unwrap().unwrap();
+    // Claim nearly 2 GiB in the vk's data size field, 4 bytes into the record
+    let field = 4096 + key_value.offset as usize + 4 + 4;
+    hive_file[field..field + 4].copy_from_slice(&0x7fff_ffffu32.to_le_bytes());
+
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let (_, key_value) = hive.values(&root).next().unwrap().unwrap();
+    assert_eq!(key_value.data_len(), 0x7fff_ffff);
+    assert!(matches!(hive.value_data(&key_value), Err(HiveError::InvalidValueData(_))));
+}
+
+#[test]
+fn value_type_does_not_read_big_data() {
+    let (mut hive_file, big_data_record) = build_hive();
+    // Break the "db" record so that any read of the data fails
+    hive_file[big_data_record..big_data_record + 2].copy_from_slice(b"xx");
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let root = hive.root().unwrap();
+    let (_, key_value) = hive.values(&root).next().unwrap().unwrap();
+    assert!(hive.value_data(&key_value).is_err());
+
+    assert_eq!(hive.value_type("", "blob").unwrap(), REG_BINARY);
+    assert!(matches!(hive.value_type("", "Missing"), Err(HiveError::ValueNotFound { .. })));
+}
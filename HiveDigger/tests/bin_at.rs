@@ -0,0 +1,96 @@
+mod common;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{time::filetime_to_datetime, Hive, HiveError};
+
+const SECOND_BIN_TIMESTAMP: u64 = 0x01d9b19d_00000000;
+
+// Function to build a hive whose key tree fills the first bin, followed by a second, empty bin
+// of 8192 bytes holding one free cell. Returns the hive and the second bin's offset.
+fn two_bin_hive() -> (Vec<u8>, u32) {
+    let root = Key::new("ROOT").subkey(Key::new("Software").sz("Name", "value"));
+    let mut hive = HiveBuilder::new().build(&root);
+    let first_bin_size = (hive.len() - 4096) as u32;
+
+    let mut bin = vec![0u8; 8192];
+    bin[0..4].copy_from_slice(b"hbin");
+    bin[4..8].copy_from_slice(&first_bin_size.to_le_bytes());
+    bin[8..12].copy_from_slice(&8192u32.to_le_bytes());
+    bin[20..28].copy_from_slice(&SECOND_BIN_TIMESTAMP.to_le_bytes());
+    bin[32..36].copy_from_slice(&(8192i32 - 32).to_le_bytes());
+    hive.extend(bin);
+
+    set_hive_bins_data_size(&mut hive, first_bin_size + 8192);
+    (hive, first_bin_size)
+}
+
+// Function to record a hive bins data size in the base block and recompute the checksum to match
+fn set_hive_bins_data_size(hive: &mut [u8], hive_bins_data_size: u32) {
+    hive[40..44].copy_from_slice(&hive_bins_data_size.to_le_bytes());
+    let checksum = hive[..508]
+        .chunks_exact(4)
+        .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+    hive[508..512].copy_from_slice(&checksum.to_le_bytes());
+}
+
+#[test]
+fn cells_resolve_to_the_bin_holding_them() {
+    let (data, second_bin_offset) = two_bin_hive();
+    let mut hive = Hive::from_bytes(&data).unwrap();
+
+    let software = hive.open_key("Software").unwrap();
+    let bin = hive.bin_at(software.offset).unwrap();
+    assert_eq!(bin.offset(), 0);
+    assert_eq!(bin.size(), second_bin_offset);
+
+    // The free cell just after the second bin's header, and the last byte of that bin
+    for cell_offset in [second_bin_offset + 32, second_bin_offset + 8191] {
+        let bin = hive.bin_at(cell_offset).unwrap();
+        assert_eq!(bin.offset(), second_bin_offset);
+        assert_eq!(bin.size(), 8192);
+        assert_eq!(bin.last_written(), filetime_to_datetime(SECOND_BIN_TIMESTAMP));
+    }
+
+    // Looked up again from the index built by the first call
+    assert_eq!(hive.bin_at(software.offset).unwrap().offset(), 0);
+}
+
+#[test]
+fn offsets_outside_any_bin_are_an_error() {
+    let (data, second_bin_offset) = two_bin_hive();
+    let mut hive = Hive::from_bytes(&data).unwrap();
+
+    // Inside a bin header, and past the end of the hive bins data
+    for offset in [0, 31, second_bin_offset + 4, second_bin_offset + 8192, u32::MAX] {
+        match hive.bin_at(offset) {
+            Err(HiveError::OffsetOutsideBins { offset: found }) => assert_eq!(found, offset),
+            other => panic!("expected OffsetOutsideBins for {:#x}, got {:?}", offset, other),
+        }
+    }
+}
+
+#[test]
+fn damaged_bin_header_is_reported() {
+    let (mut data, second_bin_offset) = two_bin_hive();
+    let position = 4096 + second_bin_offset as usize;
+    data[position..position + 4].copy_from_slice(b"xxxx");
+    let mut hive = Hive::from_bytes(&data).unwrap();
+
+    match hive.bin_at(32) {
+        Err(HiveError::InvalidBin { offset }) => assert_eq!(offset, second_bin_offset),
+        other => panic!("expected InvalidBin, got {:?}", other),
+    }
+}
+
+#[test]
+fn bin_running_past_the_hive_bins_data_is_reported() {
+    let (mut data, second_bin_offset) = two_bin_hive();
+    // The base block only accounts for half of the second bin
+    set_hive_bins_data_size(&mut data, second_bin_offset + 4096);
+    let mut hive = Hive::from_bytes(&data).unwrap();
+
+    match hive.bin_at(second_bin_offset + 32) {
+        Err(HiveError::InvalidBin { offset }) => assert_eq!(offset, second_bin_offset),
+        other => panic!("expected InvalidBin, got {:?}", other),
+    }
+}
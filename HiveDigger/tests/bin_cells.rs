@@ -0,0 +1,109 @@
+mod common;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Cell, Hive, HiveError};
+
+// Function to build a one-bin hive with a key, a subkey and a string value
+fn small_hive() -> Vec<u8> {
+    let root = Key::new("ROOT").subkey(Key::new("Software").sz("Name", "value"));
+    HiveBuilder::new().build(&root)
+}
+
+// Function to append an empty 4096-byte bin holding one free cell, returning its offset. The
+// base block is left alone, so the bin is trailing data until the hive bins data size is grown.
+fn append_bin(hive: &mut Vec<u8>) -> u32 {
+    let bin_offset = (hive.len() - 4096) as u32;
+    let mut bin = vec![0u8; 4096];
+    bin[0..4].copy_from_slice(b"hbin");
+    bin[4..8].copy_from_slice(&bin_offset.to_le_bytes());
+    bin[8..12].copy_from_slice(&4096u32.to_le_bytes());
+    bin[32..36].copy_from_slice(&(4096i32 - 32).to_le_bytes());
+    hive.extend(bin);
+    bin_offset
+}
+
+#[test]
+fn every_cell_of_the_bin_is_enumerated() {
+    let data = small_hive();
+    let mut hive = Hive::from_bytes(&data).unwrap();
+    let software = hive.open_key("Software").unwrap();
+    let bin = hive.bin_at(software.offset).unwrap();
+
+    let cells = hive.cells_in_bin(&bin).collect::<Result<Vec<_>, _>>().unwrap();
+
+    // Back to back from just after the bin header to the end of the bin
+    let mut next_offset = bin.offset() + 32;
+    for (cell_offset, cell_header, _) in &cells {
+        assert_eq!(*cell_offset, next_offset);
+        next_offset += cell_header.size();
+    }
+    assert_eq!(next_offset, bin.offset() + bin.size());
+
+    let key_names: Vec<&str> = cells
+        .iter()
+        .filter_map(|(_, _, cell)| match cell {
+            Cell::KeyNode { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(key_names, ["ROOT", "Software"]);
+    assert!(cells.iter().any(|(_, _, cell)| matches!(cell, Cell::KeyValue { name, .. } if name == "Name")));
+    assert!(cells.iter().any(|(cell_offset, _, _)| *cell_offset == software.offset));
+
+    // The builder fills the rest of the bin with one free cell
+    let (_, last_header, _) = cells.last().unwrap();
+    assert!(!last_header.is_allocated());
+    assert!(cells[..cells.len() - 1].iter().all(|(_, cell_header, _)| cell_header.is_allocated()));
+}
+
+#[test]
+fn zero_cell_size_stops_the_iteration() {
+    let mut data = small_hive();
+    let (bin, software_offset) = {
+        let mut hive = Hive::from_bytes(&data).unwrap();
+        let software = hive.open_key("Software").unwrap();
+        (hive.bin_at(software.offset).unwrap(), software.offset)
+    };
+    let position = 4096 + software_offset as usize;
+    data[position..position + 4].copy_from_slice(&0i32.to_le_bytes());
+
+    let mut hive = Hive::from_bytes(&data).unwrap();
+    let cells: Vec<_> = hive.cells_in_bin(&bin).collect();
+    match cells.last() {
+        Some(Err(HiveError::InvalidCell { offset })) => assert_eq!(*offset, software_offset),
+        other => panic!("expected InvalidCell last, got {:?}", other),
+    }
+    // Every cell before it was still read
+    assert!(cells[..cells.len() - 1].iter().all(Result::is_ok));
+    assert_eq!(
+        cells[..cells.len() - 1].last().map(|cell| {
+            let (cell_offset, cell_header, _) = cell.as_ref().unwrap();
+            cell_offset + cell_header.size()
+        }),
+        Some(software_offset)
+    );
+}
+
+#[test]
+fn bin_past_the_hive_bins_data_is_invalid() {
+    // Two bins past the first, which only a hive whose base block counts them reaches
+    let mut trailing = small_hive();
+    let hive_bins_data_size = (trailing.len() - 4096) as u32;
+    append_bin(&mut trailing);
+    let last_bin_offset = append_bin(&mut trailing);
+
+    let mut grown = trailing.clone();
+    grown[40..44].copy_from_slice(&(hive_bins_data_size + 8192).to_le_bytes());
+    let checksum = grown[..508]
+        .chunks_exact(4)
+        .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+    grown[508..512].copy_from_slice(&checksum.to_le_bytes());
+    let last_bin = Hive::from_bytes(&grown).unwrap().bin_at(last_bin_offset + 32).unwrap();
+    assert_eq!(last_bin.offset(), last_bin_offset);
+
+    // The bin's bytes are in the file, but it starts beyond the hive bins data size
+    let mut hive = Hive::from_bytes(&trailing).unwrap();
+    let cells: Vec<_> = hive.cells_in_bin(&last_bin).collect();
+    assert!(last_bin_offset > hive_bins_data_size);
+    assert!(matches!(cells[..], [Err(HiveError::InvalidBin { offset })] if offset == last_bin_offset));
+}
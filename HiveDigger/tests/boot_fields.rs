@@ -0,0 +1,42 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::Hive;
+
+// Function to build a small hive with the given boot type and boot recover fields
+fn hive_with_boot_fields(boot_type: u32, boot_recover: u32) -> Hive<Cursor<Vec<u8>>> {
+    let mut hive_file = HiveBuilder::new().build(&Key::new("ROOT"));
+    // Both fields lie past the checksummed first 508 bytes
+    hive_file[4088..4092].copy_from_slice(&boot_type.to_le_bytes());
+    hive_file[4092..4096].copy_from_slice(&boot_recover.to_le_bytes());
+    Hive::from_reader(Cursor::new(hive_file)).unwrap()
+}
+
+#[test]
+fn boot_fields_are_zero_on_a_regular_hive() {
+    let hive = hive_with_boot_fields(0, 0);
+    assert_eq!(hive.boot_type(), 0);
+    assert_eq!(hive.boot_recover(), 0);
+}
+
+#[test]
+fn boot_fields_are_read_from_the_end_of_the_base_block() {
+    let hive = hive_with_boot_fields(4, 2);
+    assert_eq!(hive.boot_type(), 4);
+    assert_eq!(hive.boot_recover(), 2);
+
+    let hive = hive_with_boot_fields(0, 1);
+    assert_eq!(hive.boot_type(), 0);
+    assert_eq!(hive.boot_recover(), 1);
+}
+
+#[test]
+fn statistics_and_header_summary_carry_the_boot_fields() {
+    let mut hive = hive_with_boot_fields(4, 2);
+    let statistics = hive.statistics().unwrap();
+    assert_eq!((statistics.boot_type, statistics.boot_recover), (4, 2));
+    let header = hive.header_summary().unwrap();
+    assert_eq!((header.boot_type, header.boot_recover), (4, 2));
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::{
+    builder::{HiveBuilder, Key},
+    fixtures::fixture_path,
+};
+use hivedigger::{artifacts::extract_bootkey, Hive, HiveError};
+
+#[test]
+fn components_and_bootkey_from_the_fixture() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let bootkey = extract_bootkey(&mut hive).unwrap();
+
+    assert_eq!(bootkey.control_set, "ControlSet001");
+    assert_eq!(
+        [bootkey.jd.as_str(), &bootkey.skew1, &bootkey.gbg, &bootkey.data],
+        ["4b3d8a1e", "2c9f7e10", "aa55aa55", "0123abcd"]
+    );
+    assert_eq!(
+        bootkey.scrambled,
+        [0x4b, 0x3d, 0x8a, 0x1e, 0x2c, 0x9f, 0x7e, 0x10, 0xaa, 0x55, 0xaa, 0x55, 0x01, 0x23, 0xab, 0xcd]
+    );
+    assert_eq!(
+        bootkey.bootkey,
+        [0xaa, 0x9f, 0x2c, 0x8a, 0x55, 0x55, 0x23, 0x1e, 0x4b, 0x7e, 0x3d, 0x01, 0xab, 0xaa, 0xcd, 0x10]
+    );
+}
+
+// Function to build a SYSTEM hive whose Lsa keys have the given class names
+fn system_with_classes(classes: [&str; 4]) -> Vec<u8> {
+    let mut lsa = Key::new("Lsa");
+    for (name, class) in ["JD", "Skew1", "GBG", "Data"].into_iter().zip(classes) {
+        lsa = lsa.subkey(Key::new(name).class(class));
+    }
+    let root = Key::new("ROOT")
+        .subkey(Key::new("ControlSet002").subkey(Key::new("Control").subkey(lsa)))
+        .subkey(Key::new("Select").dword("Current", 2));
+    HiveBuilder::new().build(&root)
+}
+
+#[test]
+fn uppercase_class_names_are_kept_as_stored() {
+    let data = system_with_classes(["4B3D8A1E", "2C9F7E10", "AA55AA55", "0123ABCD"]);
+    let mut hive = Hive::from_bytes(&data).unwrap();
+    let bootkey = extract_bootkey(&mut hive).unwrap();
+
+    assert_eq!(bootkey.control_set, "ControlSet002");
+    assert_eq!(bootkey.jd, "4B3D8A1E");
+    assert_eq!(bootkey.bootkey[0], 0xaa);
+}
+
+#[test]
+fn class_names_that_are_not_hex_are_an_error() {
+    let data = system_with_classes(["4b3d8a1e", "2c9f7e1", "aa55aa55", "0123abcd"]);
+    let mut hive = Hive::from_bytes(&data).unwrap();
+    match extract_bootkey(&mut hive) {
+        Err(HiveError::InvalidValueData(message)) => assert!(message.contains("Skew1"), "{}", message),
+        other => panic!("expected InvalidValueData, got {:?}", other),
+    }
+
+    let data = system_with_classes(["4b3d8a1e", "2c9f7e10", "zz55aa55", "0123abcd"]);
+    let mut hive = Hive::from_bytes(&data).unwrap();
+    assert!(matches!(extract_bootkey(&mut hive), Err(HiveError::InvalidValueData(_))));
+}
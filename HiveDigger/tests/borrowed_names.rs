@@ -0,0 +1,59 @@
+mod common;
+
+use std::{borrow::Cow, io::Cursor};
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::Hive;
+
+// Function to build a hive whose "AB" key has its name flagged as UTF-16 rather than ASCII, so
+// its two bytes read as the single character U+4241
+fn build_hive() -> Vec<u8> {
+    let root = Key::new("ROOT")
+        .subkey(
+            Key::new("Values")
+                .sz("", "default")
+                .sz("Path", "C:\\Windows")
+                .dword("Start", 2)
+                .binary("Large", &vec![0x5a; 20_000]),
+        )
+        .subkey(Key::new("AB"))
+        .subkey(Key::new("Plain"));
+    let mut hive_file = HiveBuilder::new().build(&root);
+    let offset = Hive::from_bytes(&hive_file).unwrap().open_key("AB").unwrap().offset;
+    let flags = 4096 + offset as usize + 4 + 2;
+    hive_file[flags] &= !0x20;
+    hive_file
+}
+
+#[test]
+fn ascii_names_borrow_and_utf16_names_are_transcoded() {
+    let hive_file = build_hive();
+    let mut hive = Hive::from_bytes(&hive_file).unwrap();
+    let root = hive.root().unwrap();
+    let names = hive.subkey_names(&root).unwrap();
+
+    let borrowed = hive.subkey_names_borrowed(&root).unwrap();
+    assert_eq!(borrowed, ["Values", "\u{4241}", "Plain"]);
+    assert!(matches!(borrowed[0], Cow::Borrowed(_)));
+    assert!(matches!(borrowed[1], Cow::Owned(_)));
+    assert!(matches!(borrowed[2], Cow::Borrowed(_)));
+    assert_eq!(borrowed, names);
+}
+
+#[test]
+fn value_names_and_data_match_the_allocating_accessors() {
+    let mut hive = Hive::from_reader(Cursor::new(build_hive())).unwrap();
+    let key_node = hive.open_key("Values").unwrap();
+
+    assert_eq!(hive.value_names_borrowed(&key_node).unwrap(), ["", "Path", "Start", "Large"]);
+
+    let key_values: Vec<_> = hive.values(&key_node).map(|value| value.unwrap().1).collect();
+    for key_value in key_values {
+        let data = hive.value_data(&key_value).unwrap();
+        let borrowed = hive.value_data_borrowed(&key_value).unwrap();
+        assert_eq!(borrowed, data);
+        // Only data in a single cell of its own can be borrowed; resident and big data is copied
+        let single_cell = !key_value.is_resident() && data.len() <= 16344;
+        assert_eq!(matches!(borrowed, Cow::Borrowed(_)), single_cell, "{} bytes", data.len());
+    }
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveCapabilities};
+
+// Function to open an empty hive written with the given minor version
+fn capabilities_of(minor_version: u32) -> HiveCapabilities {
+    let hive_file = HiveBuilder::new().minor_version(minor_version).build(&Key::new("ROOT"));
+    let hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    assert_eq!(hive.version(), (1, minor_version));
+    hive.capabilities()
+}
+
+#[test]
+fn capabilities_follow_the_minor_version() {
+    let expected = [
+        (3, [true, false, false, false]),
+        (4, [true, true, false, false]),
+        (5, [true, true, true, false]),
+        (6, [true, true, true, true]),
+    ];
+    for (minor_version, [fast_leaves, big_data, hash_leaves, layered_keys]) in expected {
+        assert_eq!(
+            capabilities_of(minor_version),
+            HiveCapabilities {
+                fast_leaves,
+                big_data,
+                hash_leaves,
+                layered_keys,
+            },
+            "minor version {}",
+            minor_version
+        );
+    }
+}
+
+#[test]
+fn capabilities_match_from_minor_version() {
+    assert_eq!(capabilities_of(5), HiveCapabilities::from_minor_version(5));
+    assert!(!HiveCapabilities::from_minor_version(2).fast_leaves);
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Cell, CellHeader, Hive, HiveError};
+
+#[test]
+fn negative_sizes_are_allocated_and_positive_sizes_free() {
+    let allocated = CellHeader::from_bytes((-0x58i32).to_le_bytes());
+    assert!(allocated.is_allocated());
+    assert_eq!(allocated.size(), 0x58);
+    assert_eq!(allocated.data_size(), 0x54);
+
+    let free = CellHeader::from_bytes(0x58i32.to_le_bytes());
+    assert!(!free.is_allocated());
+    assert_eq!(free.size(), 0x58);
+    assert_eq!(free.data_size(), 0x54);
+}
+
+#[test]
+fn freed_key_cell_keeps_its_record_but_is_not_live() {
+    let root = Key::new("ROOT").subkey(Key::new("Deleted").sz("Name", "value"));
+    let mut data = HiveBuilder::new().build(&root);
+    let deleted_offset = {
+        let mut hive = Hive::from_bytes(&data).unwrap();
+        let deleted = hive.open_key("Deleted").unwrap();
+        let cell_header = hive.cell_header(deleted.offset).unwrap();
+        assert!(cell_header.is_allocated());
+        deleted.offset
+    };
+
+    // Free the key's cell the way Windows does on deletion: flip the size to positive
+    let position = 4096 + deleted_offset as usize;
+    let size = i32::from_le_bytes(data[position..position + 4].try_into().unwrap());
+    data[position..position + 4].copy_from_slice(&(-size).to_le_bytes());
+    let mut hive = Hive::from_bytes(&data).unwrap();
+
+    let cell_header = hive.cell_header(deleted_offset).unwrap();
+    assert!(!cell_header.is_allocated());
+    assert_eq!(cell_header.size(), (-size) as u32);
+    // The record is still there to be recovered, but lookups of live keys refuse it
+    assert!(matches!(hive.parse_cell(deleted_offset).unwrap(), Cell::KeyNode { name, .. } if name == "Deleted"));
+    assert!(matches!(
+        hive.open_key_node_at(deleted_offset),
+        Err(HiveError::UnexpectedCell { expected: "nk", .. })
+    ));
+    // And the free space statistics count it
+    let free_bytes = Hive::from_bytes(&HiveBuilder::new().build(&root)).unwrap().statistics().unwrap().free_bytes;
+    assert_eq!(hive.statistics().unwrap().free_bytes, free_bytes + (-size) as u64);
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::Hive;
+
+const CLASS: &str = "0123456789abcdef";
+
+fn build_hive() -> Vec<u8> {
+    HiveBuilder::new().build(&Key::new("ROOT").subkey(Key::new("JD").class(CLASS)))
+}
+
+#[test]
+fn sixteen_character_class_name_reads_in_full() {
+    let mut hive = Hive::from_reader(Cursor::new(build_hive())).unwrap();
+    let jd = hive.open_key("JD").unwrap();
+    assert_eq!(hive.class_name(&jd).unwrap().as_deref(), Some(CLASS));
+    assert_eq!(hive.read_class_name_bytes(&jd).unwrap().len(), 32);
+}
+
+#[test]
+fn class_name_longer_than_its_cell_is_an_error() {
+    let mut hive_file = build_hive();
+    let offset = Hive::from_bytes(&hive_file).unwrap().open_key("JD").unwrap().offset;
+    // The class name length sits 74 bytes into the nk record
+    let field = 4096 + offset as usize + 4 + 74;
+    hive_file[field..field + 2].copy_from_slice(&0x1000u16.to_le_bytes());
+
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let jd = hive.open_key("JD").unwrap();
+    assert!(hive.class_name(&jd).is_err());
+}
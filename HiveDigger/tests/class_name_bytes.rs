@@ -0,0 +1,44 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{
+    builder::{HiveBuilder, Key},
+    fixtures::fixture_path,
+};
+use hivedigger::Hive;
+
+#[test]
+fn boot_key_component_as_bytes_and_as_hex() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let jd = hive.open_key("ControlSet001\\Control\\Lsa\\JD").unwrap();
+
+    let bytes = hive.read_class_name_bytes(&jd).unwrap();
+    let expected: Vec<u8> = "4b3d8a1e".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    assert_eq!(bytes, expected);
+
+    let class_name = hive.class_name(&jd).unwrap().unwrap();
+    let decoded: Vec<u8> = (0..class_name.len())
+        .step_by(2)
+        .map(|position| u8::from_str_radix(&class_name[position..position + 2], 16).unwrap())
+        .collect();
+    assert_eq!(decoded, [0x4b, 0x3d, 0x8a, 0x1e]);
+}
+
+#[test]
+fn binary_class_data_is_returned_as_stored() {
+    // Odd-length data that isn't valid UTF-16 either
+    let class_bytes = [0x00, 0xd8, 0xff, 0x10, 0x7f];
+    let root = Key::new("ROOT")
+        .subkey(Key::new("Binary").class_bytes(&class_bytes))
+        .subkey(Key::new("NoClass"));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+
+    let binary = hive.open_key("Binary").unwrap();
+    assert_eq!(hive.read_class_name_bytes(&binary).unwrap(), class_bytes);
+    assert_eq!(hive.class_name(&binary).unwrap().unwrap(), "\u{fffd}\u{10ff}");
+
+    let no_class = hive.open_key("NoClass").unwrap();
+    assert!(hive.read_class_name_bytes(&no_class).unwrap().is_empty());
+    assert_eq!(hive.class_name(&no_class).unwrap(), None);
+}
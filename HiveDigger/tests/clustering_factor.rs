@@ -0,0 +1,73 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::fixture_path;
+use hivedigger::{log::TransactionLog, Hive, HiveError, HiveOptions};
+
+// Function to read the SYSTEM fixture with its base block's clustering factor replaced and the
+// checksum recomputed, so only the clustering factor differs
+fn system_with_clustering_factor(clustering_factor: u32) -> Vec<u8> {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    hive_file[44..48].copy_from_slice(&clustering_factor.to_le_bytes());
+    let checksum = hive_file[..508]
+        .chunks_exact(4)
+        .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+    hive_file[508..512].copy_from_slice(&checksum.to_le_bytes());
+    hive_file
+}
+
+#[test]
+fn plausible_clustering_factors_are_read() {
+    for clustering_factor in [1, 8, 64] {
+        let mut hive = Hive::from_reader(Cursor::new(system_with_clustering_factor(clustering_factor))).unwrap();
+        assert_eq!(hive.clustering_factor(), clustering_factor);
+        // Bins stay 4096-byte aligned whatever the sector size
+        assert!(hive.open_key("ControlSet001\\Control\\Lsa").is_ok());
+    }
+}
+
+#[test]
+fn absurd_clustering_factors_are_rejected() {
+    let recovery = HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    };
+    for clustering_factor in [0, 3, 128, u32::MAX] {
+        let hive_file = system_with_clustering_factor(clustering_factor);
+        assert!(matches!(
+            Hive::from_reader(Cursor::new(hive_file.clone())),
+            Err(HiveError::InvalidClusteringFactor(found)) if found == clustering_factor
+        ));
+        // Recovery is for damaged base blocks, not for ones that check out with a bad field
+        assert!(matches!(
+            Hive::from_reader_with_options(Cursor::new(hive_file), recovery.clone()),
+            Err(HiveError::InvalidClusteringFactor(found)) if found == clustering_factor
+        ));
+    }
+}
+
+#[test]
+fn log_entries_start_after_the_first_sector() {
+    // Clustering factor 8: 4096-byte sectors, so the first log entry is at 4096, not 512
+    let mut log = vec![0u8; 4096];
+    log[0..4].copy_from_slice(b"regf");
+    log[4..8].copy_from_slice(&2u32.to_le_bytes());
+    log[28..32].copy_from_slice(&6u32.to_le_bytes());
+    log[40..44].copy_from_slice(&4096u32.to_le_bytes());
+    log[44..48].copy_from_slice(&8u32.to_le_bytes());
+    let mut entry = b"HvLE".to_vec();
+    entry.extend(512u32.to_le_bytes());
+    entry.extend(0u32.to_le_bytes());
+    entry.extend(2u32.to_le_bytes());
+    entry.resize(512, 0);
+    log.extend(entry);
+    let log_path = std::env::temp_dir().join(format!("hivedigger-sectors-{}.LOG1", std::process::id()));
+    std::fs::write(&log_path, &log).unwrap();
+
+    let mut transaction_log = TransactionLog::open(&log_path).unwrap();
+    let entries = transaction_log.entries().collect::<Result<Vec<_>, _>>().unwrap();
+    std::fs::remove_file(&log_path).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].file_offset, 4096);
+}
@@ -0,0 +1,415 @@
+// Minimal hive writer for test fixtures: lays out a tree of keys and values as nk, vk, subkey
+// list, value list, class name and big data cells in a single hive bin, behind a base block
+// with a valid checksum. It writes only what the reader needs and makes no attempt to match
+// how Windows lays hives out.
+
+pub const REG_SZ: u32 = 1;
+pub const REG_EXPAND_SZ: u32 = 2;
+pub const REG_BINARY: u32 = 3;
+pub const REG_DWORD: u32 = 4;
+pub const REG_DWORD_BIG_ENDIAN: u32 = 5;
+pub const REG_MULTI_SZ: u32 = 7;
+pub const REG_QWORD: u32 = 11;
+
+// Data longer than this is split into big data segments
+const BIG_DATA_SEGMENT_SIZE: usize = 16344;
+
+// Last-written time given to every key unless set otherwise: 2019-04-17 18:40:00 UTC
+const DEFAULT_TIMESTAMP: u64 = 0x01d4f54cf65a0000;
+
+// Enum for the kind of subkey list written for a key's subkeys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    Li,
+    Lf,
+    Lh,
+    // An index root over hash leaves of two subkeys each
+    Ri,
+    // An index root over index roots of two such hash leaves each
+    NestedRi,
+    // No list cell: the subkey list offset is the only subkey's key node offset
+    Direct,
+}
+
+// Struct representing a value to write
+#[derive(Debug, Clone)]
+pub struct Value {
+    pub name: String,
+    pub data_type: u32,
+    pub data: Vec<u8>,
+}
+
+// Struct representing a key to write, with its values and subkeys
+#[derive(Debug, Clone)]
+pub struct Key {
+    pub name: String,
+    pub class: Option<Vec<u8>>, // Stored bytes of the class name
+    pub values: Vec<Value>,
+    pub subkeys: Vec<Key>,
+    pub list_kind: ListKind,
+    pub timestamp: u64,
+    // Index of the security descriptor (see `HiveBuilder::security_descriptor`) the key uses
+    pub security: Option<usize>,
+}
+
+impl Key {
+    pub fn new(name: &str) -> Key {
+        Key {
+            name: name.to_string(),
+            class: None,
+            values: Vec::new(),
+            subkeys: Vec::new(),
+            list_kind: ListKind::Lh,
+            timestamp: DEFAULT_TIMESTAMP,
+            security: None,
+        }
+    }
+
+    pub fn subkey(mut self, subkey: Key) -> Key {
+        self.subkeys.push(subkey);
+        self
+    }
+
+    pub fn class(self, class: &str) -> Key {
+        self.class_bytes(&class.encode_utf16().flat_map(u16::to_le_bytes).collect::<Vec<u8>>())
+    }
+
+    // Class data that isn't UTF-16 text, stored as given
+    pub fn class_bytes(mut self, class_bytes: &[u8]) -> Key {
+        self.class = Some(class_bytes.to_vec());
+        self
+    }
+
+    pub fn list(mut self, list_kind: ListKind) -> Key {
+        self.list_kind = list_kind;
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: u64) -> Key {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn security(mut self, index: usize) -> Key {
+        self.security = Some(index);
+        self
+    }
+
+    pub fn value(mut self, name: &str, data_type: u32, data: Vec<u8>) -> Key {
+        self.values.push(Value {
+            name: name.to_string(),
+            data_type,
+            data,
+        });
+        self
+    }
+
+    pub fn sz(self, name: &str, string: &str) -> Key {
+        self.value(name, REG_SZ, utf16z(string))
+    }
+
+    pub fn expand_sz(self, name: &str, string: &str) -> Key {
+        self.value(name, REG_EXPAND_SZ, utf16z(string))
+    }
+
+    pub fn multi_sz(self, name: &str, strings: &[&str]) -> Key {
+        let mut data: Vec<u8> = strings.iter().flat_map(|string| utf16z(string)).collect();
+        data.extend([0, 0]);
+        self.value(name, REG_MULTI_SZ, data)
+    }
+
+    pub fn dword(self, name: &str, dword: u32) -> Key {
+        self.value(name, REG_DWORD, dword.to_le_bytes().to_vec())
+    }
+
+    pub fn qword(self, name: &str, qword: u64) -> Key {
+        self.value(name, REG_QWORD, qword.to_le_bytes().to_vec())
+    }
+
+    pub fn binary(self, name: &str, data: &[u8]) -> Key {
+        self.value(name, REG_BINARY, data.to_vec())
+    }
+}
+
+// Function to encode a string as NUL-terminated UTF-16LE
+pub fn utf16z(string: &str) -> Vec<u8> {
+    let mut data: Vec<u8> = string.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    data.extend([0, 0]);
+    data
+}
+
+// Struct representing a hive being written: the hive bin so far and base block fields
+pub struct HiveBuilder {
+    bin: Vec<u8>,
+    file_name: String,
+    minor_version: u32,
+    security_descriptors: Vec<Vec<u8>>,
+    security_offsets: Vec<u32>,
+}
+
+impl Default for HiveBuilder {
+    fn default() -> HiveBuilder {
+        HiveBuilder::new()
+    }
+}
+
+impl HiveBuilder {
+    pub fn new() -> HiveBuilder {
+        HiveBuilder {
+            // Room for the hive bin header, filled in by `build`
+            bin: vec![0u8; 32],
+            file_name: String::new(),
+            minor_version: 5,
+            security_descriptors: Vec::new(),
+            security_offsets: Vec::new(),
+        }
+    }
+
+    // Function to set the file name embedded in the base block
+    pub fn file_name(mut self, file_name: &str) -> HiveBuilder {
+        self.file_name = file_name.to_string();
+        self
+    }
+
+    pub fn minor_version(mut self, minor_version: u32) -> HiveBuilder {
+        self.minor_version = minor_version;
+        self
+    }
+
+    // Function to add a security descriptor for keys to refer to by index with `Key::security`.
+    // The "sk" records are written in order as one circular list.
+    pub fn security_descriptor(mut self, descriptor: &[u8]) -> HiveBuilder {
+        self.security_descriptors.push(descriptor.to_vec());
+        self
+    }
+
+    // Function to write a key tree and return the whole hive file
+    pub fn build(mut self, root: &Key) -> Vec<u8> {
+        self.add_security_descriptors(root);
+        let root_offset = self.add_key(root, None);
+        self.finish(root_offset)
+    }
+
+    // Function to write the "sk" records, counting the keys that refer to each, and link them
+    fn add_security_descriptors(&mut self, root: &Key) {
+        fn count_references(key: &Key, counts: &mut [u32]) {
+            if let Some(index) = key.security {
+                counts[index] += 1;
+            }
+            for subkey in &key.subkeys {
+                count_references(subkey, counts);
+            }
+        }
+        let mut counts = vec![0u32; self.security_descriptors.len()];
+        count_references(root, &mut counts);
+
+        for (descriptor, reference_count) in self.security_descriptors.clone().iter().zip(counts) {
+            let mut sk = b"sk".to_vec();
+            sk.extend([0u8; 10]);
+            sk.extend(reference_count.to_le_bytes());
+            sk.extend((descriptor.len() as u32).to_le_bytes());
+            sk.extend(descriptor);
+            let offset = self.alloc(&sk);
+            self.security_offsets.push(offset);
+        }
+        let offsets = self.security_offsets.clone();
+        for (position, &offset) in offsets.iter().enumerate() {
+            let flink = offsets[(position + 1) % offsets.len()];
+            let blink = offsets[(position + offsets.len() - 1) % offsets.len()];
+            self.patch(offset, 4, &flink.to_le_bytes());
+            self.patch(offset, 8, &blink.to_le_bytes());
+        }
+    }
+
+    // Function to append an allocated cell holding `payload`, returning its offset
+    pub fn alloc(&mut self, payload: &[u8]) -> u32 {
+        let size = (payload.len() + 4).div_ceil(8) * 8;
+        let offset = self.bin.len() as u32;
+        self.bin.extend((-(size as i32)).to_le_bytes());
+        self.bin.extend(payload);
+        self.bin.resize(offset as usize + size, 0);
+        offset
+    }
+
+    // Function to overwrite bytes inside the data of the cell at `offset`
+    fn patch(&mut self, offset: u32, position: usize, bytes: &[u8]) {
+        let start = offset as usize + 4 + position;
+        self.bin[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+
+    fn add_key(&mut self, key: &Key, parent: Option<u32>) -> u32 {
+        let name = key.name.as_bytes();
+        let mut nk = vec![0u8; 76];
+        nk[0..2].copy_from_slice(b"nk");
+        // COMP_NAME, plus HIVE_ENTRY and NO_DELETE on the root
+        let flags: u16 = if parent.is_none() { 0x2c } else { 0x20 };
+        nk[2..4].copy_from_slice(&flags.to_le_bytes());
+        nk[4..12].copy_from_slice(&key.timestamp.to_le_bytes());
+        nk[16..20].copy_from_slice(&parent.unwrap_or(0).to_le_bytes());
+        nk[20..24].copy_from_slice(&(key.subkeys.len() as u32).to_le_bytes());
+        nk[36..40].copy_from_slice(&(key.values.len() as u32).to_le_bytes());
+        // Subkey lists, value list, security and class name start out unused
+        for position in [28, 32, 40, 44, 48] {
+            nk[position..position + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        }
+        nk[72..74].copy_from_slice(&(name.len() as u16).to_le_bytes());
+        nk.extend(name);
+        let offset = self.alloc(&nk);
+
+        if let Some(index) = key.security {
+            let security_offset = self.security_offsets[index];
+            self.patch(offset, 44, &security_offset.to_le_bytes());
+        }
+
+        if let Some(class_bytes) = &key.class {
+            let class_offset = self.alloc(class_bytes);
+            self.patch(offset, 48, &class_offset.to_le_bytes());
+            self.patch(offset, 74, &(class_bytes.len() as u16).to_le_bytes());
+        }
+
+        let subkeys: Vec<(u32, &Key)> = key
+            .subkeys
+            .iter()
+            .map(|subkey| (self.add_key(subkey, Some(offset)), subkey))
+            .collect();
+        if !subkeys.is_empty() {
+            let list_offset = self.add_subkey_list(&subkeys, key.list_kind);
+            self.patch(offset, 28, &list_offset.to_le_bytes());
+        }
+
+        if !key.values.is_empty() {
+            let value_offsets: Vec<u8> = key
+                .values
+                .iter()
+                .flat_map(|value| self.add_value(value).to_le_bytes())
+                .collect();
+            let list_offset = self.alloc(&value_offsets);
+            self.patch(offset, 40, &list_offset.to_le_bytes());
+        }
+        offset
+    }
+
+    fn add_subkey_list(&mut self, subkeys: &[(u32, &Key)], list_kind: ListKind) -> u32 {
+        if list_kind == ListKind::Direct {
+            assert_eq!(subkeys.len(), 1, "a direct subkey list holds a single subkey");
+            return subkeys[0].0;
+        }
+        let list_offsets: Vec<u32> = match list_kind {
+            ListKind::Ri => subkeys.chunks(2).map(|chunk| self.add_leaf(chunk, ListKind::Lh)).collect(),
+            ListKind::NestedRi => subkeys.chunks(4).map(|chunk| self.add_subkey_list(chunk, ListKind::Ri)).collect(),
+            _ => return self.add_leaf(subkeys, list_kind),
+        };
+        let mut index_root = b"ri".to_vec();
+        index_root.extend((list_offsets.len() as u16).to_le_bytes());
+        for list_offset in list_offsets {
+            index_root.extend(list_offset.to_le_bytes());
+        }
+        self.alloc(&index_root)
+    }
+
+    fn add_leaf(&mut self, subkeys: &[(u32, &Key)], list_kind: ListKind) -> u32 {
+        let mut leaf = match list_kind {
+            ListKind::Li => b"li".to_vec(),
+            ListKind::Lf => b"lf".to_vec(),
+            _ => b"lh".to_vec(),
+        };
+        leaf.extend((subkeys.len() as u16).to_le_bytes());
+        for (offset, subkey) in subkeys {
+            leaf.extend(offset.to_le_bytes());
+            match list_kind {
+                ListKind::Li => {}
+                // Fast leaves hint with the first four characters of the name
+                ListKind::Lf => {
+                    let mut hint = [0u8; 4];
+                    for (position, byte) in subkey.name.bytes().take(4).enumerate() {
+                        hint[position] = byte;
+                    }
+                    leaf.extend(hint);
+                }
+                // Hash leaves use hash = hash * 37 + character over the uppercased name
+                _ => {
+                    let hash = subkey
+                        .name
+                        .to_uppercase()
+                        .bytes()
+                        .fold(0u32, |hash, byte| hash.wrapping_mul(37).wrapping_add(byte as u32));
+                    leaf.extend(hash.to_le_bytes());
+                }
+            }
+        }
+        self.alloc(&leaf)
+    }
+
+    fn add_value(&mut self, value: &Value) -> u32 {
+        let name = value.name.as_bytes();
+        let mut vk = vec![0u8; 20];
+        vk[0..2].copy_from_slice(b"vk");
+        vk[2..4].copy_from_slice(&(name.len() as u16).to_le_bytes());
+        vk[12..16].copy_from_slice(&value.data_type.to_le_bytes());
+        // The name is stored as ASCII; the default value has none
+        let flags: u16 = if name.is_empty() { 0 } else { 1 };
+        vk[16..18].copy_from_slice(&flags.to_le_bytes());
+
+        let data_length = value.data.len() as u32;
+        if value.data.len() <= 4 {
+            // Resident data is held in the data offset field
+            vk[4..8].copy_from_slice(&(data_length | 0x80000000).to_le_bytes());
+            vk[8..8 + value.data.len()].copy_from_slice(&value.data);
+        } else if value.data.len() <= BIG_DATA_SEGMENT_SIZE {
+            let data_offset = self.alloc(&value.data);
+            vk[4..8].copy_from_slice(&data_length.to_le_bytes());
+            vk[8..12].copy_from_slice(&data_offset.to_le_bytes());
+        } else {
+            let segment_offsets: Vec<u8> = value
+                .data
+                .chunks(BIG_DATA_SEGMENT_SIZE)
+                .flat_map(|segment| self.alloc(segment).to_le_bytes())
+                .collect();
+            let segment_count = (segment_offsets.len() / 4) as u16;
+            let segment_list_offset = self.alloc(&segment_offsets);
+            let mut big_data = b"db".to_vec();
+            big_data.extend(segment_count.to_le_bytes());
+            big_data.extend(segment_list_offset.to_le_bytes());
+            let big_data_offset = self.alloc(&big_data);
+            vk[4..8].copy_from_slice(&data_length.to_le_bytes());
+            vk[8..12].copy_from_slice(&big_data_offset.to_le_bytes());
+        }
+        vk.extend(name);
+        self.alloc(&vk)
+    }
+
+    // Function to close the hive bin with a free cell and put the base block in front of it
+    fn finish(mut self, root_offset: u32) -> Vec<u8> {
+        let used = self.bin.len();
+        let bin_size = used.div_ceil(4096) * 4096;
+        if bin_size > used {
+            self.bin.extend(((bin_size - used) as i32).to_le_bytes());
+            self.bin.resize(bin_size, 0);
+        }
+        self.bin[0..4].copy_from_slice(b"hbin");
+        self.bin[8..12].copy_from_slice(&(bin_size as u32).to_le_bytes());
+
+        let mut base_block = vec![0u8; 4096];
+        base_block[0..4].copy_from_slice(b"regf");
+        // Primary and secondary sequence numbers match: the hive is clean
+        base_block[4..8].copy_from_slice(&1u32.to_le_bytes());
+        base_block[8..12].copy_from_slice(&1u32.to_le_bytes());
+        base_block[12..20].copy_from_slice(&DEFAULT_TIMESTAMP.to_le_bytes());
+        base_block[20..24].copy_from_slice(&1u32.to_le_bytes());
+        base_block[24..28].copy_from_slice(&self.minor_version.to_le_bytes());
+        // Primary file, direct memory load
+        base_block[32..36].copy_from_slice(&1u32.to_le_bytes());
+        base_block[36..40].copy_from_slice(&root_offset.to_le_bytes());
+        base_block[40..44].copy_from_slice(&(bin_size as u32).to_le_bytes());
+        base_block[44..48].copy_from_slice(&1u32.to_le_bytes());
+        let file_name: Vec<u8> = self.file_name.encode_utf16().flat_map(u16::to_le_bytes).take(64).collect();
+        base_block[48..48 + file_name.len()].copy_from_slice(&file_name);
+        let checksum = base_block[..508]
+            .chunks_exact(4)
+            .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+        base_block[508..512].copy_from_slice(&checksum.to_le_bytes());
+
+        base_block.extend(self.bin);
+        base_block
+    }
+}
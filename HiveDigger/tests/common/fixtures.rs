@@ -0,0 +1,364 @@
+// Definitions of the fixture hives checked in under tests/fixtures/hives. They are small, made
+// up hives shaped like the real ones, holding the keys and values the library has helpers for.
+// After changing one, regenerate the files as described in tests/fixtures/README.md.
+
+use std::path::{Path, PathBuf};
+
+use super::builder::{utf16z, HiveBuilder, Key, ListKind, REG_BINARY, REG_DWORD_BIG_ENDIAN};
+
+// Struct representing one fixture hive: its file name and how to build it
+pub struct Fixture {
+    pub name: &'static str,
+    pub build: fn() -> Vec<u8>,
+}
+
+pub const FIXTURES: &[Fixture] = &[
+    Fixture { name: "SYSTEM", build: system },
+    Fixture { name: "SOFTWARE", build: software },
+    Fixture { name: "SAM", build: sam },
+    Fixture { name: "NTUSER.DAT", build: ntuser },
+    Fixture { name: "Amcache.hve", build: amcache },
+    Fixture { name: "UsrClass.dat", build: usrclass },
+];
+
+// Function to get the directory holding the fixture hives and goldens
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+}
+
+// Function to get the path of a checked-in fixture hive, e.g. `fixture_path("SYSTEM")`
+pub fn fixture_path(name: &str) -> PathBuf {
+    fixtures_dir().join("hives").join(name)
+}
+
+// USB mass storage device interface path recorded in MountedDevices for a removable drive
+pub const USB_DEVICE_PATH: &str =
+    "_??_USBSTOR#Disk&Ven_SanDisk&Prod_Cruzer_Blade&Rev_1.00#4C530001230101112094&0#{53f56307-b6bf-11d0-94f2-00a0c91efb8b}";
+
+// SYSTEM: two control sets selected through Select, the boot key class names under Lsa,
+// services listed through an index root, and MountedDevices entries for an MBR partition, a
+// GPT partition and a USB drive. Setup holds one value of each layout and odd type, including
+// big data.
+pub fn system() -> Vec<u8> {
+    let lsa = Key::new("Lsa")
+        .subkey(Key::new("JD").class("4b3d8a1e"))
+        .subkey(Key::new("Skew1").class("2c9f7e10"))
+        .subkey(Key::new("GBG").class("aa55aa55"))
+        .subkey(Key::new("Data").class("0123abcd"))
+        .dword("LimitBlankPasswordUse", 1)
+        .multi_sz("Authentication Packages", &["msv1_0"]);
+    let control = Key::new("Control")
+        .subkey(Key::new("ComputerName").subkey(Key::new("ComputerName").sz("ComputerName", "WORKSTATION-01")))
+        .subkey(lsa)
+        .subkey(
+            Key::new("Session Manager").subkey(
+                Key::new("Environment")
+                    .expand_sz("Path", "%SystemRoot%\\system32;%SystemRoot%")
+                    .sz("windir", "%SystemRoot%"),
+            ),
+        )
+        .subkey(Key::new("TimeZoneInformation").sz("TimeZoneKeyName", "UTC").dword("ActiveTimeBias", 0));
+    let services = Key::new("Services")
+        .list(ListKind::Ri)
+        .subkey(Key::new("EventLog").dword("Start", 2).subkey(Key::new("Parameters")))
+        .subkey(
+            Key::new("Tcpip")
+                .expand_sz("ImagePath", "System32\\drivers\\tcpip.sys")
+                .dword("Start", 0),
+        )
+        .subkey(
+            Key::new("TermService")
+                .expand_sz("ImagePath", "%SystemRoot%\\System32\\svchost.exe -k NetworkService")
+                .dword("Start", 3),
+        )
+        .subkey(Key::new("W32Time").dword("Start", 3));
+    let control_set_001 = Key::new("ControlSet001").subkey(control).subkey(services);
+    let control_set_002 = Key::new("ControlSet002")
+        .subkey(Key::new("Services").subkey(Key::new("W32Time").dword("Start", 4)))
+        .timestamp(0x01d4f0000000000);
+    let select = Key::new("Select")
+        .dword("Current", 1)
+        .dword("Default", 1)
+        .dword("Failed", 0)
+        .dword("LastKnownGood", 2);
+    let mut mbr_partition = 0x9a3c1f5eu32.to_le_bytes().to_vec();
+    mbr_partition.extend(0x100000u64.to_le_bytes());
+    let mut gpt_partition = b"DMIO:ID:".to_vec();
+    gpt_partition.extend([
+        0x5b, 0x3a, 0x8e, 0x1c, 0x4d, 0x2f, 0x6a, 0x4b, 0x9c, 0x11, 0x02, 0x45, 0x7e, 0x6d, 0x3f, 0xa0,
+    ]);
+    let usb_device: Vec<u8> = USB_DEVICE_PATH.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mounted_devices = Key::new("MountedDevices")
+        .binary("\\DosDevices\\C:", &mbr_partition)
+        .binary("\\DosDevices\\D:", &gpt_partition)
+        .binary("\\DosDevices\\E:", &usb_device)
+        .binary("\\??\\Volume{6f1c4b2e-93a5-11ee-b9d1-0242ac120002}", &mbr_partition)
+        .binary("\\??\\Volume{6f1c4b2f-93a5-11ee-b9d1-0242ac120002}", &usb_device);
+    let big_data: Vec<u8> = (0..20000u32).map(|position| (position % 251) as u8).collect();
+    let setup = Key::new("Setup")
+        .sz("", "default")
+        .value("BigData", REG_BINARY, big_data)
+        .value("BigEndian", REG_DWORD_BIG_ENDIAN, vec![0x12, 0x34, 0x56, 0x78])
+        .multi_sz("Multi", &["a", "bc"])
+        .qword("Qword", 0x01d70000_12345678)
+        .value("Short", REG_BINARY, vec![0xde, 0xad]);
+    let root = Key::new("ROOT")
+        .list(ListKind::Lf)
+        .subkey(control_set_001)
+        .subkey(control_set_002)
+        .subkey(mounted_devices)
+        .subkey(select)
+        .subkey(setup);
+    HiveBuilder::new().file_name("SYSTEM").build(&root)
+}
+
+// Function to encode a SYSTEMTIME as NetworkList stores it: year, month, day of week, day, hour,
+// minute, second and milliseconds
+fn systemtime(fields: [u16; 8]) -> Vec<u8> {
+    fields.iter().flat_map(|field| field.to_le_bytes()).collect()
+}
+
+// SOFTWARE: the CurrentVersion values behind `os_info`, two profiles in ProfileList, Winlogon,
+// a Run entry and NetworkList profiles for a home wireless network and a domain wired one
+pub fn software() -> Vec<u8> {
+    let network_list = Key::new("NetworkList").subkey(
+        Key::new("Profiles")
+            .subkey(
+                Key::new("{3E1F6C2A-8B4D-4F7E-9A10-5C2D7B8E9F01}")
+                    .sz("ProfileName", "HomeWiFi")
+                    .sz("Description", "HomeWiFi")
+                    .dword("NameType", 0x47)
+                    .dword("Managed", 0)
+                    .dword("Category", 1)
+                    .binary("DateCreated", &systemtime([2023, 3, 5, 12, 18, 45, 10, 250]))
+                    .binary("DateLastConnected", &systemtime([2024, 2, 4, 29, 8, 5, 0, 0])),
+            )
+            .subkey(
+                Key::new("{9C0B7D4E-2F1A-4B6C-8D3E-0A5F6B7C8D92}")
+                    .sz("ProfileName", "corp.example.com")
+                    .sz("Description", "corp.example.com")
+                    .dword("NameType", 0x06)
+                    .dword("Managed", 1)
+                    .dword("Category", 2)
+                    .binary("DateCreated", &systemtime([2019, 4, 3, 17, 9, 30, 0, 0]))
+                    .binary("DateLastConnected", &[0u8; 16]),
+            ),
+    );
+    let profile_list = Key::new("ProfileList")
+        .subkey(Key::new("S-1-5-18").expand_sz("ProfileImagePath", "%systemroot%\\system32\\config\\systemprofile"))
+        .subkey(
+            Key::new("S-1-5-21-1004336348-1177238915-682003330-1001")
+                .expand_sz("ProfileImagePath", "C:\\Users\\analyst")
+                .dword("State", 0),
+        );
+    let current_version = Key::new("CurrentVersion")
+        .sz("CurrentBuild", "19045")
+        .sz("DisplayVersion", "22H2")
+        .sz("EditionID", "Professional")
+        .dword("InstallDate", 1555526400)
+        .qword("InstallTime", 132000000000000000)
+        .sz("ProductName", "Windows 10 Pro")
+        .sz("RegisteredOwner", "analyst")
+        .subkey(network_list)
+        .subkey(profile_list)
+        .subkey(
+            Key::new("Winlogon")
+                .sz("AutoAdminLogon", "0")
+                .sz("DefaultUserName", "analyst")
+                .sz("Shell", "explorer.exe"),
+        );
+    let microsoft = Key::new("Microsoft")
+        .subkey(Key::new("Windows NT").subkey(current_version))
+        .subkey(Key::new("Windows").subkey(
+            Key::new("CurrentVersion").subkey(
+                Key::new("Run").sz("SecurityHealth", "%windir%\\system32\\SecurityHealthSystray.exe"),
+            ),
+        ));
+    let root = Key::new("ROOT")
+        .subkey(Key::new("Classes").subkey(Key::new(".txt").sz("", "txtfile")))
+        .subkey(microsoft);
+    HiveBuilder::new().file_name("SOFTWARE").build(&root)
+}
+
+// SAM: two accounts with F and V records, and the Names keys that store each account's RID as
+// the type of their default value
+pub fn sam() -> Vec<u8> {
+    let account_f = |rid: u32, logons: u16| {
+        let mut f = vec![0u8; 0x50];
+        f[0] = 3;
+        f[0x08..0x10].copy_from_slice(&132000000000000000u64.to_le_bytes());
+        f[0x30..0x34].copy_from_slice(&rid.to_le_bytes());
+        f[0x38..0x3a].copy_from_slice(&0x0210u16.to_le_bytes());
+        f[0x42..0x44].copy_from_slice(&logons.to_le_bytes());
+        f
+    };
+    let users = Key::new("Users")
+        .subkey(Key::new("000001F4").binary("F", &account_f(0x1f4, 7)).binary("V", &[0u8; 0xcc]))
+        .subkey(Key::new("000001F5").binary("F", &account_f(0x1f5, 0)).binary("V", &[0u8; 0xcc]))
+        .subkey(
+            Key::new("Names")
+                .subkey(Key::new("Administrator").value("", 0x1f4, Vec::new()))
+                .subkey(Key::new("Guest").value("", 0x1f5, Vec::new())),
+        );
+    let account = Key::new("Account").binary("F", &[0x02, 0x00, 0x01, 0x00, 0, 0, 0, 0, 0x10, 0x27]).subkey(users);
+    let root = Key::new("ROOT").subkey(Key::new("SAM").subkey(Key::new("Domains").subkey(account)));
+    HiveBuilder::new().file_name("SAM").build(&root)
+}
+
+// NTUSER.DAT: the Explorer MRU keys behind the `ntuser` helpers and a user environment
+pub fn ntuser() -> Vec<u8> {
+    let recent_doc = |name: &str| {
+        let mut data = utf16z(name);
+        data.extend(b"shell item");
+        data
+    };
+    let mut mru_list_ex = Vec::new();
+    for position in [1u32, 0, u32::MAX] {
+        mru_list_ex.extend(position.to_le_bytes());
+    }
+    let explorer = Key::new("Explorer")
+        .subkey(
+            Key::new("RecentDocs")
+                .binary("0", &recent_doc("report.docx"))
+                .binary("1", &recent_doc("notes.txt"))
+                .binary("MRUListEx", &mru_list_ex),
+        )
+        .subkey(
+            Key::new("RunMRU")
+                .sz("MRUList", "ba")
+                .sz("a", "cmd\\1")
+                .sz("b", "notepad C:\\Users\\analyst\\notes.txt\\1"),
+        )
+        .subkey(
+            Key::new("TypedPaths")
+                .sz("url1", "C:\\Users\\analyst\\Documents")
+                .sz("url2", "\\\\fileserver\\share"),
+        );
+    let software = Key::new("Software").subkey(
+        Key::new("Microsoft")
+            .subkey(Key::new("Windows").subkey(Key::new("CurrentVersion").subkey(explorer))),
+    );
+    let root = Key::new("ROOT")
+        .subkey(Key::new("Environment").expand_sz("TEMP", "%USERPROFILE%\\AppData\\Local\\Temp"))
+        .subkey(software);
+    HiveBuilder::new().file_name("\\??\\C:\\Users\\analyst\\ntuser.dat").build(&root)
+}
+
+// Amcache.hve: InventoryApplicationFile entries for a signed system binary and for an unsigned
+// download with no link date or publisher, next to an InventoryApplication program entry
+pub fn amcache() -> Vec<u8> {
+    let notepad = Key::new("notepad.exe|8f1e3c2d5a6b7c9e")
+        .sz("LowerCaseLongPath", "c:\\windows\\system32\\notepad.exe")
+        .sz("FileId", "0000f1c4d5e8a3b6c9d2e7f0a1b4c7d0e3f6a9b2c5d8")
+        .sz("LinkDate", "04/17/2019 18:40:00")
+        .sz("Publisher", "microsoft corporation")
+        .sz("Name", "notepad.exe")
+        .qword("Size", 201216);
+    let dropper = Key::new("dropper.exe|1a2b3c4d5e6f7a8b")
+        .sz("LowerCaseLongPath", "c:\\users\\analyst\\downloads\\dropper.exe")
+        .sz("FileId", "00005e884898da28047151d0e56f8dc6292773603d0d")
+        .sz("LinkDate", "")
+        .sz("Name", "dropper.exe")
+        .qword("Size", 73728)
+        .timestamp(0x01d9a0fc31180200);
+    let root = Key::new("{11517B7C-E79D-4e20-961B-75A811715ADD}").subkey(
+        Key::new("Root")
+            .subkey(Key::new("InventoryApplication").subkey(Key::new("0000a1b2c3d4e5f6").sz("Name", "Notepad")))
+            .subkey(Key::new("InventoryApplicationFile").subkey(notepad).subkey(dropper)),
+    );
+    HiveBuilder::new().file_name("\\??\\C:\\Windows\\AppCompat\\Programs\\Amcache.hve").build(&root)
+}
+
+// CLSID of My Computer as stored in a root folder shell item
+const MY_COMPUTER_CLSID: [u8; 16] = [
+    0xe0, 0x4f, 0xd0, 0x20, 0xea, 0x3a, 0x69, 0x10, 0xa2, 0xd8, 0x08, 0x00, 0x2b, 0x30, 0x30, 0x9d,
+];
+
+// Function to end a shell item ID list after one item, as BagMRU values store them
+fn shell_item_list(mut item: Vec<u8>) -> Vec<u8> {
+    let size = item.len() as u16 + 2;
+    item.splice(0..0, size.to_le_bytes());
+    item.extend([0, 0]);
+    item
+}
+
+// Function to build a directory shell item whose BEEF0004 extension block (version 9) holds the
+// long name. Dates and times are DOS ones: (date, time).
+fn directory_item(short_name: &str, long_name: &str, modified: (u16, u16), created: (u16, u16)) -> Vec<u8> {
+    let mut item = vec![0x31, 0x00];
+    item.extend(0u32.to_le_bytes());
+    item.extend(modified.0.to_le_bytes());
+    item.extend(modified.1.to_le_bytes());
+    item.extend(0x0010u16.to_le_bytes());
+    item.extend(short_name.as_bytes());
+    item.push(0);
+    if item.len() % 2 == 1 {
+        item.push(0);
+    }
+    let extension_offset = item.len() as u16 + 2;
+    let mut extension = Vec::new();
+    extension.extend(9u16.to_le_bytes());
+    extension.extend(0xBEEF0004u32.to_le_bytes());
+    for field in [created.0, created.1, created.0, created.1, 0x002e] {
+        extension.extend(field.to_le_bytes());
+    }
+    extension.extend([0u8; 18]);
+    extension.extend(0u16.to_le_bytes());
+    extension.extend([0u8; 8]);
+    extension.extend(utf16z(long_name));
+    extension.extend(extension_offset.to_le_bytes());
+    item.extend((extension.len() as u16 + 2).to_le_bytes());
+    item.extend(extension);
+    shell_item_list(item)
+}
+
+// UsrClass.dat: a BagMRU tree for My Computer > C:\ > Users > analyst, with a folder that's
+// missing from its key's MRUListEx and a control panel item this crate doesn't decode
+pub fn usrclass() -> Vec<u8> {
+    let mru_list_ex = |numbers: &[u32]| {
+        let mut data: Vec<u8> = numbers.iter().flat_map(|number| number.to_le_bytes()).collect();
+        data.extend(u32::MAX.to_le_bytes());
+        data
+    };
+    let mut my_computer = vec![0x1f, 0x50];
+    my_computer.extend(MY_COMPUTER_CLSID);
+    let mut volume = vec![0x2f];
+    volume.extend(b"C:\\");
+    volume.resize(0x17, 0);
+    let control_panel = vec![0x71, 0x80, 0x00, 0x00, 0x00, 0x00, 0xde, 0xad, 0xbe, 0xef];
+
+    let analyst = Key::new("0").dword("NodeSlot", 4).binary("MRUListEx", &mru_list_ex(&[])).timestamp(0x01d9b1a0_00000000);
+    let users = Key::new("0")
+        .binary("0", &directory_item("analyst", "analyst", (0x56e1, 0x6000), (0x4e91, 0x5000)))
+        .binary("1", &directory_item("PROGRA~1", "Program Files", (0x4e91, 0x4800), (0x4e91, 0x4800)))
+        .binary("MRUListEx", &mru_list_ex(&[0]))
+        .dword("NodeSlot", 3)
+        .subkey(analyst)
+        .timestamp(0x01d9b19f_00000000);
+    let drive = Key::new("0")
+        .binary("0", &directory_item("Users", "Users", (0x56e1, 0x5c00), (0x4e91, 0x4800)))
+        .binary("MRUListEx", &mru_list_ex(&[0]))
+        .dword("NodeSlot", 2)
+        .subkey(users)
+        .timestamp(0x01d9b19e_00000000);
+    let computer = Key::new("0")
+        .binary("0", &shell_item_list(volume))
+        .binary("MRUListEx", &mru_list_ex(&[0]))
+        .dword("NodeSlot", 1)
+        .subkey(drive)
+        .timestamp(0x01d9b19d_00000000);
+    let bag_mru = Key::new("BagMRU")
+        .binary("0", &shell_item_list(my_computer))
+        .binary("1", &shell_item_list(control_panel))
+        .binary("MRUListEx", &mru_list_ex(&[1, 0]))
+        .subkey(computer);
+    let bags = Key::new("Bags").subkey(Key::new("1").subkey(Key::new("Shell").dword("Mode", 4)));
+    let shell = Key::new("Shell").subkey(bag_mru).subkey(bags);
+    let root = Key::new("S-1-5-21-1004336348-1177238915-682003330-1001_Classes").subkey(
+        Key::new("Local Settings").subkey(
+            Key::new("Software").subkey(Key::new("Microsoft").subkey(Key::new("Windows").subkey(shell))),
+        ),
+    );
+    // Windows keeps the last 31 characters of a path this long
+    HiveBuilder::new().file_name("\\Microsoft\\Windows\\UsrClass.dat").build(&root)
+}
@@ -0,0 +1,6 @@
+// Shared test support: a hive writer and the fixture hives built with it. Each test crate uses
+// only part of this, hence the dead code allowance.
+#![allow(dead_code)]
+
+pub mod builder;
+pub mod fixtures;
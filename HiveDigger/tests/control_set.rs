@@ -0,0 +1,35 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveError};
+
+#[test]
+fn current_and_last_known_good_follow_the_select_key() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+
+    // Select\Current is 1 and Select\LastKnownGood is 2
+    let current = hive.current_control_set().unwrap();
+    assert_eq!(current.offset, hive.open_key("ControlSet001").unwrap().offset);
+    let last_known_good = hive.last_known_good().unwrap();
+    assert_eq!(last_known_good.offset, hive.open_key("ControlSet002").unwrap().offset);
+
+    assert_eq!(hive.subkey_names(&current).unwrap(), ["Control", "Services"]);
+    assert_eq!(hive.subkey_names(&last_known_good).unwrap(), ["Services"]);
+}
+
+#[test]
+fn select_entries_that_cannot_name_a_control_set_are_errors() {
+    let root = Key::new("ROOT")
+        .subkey(Key::new("ControlSet001"))
+        .subkey(Key::new("Select").sz("Current", "1").dword("LastKnownGood", 3));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+
+    assert!(matches!(hive.current_control_set(), Err(HiveError::InvalidValueData(_))));
+    assert!(matches!(hive.last_known_good(), Err(HiveError::KeyNotFound(_))));
+
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&Key::new("ROOT")))).unwrap();
+    assert!(hive.current_control_set().is_err());
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, ListKind};
+use hivedigger::{Hive, HiveOptions, ValidationIssue};
+
+// Function to build a hive whose root lists its subkey "A" twice, the second time in place of "B"
+fn cross_linked_hive() -> Vec<u8> {
+    let root = Key::new("ROOT").list(ListKind::Li).subkey(Key::new("A")).subkey(Key::new("B"));
+    let mut hive_file = HiveBuilder::new().build(&root);
+    let list_position = hive_file.windows(4).position(|window| window == b"li\x02\x00").unwrap();
+    let first = list_position + 4;
+    hive_file.copy_within(first..first + 4, first + 4);
+    hive_file
+}
+
+#[test]
+fn walk_reports_a_key_reached_twice_instead_of_following_it() {
+    let options = HiveOptions {
+        skip_errors: true,
+        ..HiveOptions::default()
+    };
+    let mut hive = Hive::from_reader_with_options(Cursor::new(cross_linked_hive()), options).unwrap();
+    let entries: Vec<_> = hive.walk().collect();
+
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1].as_ref().unwrap().0, "A");
+    assert!(entries[2].as_ref().unwrap_err().to_string().contains("already part of the tree"));
+}
+
+#[test]
+fn validate_reports_cross_linked_keys() {
+    let mut hive = Hive::from_reader(Cursor::new(cross_linked_hive())).unwrap();
+    let report = hive.validate().unwrap();
+    assert!(report
+        .issues
+        .iter()
+        .any(|issue| matches!(issue, ValidationIssue::CrossLinkedKey { path, .. } if path.is_empty())));
+}
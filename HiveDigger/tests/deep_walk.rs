@@ -0,0 +1,71 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError};
+
+const DEPTH: usize = 1000;
+
+// Function to build a hive whose keys form one chain DEPTH levels below the root. The builder
+// recurses over the key tree, so it gets a thread with a larger stack; the walks under test run
+// on the ordinary test thread.
+fn build_deep_hive() -> Hive<Cursor<Vec<u8>>> {
+    let build = || {
+        let mut key = Key::new(&format!("Level{}", DEPTH)).dword("Bottom", 1);
+        for level in (1..DEPTH).rev() {
+            key = Key::new(&format!("Level{}", level)).subkey(key);
+        }
+        HiveBuilder::new().build(&Key::new("ROOT").subkey(key))
+    };
+    let hive_file = std::thread::Builder::new().stack_size(64 << 20).spawn(build).unwrap().join().unwrap();
+    Hive::from_reader(Cursor::new(hive_file)).unwrap()
+}
+
+#[test]
+fn a_thousand_levels_walk_in_order_with_a_higher_limit() {
+    let mut hive = build_deep_hive();
+    let keys = hive.walk_depth_limited(2 * DEPTH).collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(keys.len(), DEPTH + 1);
+    assert_eq!(keys[0].0, "");
+    for (level, (path, _)) in keys.iter().enumerate().skip(1) {
+        assert_eq!(path.rsplit('\\').next().unwrap(), format!("Level{}", level));
+        assert_eq!(path.split('\\').count(), level);
+    }
+    let (path, bottom) = &keys[DEPTH];
+    assert_eq!(hive.open_key(path).unwrap().offset, bottom.offset);
+}
+
+#[test]
+fn keys_past_the_limit_are_an_error() {
+    let mut hive = build_deep_hive();
+    for (walk, max_depth) in [(hive.walk().collect::<Vec<_>>(), 512), (hive.walk_depth_limited(100).collect(), 100)] {
+        // Keys down to the limit are yielded, then the walk stops at the first key below it
+        assert_eq!(walk.len(), max_depth + 2);
+        assert!(walk[..=max_depth].iter().all(Result::is_ok));
+        let Err(HiveError::Io(e)) = &walk[max_depth + 1] else {
+            panic!("expected the depth error");
+        };
+        assert!(e.to_string().contains(&format!("nested deeper than {} levels", max_depth)));
+    }
+}
+
+#[test]
+fn subtree_to_value_stops_at_the_depth_limit() {
+    let mut hive = build_deep_hive();
+    let level1 = hive.open_key("Level1").unwrap();
+    let Err(HiveError::Io(e)) = hive.subtree_to_value(&level1) else {
+        panic!("expected the depth error");
+    };
+    assert!(e.to_string().contains("nested deeper than 512 levels"));
+
+    // Starting low enough, the rest of the chain fits within the limit
+    let path: Vec<String> = (1..=DEPTH - 400).map(|level| format!("Level{}", level)).collect();
+    let start = hive.open_key(&path.join("\\")).unwrap();
+    let mut subtree = &hive.subtree_to_value(&start).unwrap();
+    for level in DEPTH - 399..=DEPTH {
+        subtree = &subtree["subkeys"][format!("Level{}", level)];
+    }
+    assert_eq!(subtree["values"]["Bottom"]["data"], 1);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, KeyValue};
+
+// Function to build a hive with one subkey holding a resident DWORD, a string stored in its
+// own cell and a value of an unknown type
+fn hive() -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Service")
+            .timestamp(132_539_328_000_000_000) // 2021-01-01
+            .dword("Start", 2)
+            .sz("ImagePath", "C:\\Windows\\system32\\svchost.exe")
+            .value("Vendor", 0x12345678, vec![1, 2, 3]),
+    );
+    Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap()
+}
+
+// Function to find one value of a key by name
+fn value(hive: &mut Hive<Cursor<Vec<u8>>>, key_path: &str, value_name: &str) -> KeyValue {
+    let key_node = hive.open_key(key_path).unwrap();
+    let values: Vec<_> = hive.values(&key_node).map(|value| value.unwrap()).collect();
+    values.into_iter().find(|(name, _)| name == value_name).unwrap().1.node
+}
+
+// Function to get the value of a "Label: value" line of a `describe` dump
+fn field<'a>(description: &'a str, label: &str) -> &'a str {
+    let line = description.lines().find(|line| line.starts_with(label)).unwrap();
+    line[label.len()..].trim()
+}
+
+#[test]
+fn key_node_flags_are_decoded_by_name() {
+    let mut hive = hive();
+    let root = hive.root().unwrap().describe();
+    assert!(field(&root, "Flags:").contains("HIVE_ENTRY"));
+    assert!(field(&root, "Flags:").contains("COMP_NAME"));
+    assert_eq!(field(&root, "Signature:"), "nk");
+    assert_eq!(field(&root, "Subkeys:"), "1");
+
+    let service = hive.open_key("Service").unwrap().describe();
+    assert!(!field(&service, "Flags:").contains("HIVE_ENTRY"));
+    assert_eq!(field(&service, "Values:"), "3");
+    assert_eq!(field(&service, "Last written:"), "2021-01-01 00:00:00 UTC (0x01d6dfd10c358000)");
+    assert_eq!(field(&service, "Subkey list:"), "none");
+    assert_eq!(field(&service, "Class name:"), "none");
+}
+
+#[test]
+fn key_value_types_and_resident_data_are_decoded() {
+    let mut hive = hive();
+
+    let start = value(&mut hive, "Service", "Start").describe();
+    assert_eq!(field(&start, "Signature:"), "vk");
+    assert_eq!(field(&start, "Data type:"), "REG_DWORD (4)");
+    assert_eq!(field(&start, "Data size:"), "4 (resident)");
+    assert_eq!(field(&start, "Data offset:"), "0x00000002 (resident data)");
+
+    let image_path = value(&mut hive, "Service", "ImagePath").describe();
+    assert_eq!(field(&image_path, "Data type:"), "REG_SZ (1)");
+    assert!(field(&image_path, "Data offset:").starts_with("0x"));
+    assert!(!image_path.contains("resident"));
+
+    let vendor = value(&mut hive, "Service", "Vendor").describe();
+    assert_eq!(field(&vendor, "Data type:"), "unknown (0x12345678)");
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{
+    builder::{HiveBuilder, Key},
+    fixtures::fixture_path,
+};
+use hivedigger::{DiffEntry, Hive, HiveError, RegistryValue};
+
+// Function to build a control set whose W32Time service starts as given
+fn control_set(name: &str, w32time_start: u32) -> Key {
+    let services = Key::new("Services")
+        .subkey(Key::new("EventLog").dword("Start", 2))
+        .subkey(Key::new("W32Time").dword("Start", w32time_start).sz("DisplayName", "Windows Time"));
+    Key::new(name)
+        .subkey(Key::new("Control").subkey(Key::new("ComputerName").sz("ComputerName", "WORKSTATION-01")))
+        .subkey(services)
+}
+
+#[test]
+fn control_sets_differing_in_one_start_value() {
+    let root = Key::new("ROOT")
+        .subkey(control_set("ControlSet001", 3))
+        .subkey(control_set("ControlSet002", 4))
+        .subkey(Key::new("Select").dword("Current", 1).dword("LastKnownGood", 2));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+
+    let diff = hive.diff_control_sets(1, 2).unwrap();
+    assert_eq!(
+        diff.entries,
+        [DiffEntry::ValueChanged {
+            path: "Services\\W32Time".to_string(),
+            name: "Start".to_string(),
+            old: RegistryValue::Dword(3),
+            new: RegistryValue::Dword(4),
+        }]
+    );
+    assert!(hive.diff_control_sets(1, 1).unwrap().is_empty());
+}
+
+#[test]
+fn keys_only_in_one_control_set_are_listed_with_their_subkeys() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let diff = hive.diff_control_sets(2, 1).unwrap();
+
+    // ControlSet002 in the fixture only has Services\W32Time, which starts differently
+    assert!(diff.entries.contains(&DiffEntry::ValueChanged {
+        path: "Services\\W32Time".to_string(),
+        name: "Start".to_string(),
+        old: RegistryValue::Dword(4),
+        new: RegistryValue::Dword(3),
+    }));
+    assert!(diff.entries.contains(&DiffEntry::KeyAdded { path: "Control\\Lsa".to_string() }));
+    assert!(diff.entries.contains(&DiffEntry::KeyAdded { path: "Control\\Lsa\\JD".to_string() }));
+    assert!(!diff.entries.iter().any(|entry| matches!(entry, DiffEntry::KeyRemoved { .. })));
+    assert!(matches!(hive.diff_control_sets(1, 3), Err(HiveError::KeyNotFound(path)) if path == "ControlSet003"));
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, ListKind};
+use hivedigger::{extract_syskey_from_reader, Hive, RegistryValue};
+
+// Function to build a hive where Parent's subkey list offset is its only subkey's key node
+fn direct_list_hive() -> Vec<u8> {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Parent")
+            .list(ListKind::Direct)
+            .subkey(Key::new("Child").dword("Answer", 42)),
+    );
+    HiveBuilder::new().build(&root)
+}
+
+#[test]
+fn a_subkey_list_offset_pointing_at_a_key_node_is_a_one_key_list() {
+    let mut hive = Hive::from_reader(Cursor::new(direct_list_hive())).unwrap();
+    let parent = hive.open_key("Parent").unwrap();
+
+    assert_eq!(hive.subkey_names(&parent).unwrap(), ["Child"]);
+    assert_eq!(hive.value("Parent\\Child", "Answer").unwrap(), RegistryValue::Dword(42));
+    let paths: Vec<String> = hive.walk().map(|key| key.unwrap().0).collect();
+    assert_eq!(paths, ["", "Parent", "Parent\\Child"]);
+    assert!(hive.validate().unwrap().is_valid());
+}
+
+#[test]
+fn syskey_lookup_follows_direct_subkey_lists() {
+    let root = Key::new("ROOT").list(ListKind::Direct).subkey(
+        Key::new("CurrentControlSet").list(ListKind::Direct).subkey(
+            Key::new("Control")
+                .list(ListKind::Direct)
+                .subkey(Key::new("Lsa").binary("JD", &[0x01, 0x23, 0xab, 0xcd, 0x45, 0x67])),
+        ),
+    );
+    let mut hive_file = Cursor::new(HiveBuilder::new().build(&root));
+    assert_eq!(extract_syskey_from_reader(&mut hive_file).unwrap(), [0x01, 0x23, 0xab, 0xcd, 0x45, 0x67]);
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{display_name, Hive};
+
+#[test]
+fn control_characters_are_escaped() {
+    assert_eq!(display_name("Run\0Hidden"), "Run\\x00Hidden");
+    assert_eq!(display_name("\u{1b}[2J"), "\\x1b[2J");
+    assert_eq!(display_name("line\r\nbreak\t"), "line\\x0d\\x0abreak\\x09");
+    assert_eq!(display_name("del\u{7f} c1\u{85}"), "del\\x7f c1\\x85");
+    assert_eq!(display_name("Café (x86)"), "Café (x86)");
+    assert_eq!(display_name(""), "");
+}
+
+#[test]
+fn names_with_embedded_nuls_keep_their_raw_form_for_lookups() {
+    // Malware hides keys behind a NUL that Win32 APIs treat as the end of the name
+    let root = Key::new("ROOT").subkey(Key::new("Run").subkey(Key::new("Run\0Hidden").sz("\0Payload", "evil.exe")));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let run = hive.open_key("Run").unwrap();
+
+    let names = hive.subkey_names(&run).unwrap();
+    assert_eq!(names, ["Run\0Hidden"]);
+    assert_eq!(display_name(&names[0]), "Run\\x00Hidden");
+
+    let hidden = hive.open_key("Run\\Run\0Hidden").unwrap();
+    let value_names: Vec<String> = hive.values(&hidden).map(|value| value.unwrap().0).collect();
+    assert_eq!(value_names, ["\0Payload"]);
+    assert_eq!(display_name(&value_names[0]), "\\x00Payload");
+    assert!(hive.open_key("Run\\Run").is_err());
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, REG_DWORD, REG_DWORD_BIG_ENDIAN};
+use hivedigger::{
+    search::{SearchOptions, SearchPattern},
+    Hive, RegistryValue,
+};
+
+#[test]
+fn big_endian_dwords_are_read_most_significant_byte_first() {
+    let bytes = vec![0x12, 0x34, 0x56, 0x78];
+    let root = Key::new("ROOT")
+        .value("BigEndian", REG_DWORD_BIG_ENDIAN, bytes.clone())
+        .value("LittleEndian", REG_DWORD, bytes);
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+
+    let big_endian = hive.value("", "BigEndian").unwrap();
+    assert_eq!(big_endian, RegistryValue::DwordBigEndian(0x12345678));
+    assert_eq!(big_endian.to_string(), "0x12345678 (305419896)");
+    // The same bytes as a little-endian DWORD come out swapped
+    assert_eq!(hive.value("", "LittleEndian").unwrap(), RegistryValue::Dword(0x78563412));
+
+    let pattern = SearchPattern::substring("305419896", false);
+    let matches = hive.search(&pattern, SearchOptions::default()).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].value_name.as_deref(), Some("BigEndian"));
+}
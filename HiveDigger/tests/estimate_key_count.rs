@@ -0,0 +1,34 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{
+    builder::{HiveBuilder, Key},
+    fixtures::{fixture_path, FIXTURES},
+};
+use hivedigger::Hive;
+
+#[test]
+fn estimate_matches_the_walked_count_of_each_fixture() {
+    for fixture in FIXTURES {
+        let mut hive = Hive::open(&fixture_path(fixture.name)).unwrap();
+        let walked = hive.walk().count() as u64;
+        assert_eq!(hive.estimate_key_count().unwrap(), walked, "{}", fixture.name);
+    }
+}
+
+#[test]
+fn unreachable_key_nodes_are_counted_too() {
+    let root = Key::new("ROOT").subkey(Key::new("Live").subkey(Key::new("Child")));
+    let mut builder = HiveBuilder::new();
+    // A key node left allocated but listed under no parent, as after a careless delete
+    let mut orphan = vec![0u8; 76];
+    orphan[0..2].copy_from_slice(b"nk");
+    orphan[72..74].copy_from_slice(&6u16.to_le_bytes());
+    orphan.extend(b"Orphan");
+    builder.alloc(&orphan);
+    let mut hive = Hive::from_reader(Cursor::new(builder.build(&root))).unwrap();
+
+    assert_eq!(hive.walk().count(), 3);
+    assert_eq!(hive.estimate_key_count().unwrap(), 4);
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::fixture_path;
+use hivedigger::Hive;
+
+#[test]
+fn present_and_absent_paths() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+
+    assert!(hive.exists("ControlSet001\\Services\\TermService"));
+    assert!(hive.try_exists("controlset001\\services\\termservice").unwrap());
+    assert!(!hive.exists("ControlSet001\\Services\\RemoteAccess"));
+    assert!(!hive.try_exists("ControlSet001\\Services\\RemoteAccess").unwrap());
+    assert!(!hive.try_exists("ControlSet003\\Services").unwrap());
+}
+
+#[test]
+fn damaged_subkey_list_is_an_error_not_absence() {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    let services_offset = Hive::from_bytes(&hive_file).unwrap().open_key("ControlSet001\\Services").unwrap().offset;
+    // The subkey list offset sits 28 bytes into the key node
+    let field = 4096 + services_offset as usize + 4 + 28;
+    let list_offset = u32::from_le_bytes(hive_file[field..field + 4].try_into().unwrap());
+    let signature = 4096 + list_offset as usize + 4;
+    hive_file[signature..signature + 2].copy_from_slice(b"zz");
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+
+    assert!(hive.try_exists("ControlSet001\\Services\\TermService").is_err());
+    assert!(!hive.exists("ControlSet001\\Services\\TermService"));
+    assert!(hive.try_exists("ControlSet001\\Control").unwrap());
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, RegistryValue};
+
+// Function to build an environment block from name/value pairs
+fn environment(variables: &[(&str, &str)]) -> HashMap<String, String> {
+    variables.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn references_are_expanded_case_insensitively() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let path = hive.value("ControlSet001\\Control\\Session Manager\\Environment", "Path").unwrap();
+
+    let variables = environment(&[("SYSTEMROOT", "C:\\Windows")]);
+    assert_eq!(path.expand_with(&variables).unwrap(), "C:\\Windows\\system32;C:\\Windows");
+    let variables = environment(&[("systemroot", "D:\\WINNT")]);
+    assert_eq!(path.expand_with(&variables).unwrap(), "D:\\WINNT\\system32;D:\\WINNT");
+}
+
+#[test]
+fn unknown_empty_and_unpaired_references_stay_literal() {
+    let value = RegistryValue::ExpandString("%Unknown%\\%%\\%Temp%\\100%".to_string());
+    let variables = environment(&[("TEMP", "C:\\Temp"), ("", "empty")]);
+    assert_eq!(value.expand_with(&variables).unwrap(), "%Unknown%\\%%\\C:\\Temp\\100%");
+    assert_eq!(value.expand_with(&HashMap::new()).unwrap(), "%Unknown%\\%%\\%Temp%\\100%");
+}
+
+#[test]
+fn only_expandable_strings_are_expanded() {
+    let variables = environment(&[("SystemRoot", "C:\\Windows")]);
+    assert_eq!(RegistryValue::String("%SystemRoot%".to_string()).expand_with(&variables), None);
+    assert_eq!(RegistryValue::Dword(1).expand_with(&variables), None);
+}
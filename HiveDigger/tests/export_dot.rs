@@ -0,0 +1,81 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::Hive;
+
+fn build_hive() -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Software")
+            .subkey(Key::new("Vendor \"Quoted\"").dword("Installed", 1).subkey(Key::new("App").sz("Path", "C:\\App")))
+            .subkey(Key::new("Classes").subkey(Key::new(".txt").subkey(Key::new("ShellNew")))),
+    );
+    Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap()
+}
+
+// Function to check the lines between `digraph hive {` and `}` are all node or edge statements,
+// returning the node IDs and the edges
+fn parse_dot(dot: &str) -> (Vec<String>, Vec<(String, String)>) {
+    let lines: Vec<&str> = dot.lines().collect();
+    assert_eq!(lines.first(), Some(&"digraph hive {"));
+    assert_eq!(lines.last(), Some(&"}"));
+
+    let (mut nodes, mut edges) = (Vec::new(), Vec::new());
+    for line in &lines[1..lines.len() - 1] {
+        let statement = line.trim().strip_suffix(';').expect("statement ends with ';'");
+        if statement.starts_with("node ") {
+            continue;
+        }
+        if let Some((from, to)) = statement.split_once(" -> ") {
+            edges.push((from.to_string(), to.to_string()));
+            continue;
+        }
+        let (id, label) = statement.split_once(" [label=").expect("node statement");
+        let label = label.strip_suffix(']').expect("attribute list is closed");
+        assert!(label.starts_with('"') && label.ends_with('"') && label.len() >= 2);
+        // Every quote inside the label is escaped
+        let inner = label[1..label.len() - 1].replace("\\\\", "").replace("\\\"", "");
+        assert!(!inner.contains('"'), "unescaped quote in {}", label);
+        nodes.push(id.to_string());
+    }
+    (nodes, edges)
+}
+
+#[test]
+fn export_dot_draws_keys_up_to_the_depth_limit() {
+    let mut hive = build_hive();
+    let root = hive.open_key("").unwrap();
+
+    for max_depth in 0..5 {
+        let mut dot = Vec::new();
+        hive.export_dot(&mut dot, "", &root, max_depth, false).unwrap();
+        let (nodes, edges) = parse_dot(&String::from_utf8(dot).unwrap());
+
+        let expected = hive
+            .walk()
+            .map(Result::unwrap)
+            .filter(|(path, _)| path.is_empty() || path.split('\\').count() <= max_depth)
+            .count();
+        assert_eq!(nodes.len(), expected, "max_depth {}", max_depth);
+        // A tree: every node but the start key has exactly one incoming edge, from a drawn node
+        assert_eq!(edges.len(), expected - 1);
+        assert!(edges.iter().all(|(from, to)| nodes.contains(from) && nodes.contains(to)));
+    }
+}
+
+#[test]
+fn export_dot_labels_keys_with_names_and_value_counts() {
+    let mut hive = build_hive();
+    let software = hive.open_key("Software").unwrap();
+
+    let mut dot = Vec::new();
+    hive.export_dot(&mut dot, "Software", &software, 1, true).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    parse_dot(&dot);
+
+    assert!(dot.contains("[label=\"Software\\n0 values\"]"));
+    assert!(dot.contains("[label=\"Vendor \\\"Quoted\\\"\\n1 value\"]"));
+    assert!(dot.contains("[label=\"Classes\\n0 values\"]"));
+    assert!(!dot.contains("App"));
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::fixture_path;
+use hivedigger::{extract_syskey_from_reader, Hive, HiveError, HiveOptions};
+
+// Function to read the SYSTEM fixture with its base block's file format replaced and the
+// checksum recomputed, so only the file format differs
+fn system_with_file_format(file_format: u32) -> Vec<u8> {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    hive_file[32..36].copy_from_slice(&file_format.to_le_bytes());
+    let checksum = hive_file[..508]
+        .chunks_exact(4)
+        .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+    hive_file[508..512].copy_from_slice(&checksum.to_le_bytes());
+    hive_file
+}
+
+#[test]
+fn file_format_other_than_direct_memory_load_is_refused() {
+    let hive_file = system_with_file_format(2);
+
+    assert!(matches!(
+        Hive::from_reader(Cursor::new(hive_file.clone())),
+        Err(HiveError::UnsupportedFileFormat(2))
+    ));
+    assert!(matches!(
+        extract_syskey_from_reader(&mut Cursor::new(hive_file)),
+        Err(HiveError::UnsupportedFileFormat(2))
+    ));
+}
+
+#[test]
+fn recovery_does_not_rebuild_an_intact_base_block() {
+    let options = HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    };
+    assert!(matches!(
+        Hive::from_reader_with_options(Cursor::new(system_with_file_format(2)), options.clone()),
+        Err(HiveError::UnsupportedFileFormat(2))
+    ));
+
+    // A bad signature is still rebuilt
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    hive_file[0..4].copy_from_slice(b"xxxx");
+    let mut hive = Hive::from_reader_with_options(Cursor::new(hive_file), options).unwrap();
+    assert!(hive.open_key("ControlSet001\\Services").is_ok());
+}
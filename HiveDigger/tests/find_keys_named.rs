@@ -0,0 +1,41 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::Hive;
+
+#[test]
+fn a_name_under_two_parents_yields_both_paths() {
+    let root = Key::new("ROOT")
+        .subkey(
+            Key::new("Software")
+                .subkey(Key::new("Updater").dword("Enabled", 1))
+                .subkey(Key::new("Vendor").subkey(Key::new("UPDATER"))),
+        )
+        .subkey(Key::new("Classes").subkey(Key::new("UpdaterHelper")));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+
+    let matches: Vec<_> = hive.find_keys_named("updater").map(Result::unwrap).collect();
+    let paths: Vec<&str> = matches.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(paths, ["Software\\Updater", "Software\\Vendor\\UPDATER"]);
+
+    // The yielded nodes are the keys themselves
+    let updater = hive.open_key("Software\\Updater").unwrap();
+    assert_eq!(matches[0].1.offset, updater.offset);
+    assert_eq!(hive.count_values(&matches[0].1), 1);
+
+    assert_eq!(hive.find_keys_named("Missing").count(), 0);
+    assert_eq!(hive.find_keys_named("ROOT").count(), 0);
+}
+
+#[test]
+fn a_backslash_in_a_key_name_is_part_of_the_name() {
+    let root = Key::new("ROOT").subkey(Key::new("Software").subkey(Key::new("Vendor\\Updater")));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+
+    let paths: Vec<String> = hive.find_keys_named("vendor\\updater").map(|key| key.unwrap().0).collect();
+    assert_eq!(paths, ["Software\\Vendor\\Updater"]);
+    // The tail of the path after the last backslash isn't a key of its own
+    assert_eq!(hive.find_keys_named("Updater").count(), 0);
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveError};
+
+#[test]
+fn four_level_path_resolves_in_one_call() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let root = hive.root().unwrap();
+
+    let jd = hive.find_subkey_path(&root, &["ControlSet001", "Control", "Lsa", "JD"]).unwrap();
+    assert_eq!(jd.offset, hive.open_key("ControlSet001\\Control\\Lsa\\JD").unwrap().offset);
+    assert_eq!(hive.class_name(&jd).unwrap().as_deref(), Some("4b3d8a1e"));
+
+    let from_control = hive.open_key("ControlSet001\\Control").unwrap();
+    let skew1 = hive.find_subkey_path(&from_control, &["lsa", "SKEW1"]).unwrap();
+    assert_eq!(skew1.offset, hive.open_key("ControlSet001\\Control\\Lsa\\Skew1").unwrap().offset);
+}
+
+#[test]
+fn empty_path_is_the_start_key_and_missing_keys_name_the_path() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let root = hive.root().unwrap();
+    assert_eq!(hive.find_subkey_path(&root, &[]).unwrap().offset, root.offset);
+
+    let Err(HiveError::KeyNotFound(path)) = hive.find_subkey_path(&root, &["ControlSet001", "Control", "Lsa", "Missing"])
+    else {
+        panic!("expected KeyNotFound");
+    };
+    assert_eq!(path, "ControlSet001\\Control\\Lsa\\Missing");
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, RegistryValue};
+
+#[test]
+fn image_paths_under_services() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let services = hive.open_key("ControlSet001\\Services").unwrap();
+    let image_paths = hive
+        .find_value_in_subtree("ControlSet001\\Services", &services, "imagepath")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(
+        image_paths,
+        [
+            (
+                "ControlSet001\\Services\\Tcpip".to_string(),
+                RegistryValue::ExpandString("System32\\drivers\\tcpip.sys".to_string())
+            ),
+            (
+                "ControlSet001\\Services\\TermService".to_string(),
+                RegistryValue::ExpandString("%SystemRoot%\\System32\\svchost.exe -k NetworkService".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn search_stays_inside_the_subtree() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let all_starts = hive.all_values().filter(|value| matches!(value, Ok((_, name, _)) if name == "Start")).count();
+
+    let services = hive.open_key("ControlSet002\\Services").unwrap();
+    let starts = hive
+        .find_value_in_subtree("ControlSet002\\Services", &services, "Start")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(starts.len() < all_starts);
+    assert!(starts.iter().all(|(path, _)| path.starts_with("ControlSet002\\Services\\")));
+
+    assert_eq!(hive.find_value_in_subtree("ControlSet002\\Services", &services, "Missing").count(), 0);
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{utf16z, HiveBuilder, Key, REG_SZ};
+use hivedigger::Hive;
+
+// Function to format the only subkey of a hive's root, `Key`, as a regedit block
+fn format_single_key(key: Key) -> String {
+    let hive_file = HiveBuilder::new().build(&Key::new("ROOT").subkey(key));
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let key_node = hive.open_key("Key").unwrap();
+    hive.format_key(&key_node, "HKEY_LOCAL_MACHINE\\SOFTWARE\\Key").unwrap()
+}
+
+#[test]
+fn each_value_type_is_formatted_as_regedit_exports_it() {
+    let block = format_single_key(
+        Key::new("Key")
+            .sz("", "default")
+            .sz("Path", "C:\\Program Files\\\"Quoted\"")
+            .dword("Start", 3)
+            .binary("Blob", &[0xde, 0xad, 0xbe, 0xef])
+            .expand_sz("Expand", "%a%")
+            .multi_sz("Multi", &["a", "b"])
+            .qword("Qword", 0x0102030405060708),
+    );
+
+    let expected = [
+        "[HKEY_LOCAL_MACHINE\\SOFTWARE\\Key]",
+        "@=\"default\"",
+        "\"Path\"=\"C:\\\\Program Files\\\\\\\"Quoted\\\"\"",
+        "\"Start\"=dword:00000003",
+        "\"Blob\"=hex:de,ad,be,ef",
+        "\"Expand\"=hex(2):25,00,61,00,25,00,00,00",
+        "\"Multi\"=hex(7):61,00,00,00,62,00,00,00,00,00",
+        "\"Qword\"=hex(b):08,07,06,05,04,03,02,01",
+        "",
+        "",
+    ]
+    .join("\r\n");
+    assert_eq!(block, expected);
+}
+
+#[test]
+fn long_hex_data_wraps_like_regedit() {
+    let block = format_single_key(Key::new("Key").binary("Blob", &[0xab; 40]));
+    let lines: Vec<&str> = block.split("\r\n").collect();
+
+    // regedit breaks a line after the comma that takes it to 77 characters or more
+    assert_eq!(lines[1], format!("\"Blob\"=hex:{},\\", ["ab"; 22].join(",")));
+    assert_eq!(lines[2], format!("  {}", ["ab"; 18].join(",")));
+    assert_eq!(lines[3], "");
+}
+
+#[test]
+fn string_with_data_after_its_terminator_is_written_as_hex() {
+    // "ab", a NUL, then "c": a quoted string would drop the "c"
+    let mut data = utf16z("ab");
+    data.extend(utf16z("c"));
+    let block = format_single_key(Key::new("Key").value("Hidden", REG_SZ, data).sz("Padded", "x"));
+    let lines: Vec<&str> = block.split("\r\n").collect();
+
+    assert_eq!(lines[1], "\"Hidden\"=hex(1):61,00,62,00,00,00,63,00,00,00");
+    assert_eq!(lines[2], "\"Padded\"=\"x\"");
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use std::{collections::BTreeMap, fs, io::Read, io::Seek};
+
+use common::fixtures::{fixture_path, FIXTURES};
+use hivedigger::{Hive, HiveError, RegistryValue};
+
+// Function to read every key path with its values, in walk order
+fn contents<R: Read + Seek>(hive: &mut Hive<R>) -> Vec<(String, BTreeMap<String, RegistryValue>)> {
+    let keys: Vec<_> = hive.walk().collect::<Result<_, _>>().unwrap();
+    keys.into_iter()
+        .map(|(path, key_node)| {
+            let values = hive.values_map(&key_node).unwrap();
+            (path, values)
+        })
+        .collect()
+}
+
+#[test]
+fn fixtures_parse_the_same_from_bytes_as_from_the_file() {
+    for fixture in FIXTURES {
+        let path = fixture_path(fixture.name);
+        let data = fs::read(&path).unwrap();
+        let mut from_file = Hive::open(&path).unwrap();
+        let mut from_bytes = Hive::from_bytes(&data).unwrap();
+
+        assert_eq!(from_bytes.version(), from_file.version(), "{}", fixture.name);
+        assert_eq!(contents(&mut from_bytes), contents(&mut from_file), "{}", fixture.name);
+    }
+}
+
+#[test]
+fn borrowed_names_point_into_the_callers_buffer() {
+    let data = fs::read(fixture_path("SYSTEM")).unwrap();
+    let mut hive = Hive::from_bytes(&data).unwrap();
+    let key_node = hive.open_key("ControlSet001\\Control\\ComputerName\\ComputerName").unwrap();
+
+    let names = hive.value_names_borrowed(&key_node).unwrap();
+    assert_eq!(names, ["ComputerName"]);
+    assert!(data.as_ptr_range().contains(&names[0].as_ptr()));
+    assert_eq!(hive.bytes().as_ptr(), data.as_ptr());
+}
+
+#[test]
+fn truncated_bytes_are_rejected() {
+    let data = fs::read(fixture_path("SYSTEM")).unwrap();
+    assert!(matches!(Hive::from_bytes(&data[..100]), Err(HiveError::Read { .. } | HiveError::Io(_))));
+}
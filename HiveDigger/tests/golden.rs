@@ -0,0 +1,157 @@
+// Golden tests: each fixture hive under tests/fixtures/hives is read through the public API and
+// the result compared with tests/fixtures/golden/<hive>.json. See tests/fixtures/README.md for
+// regenerating both after an intended change.
+
+mod common;
+
+use std::{fs, io::Read, path::Path};
+
+use common::fixtures::{fixture_path, fixtures_dir, FIXTURES};
+use hivedigger::{extract_syskey, Hive, RegistryValue};
+use serde_json::{json, Value};
+
+// Set to rewrite the fixture hives and goldens instead of checking them
+const UPDATE_VARIABLE: &str = "HIVEDIGGER_UPDATE_GOLDEN";
+
+// Value data up to this size is recorded in full as hex; longer data only by size and preview
+const MAX_HEX_DATA_LENGTH: usize = 64;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// Function to read everything the goldens record about a hive: header fields, statistics,
+// validation issues, every key with its class name and values, and for SYSTEM the syskey
+fn snapshot(name: &str, hive_path: &Path) -> Value {
+    let mut hive = Hive::open(hive_path).expect("Failed to open fixture hive");
+    let (major_version, minor_version) = hive.version();
+    let header = json!({
+        "version": format!("{}.{}", major_version, minor_version),
+        "file_name": hive.file_name(),
+        "dirty": hive.is_dirty(),
+        "root_name": hive.root_name().expect("Failed to read root name"),
+    });
+
+    let statistics = hive.statistics().expect("Failed to compute statistics");
+    let statistics = json!({
+        "keys": statistics.key_count,
+        "values": statistics.value_count,
+        "max_depth": statistics.max_depth,
+        "largest_value": statistics.largest_value.map(|largest_value| json!({
+            "path": largest_value.path,
+            "name": largest_value.name,
+            "size": largest_value.size,
+        })),
+        "free_cells": statistics.free_cell_count,
+        "free_bytes": statistics.free_bytes,
+        "errors": statistics.errors,
+    });
+
+    let validation: Vec<String> = hive
+        .validate()
+        .expect("Failed to validate")
+        .issues
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    let mut keys = Vec::new();
+    let walk: Vec<_> = hive.walk().collect::<Result<_, _>>().expect("Failed to walk");
+    for (path, key_node) in walk {
+        let mut values = Vec::new();
+        let key_values: Vec<_> = hive.values(&key_node).collect::<Result<_, _>>().expect("Failed to list values");
+        for (value_name, key_value) in key_values {
+            // Streamed, which reads resident, cell and big data alike
+            let mut data = Vec::new();
+            hive.value_reader(&key_value)
+                .and_then(|mut reader| Ok(reader.read_to_end(&mut data)?))
+                .expect("Failed to read value data");
+            let mut value = json!({
+                "name": value_name,
+                "type": key_value.data_type(),
+                "size": data.len(),
+                "value": RegistryValue::decode(key_value.data_type(), &data).to_string(),
+            });
+            if data.len() <= MAX_HEX_DATA_LENGTH {
+                value["data"] = json!(hex(&data));
+            }
+            values.push(value);
+        }
+
+        keys.push(json!({
+            "path": path,
+            "class": hive.class_name(&key_node).expect("Failed to read class name"),
+            "subkeys": hive.subkey_names(&key_node).expect("Failed to list subkeys"),
+            "values": values,
+        }));
+    }
+
+    let mut snapshot = json!({
+        "header": header,
+        "statistics": statistics,
+        "validation": validation,
+        "keys": keys,
+    });
+    if name == "SYSTEM" {
+        snapshot["syskey"] = match extract_syskey(hive_path) {
+            Ok(syskey) => json!(hex(&syskey)),
+            Err(e) => json!({ "error": e.to_string() }),
+        };
+    }
+    snapshot
+}
+
+#[test]
+fn fixture_hives_match_their_definitions() {
+    let update = std::env::var_os(UPDATE_VARIABLE).is_some();
+    for fixture in FIXTURES {
+        let hive_path = fixture_path(fixture.name);
+        let built = (fixture.build)();
+        if update {
+            fs::create_dir_all(hive_path.parent().unwrap()).unwrap();
+            fs::write(&hive_path, &built).unwrap();
+            continue;
+        }
+
+        let checked_in = fs::read(&hive_path).unwrap_or_else(|e| panic!("Failed to read {}: {}", hive_path.display(), e));
+        assert!(
+            checked_in == built,
+            "{} no longer matches tests/common/fixtures.rs; regenerate it with {}=1",
+            fixture.name,
+            UPDATE_VARIABLE
+        );
+    }
+}
+
+#[test]
+fn fixture_hives_match_goldens() {
+    let update = std::env::var_os(UPDATE_VARIABLE).is_some();
+    for fixture in FIXTURES {
+        let hive_path = fixture_path(fixture.name);
+        let golden_path = fixtures_dir().join("golden").join(format!("{}.json", fixture.name));
+        if update {
+            // The hive files may be rewritten by the other test at the same time
+            let hive_path = std::env::temp_dir().join(format!("hivedigger-golden-{}", fixture.name));
+            fs::write(&hive_path, (fixture.build)()).unwrap();
+            let mut actual = serde_json::to_string_pretty(&snapshot(fixture.name, &hive_path)).unwrap();
+            actual.push('\n');
+            fs::create_dir_all(golden_path.parent().unwrap()).unwrap();
+            fs::write(&golden_path, actual).unwrap();
+            fs::remove_file(&hive_path).unwrap();
+            continue;
+        }
+
+        let mut actual = serde_json::to_string_pretty(&snapshot(fixture.name, &hive_path)).unwrap();
+        actual.push('\n');
+        let expected =
+            fs::read_to_string(&golden_path).unwrap_or_else(|e| panic!("Failed to read {}: {}", golden_path.display(), e));
+        assert!(
+            actual == expected,
+            "{} differs from {}; if the change is intended, regenerate with {}=1.\nActual:\n{}",
+            fixture.name,
+            golden_path.display(),
+            UPDATE_VARIABLE,
+            actual
+        );
+    }
+}
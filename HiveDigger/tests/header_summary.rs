@@ -0,0 +1,39 @@
+mod common;
+
+use std::io::Cursor;
+
+use chrono::{TimeZone, Utc};
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveOptions};
+
+#[test]
+fn header_summary_reports_the_base_block_fields() {
+    let hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    let mut hive = Hive::from_reader(Cursor::new(&hive_file)).unwrap();
+    let header = hive.header_summary().unwrap();
+
+    assert_eq!((header.major_version, header.minor_version), (1, 5));
+    assert_eq!((header.primary_seq_num, header.secondary_seq_num), (1, 1));
+    assert!(!header.dirty);
+    assert_eq!(header.last_written, Utc.with_ymd_and_hms(2019, 4, 17, 18, 40, 0).unwrap());
+    assert_eq!(header.root_cell_offset, 0x20);
+    assert_eq!(header.hive_bins_data_size as usize, hive_file.len() - 4096);
+    assert_eq!(header.checksum, u32::from_le_bytes(hive_file[508..512].try_into().unwrap()));
+    assert!(header.checksum_valid);
+    assert_eq!(header.file_name, "SYSTEM");
+    assert_eq!((header.boot_type, header.boot_recover), (0, 0));
+}
+
+#[test]
+fn header_summary_checks_the_stored_checksum_of_a_recovered_hive() {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    hive_file[508] ^= 0xff;
+    let options = HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    };
+    let mut hive = Hive::from_reader_with_options(Cursor::new(hive_file), options).unwrap();
+
+    assert!(hive.recovered());
+    assert!(!hive.header_summary().unwrap().checksum_valid);
+}
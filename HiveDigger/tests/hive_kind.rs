@@ -0,0 +1,70 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::fixture_path;
+use hivedigger::{extract_syskey_from_reader, hive_kind, log::LogFormat, Hive, HiveError, HiveKind, HiveOptions};
+
+// A file type that is neither a primary hive (0) nor a transaction log (1, 2 or 6), standing in
+// for a differencing hive
+const DIFFERENCING_FILE_TYPE: u32 = 3;
+
+// Function to read the SYSTEM fixture with its base block's file type replaced and the checksum
+// recomputed, so only the file type differs
+fn system_with_file_type(file_type: u32) -> Vec<u8> {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    hive_file[28..32].copy_from_slice(&file_type.to_le_bytes());
+    let checksum = hive_file[..508]
+        .chunks_exact(4)
+        .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+    hive_file[508..512].copy_from_slice(&checksum.to_le_bytes());
+    hive_file
+}
+
+#[test]
+fn file_types_are_classified() {
+    for (file_type, expected) in [
+        (0, HiveKind::Primary),
+        (1, HiveKind::TransactionLog(LogFormat::Old)),
+        (2, HiveKind::TransactionLog(LogFormat::Old)),
+        (6, HiveKind::TransactionLog(LogFormat::New)),
+        (DIFFERENCING_FILE_TYPE, HiveKind::Other(DIFFERENCING_FILE_TYPE)),
+    ] {
+        let mut reader = Cursor::new(system_with_file_type(file_type));
+        assert_eq!(hive_kind(&mut reader).unwrap(), expected);
+    }
+}
+
+#[test]
+fn differencing_hive_is_refused_rather_than_misparsed() {
+    let hive_file = system_with_file_type(DIFFERENCING_FILE_TYPE);
+
+    let Err(error) = Hive::from_reader(Cursor::new(hive_file.clone())) else {
+        panic!("a differencing hive was opened as a primary one");
+    };
+    assert!(matches!(error, HiveError::UnsupportedHiveType(HiveKind::Other(DIFFERENCING_FILE_TYPE))));
+    assert!(error.to_string().contains("file type 3"));
+
+    // Recovery doesn't rebuild an intact base block as a primary one
+    let options = HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    };
+    assert!(matches!(
+        Hive::from_reader_with_options(Cursor::new(hive_file.clone()), options),
+        Err(HiveError::UnsupportedHiveType(HiveKind::Other(DIFFERENCING_FILE_TYPE)))
+    ));
+
+    assert!(matches!(
+        extract_syskey_from_reader(&mut Cursor::new(hive_file)),
+        Err(HiveError::UnsupportedHiveType(HiveKind::Other(DIFFERENCING_FILE_TYPE)))
+    ));
+}
+
+#[test]
+fn transaction_log_is_not_opened_as_a_hive() {
+    assert!(matches!(
+        Hive::from_reader(Cursor::new(system_with_file_type(6))),
+        Err(HiveError::UnsupportedHiveType(HiveKind::TransactionLog(LogFormat::New)))
+    ));
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveError, HiveOptions};
+
+fn open_with_budget(max_keys: u64, skip_errors: bool) -> Hive {
+    let options = HiveOptions {
+        max_keys: Some(max_keys),
+        skip_errors,
+        ..HiveOptions::default()
+    };
+    Hive::open_with_options(&fixture_path("SYSTEM"), options).unwrap()
+}
+
+#[test]
+fn walk_stops_with_the_budget_error() {
+    for skip_errors in [false, true] {
+        let mut hive = open_with_budget(5, skip_errors);
+        let keys: Vec<_> = hive.walk().collect();
+
+        assert_eq!(keys.len(), 6);
+        assert!(keys[..5].iter().all(Result::is_ok));
+        assert!(matches!(keys[5], Err(HiveError::BudgetExceeded { max_keys: 5 })));
+    }
+}
+
+#[test]
+fn budget_covering_the_hive_is_not_an_error() {
+    let key_count = Hive::open(&fixture_path("SYSTEM")).unwrap().walk().count() as u64;
+
+    let mut hive = open_with_budget(key_count, false);
+    assert_eq!(hive.walk().collect::<Result<Vec<_>, _>>().unwrap().len() as u64, key_count);
+    // Every walk gets the full budget
+    assert_eq!(hive.statistics().unwrap().key_count, key_count);
+
+    // Skipping unreadable keys doesn't pass over the budget, so results are never cut short quietly
+    for skip_errors in [false, true] {
+        let mut hive = open_with_budget(key_count - 1, skip_errors);
+        assert!(matches!(hive.statistics(), Err(HiveError::BudgetExceeded { .. })));
+        assert!(matches!(hive.walk_with_report(), Err(HiveError::BudgetExceeded { .. })));
+    }
+}
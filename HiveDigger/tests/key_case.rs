@@ -0,0 +1,57 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, ListKind};
+use hivedigger::{extract_syskey_from_reader, Hive, HiveError, RegistryValue};
+
+// Function to build a hive with a Run key and a planted sibling that differs only by case
+fn build_hive() -> Hive<Cursor<Vec<u8>>> {
+    let run_keys = Key::new("CurrentVersion")
+        .subkey(Key::new("Run").sz("OneDrive", "OneDrive.exe /background"))
+        .subkey(Key::new("RUN").sz("Updater", "C:\\Users\\Public\\updater.exe"));
+    let root = Key::new("ROOT").subkey(Key::new("Software").subkey(run_keys));
+    Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap()
+}
+
+// Function to list the value names of the key at a path, opened exactly
+fn value_names(hive: &mut Hive<Cursor<Vec<u8>>>, path: &str) -> Vec<String> {
+    let key_node = hive.open_key_exact(path).unwrap();
+    hive.values(&key_node).map(|value| value.unwrap().0).collect()
+}
+
+#[test]
+fn exact_lookup_finds_both_keys_differing_by_case() {
+    let mut hive = build_hive();
+    assert_eq!(value_names(&mut hive, "Software\\CurrentVersion\\Run"), ["OneDrive"]);
+    assert_eq!(value_names(&mut hive, "Software\\CurrentVersion\\RUN"), ["Updater"]);
+    assert!(matches!(
+        hive.open_key_exact("software\\CurrentVersion\\Run"),
+        Err(HiveError::KeyNotFound(path)) if path == "software\\CurrentVersion\\Run"
+    ));
+}
+
+#[test]
+fn case_insensitive_lookup_collapses_them_to_the_first() {
+    let mut hive = build_hive();
+    let run = hive.open_key_exact("Software\\CurrentVersion\\Run").unwrap();
+    for path in ["Software\\CurrentVersion\\Run", "Software\\CurrentVersion\\RUN", "software\\currentversion\\run"] {
+        assert_eq!(hive.open_key_ci(path).unwrap().offset, run.offset, "{}", path);
+        assert_eq!(hive.open_key(path).unwrap().offset, run.offset, "{}", path);
+    }
+    assert_eq!(
+        hive.value("SOFTWARE\\CurrentVersion\\run", "OneDrive").unwrap(),
+        RegistryValue::String("OneDrive.exe /background".to_string())
+    );
+}
+
+#[test]
+fn syskey_lookup_ignores_the_case_of_key_names() {
+    for list_kind in [ListKind::Lh, ListKind::Lf, ListKind::Li, ListKind::Direct] {
+        let lsa = Key::new("LSA").binary("JD", &[0x01, 0x23, 0xab, 0xcd]);
+        let control = Key::new("control").list(list_kind).subkey(lsa);
+        let root = Key::new("ROOT").subkey(Key::new("CURRENTCONTROLSET").subkey(control));
+        let mut hive_file = Cursor::new(HiveBuilder::new().build(&root));
+        assert_eq!(extract_syskey_from_reader(&mut hive_file).unwrap(), [0x01, 0x23, 0xab, 0xcd], "{:?}", list_kind);
+    }
+}
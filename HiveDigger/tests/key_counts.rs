@@ -0,0 +1,26 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::Hive;
+
+#[test]
+fn declared_counts_match_the_enumerated_keys_and_values() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+
+    let root = hive.root().unwrap();
+    assert_eq!(hive.count_subkeys(&root), 5);
+    assert_eq!(hive.count_values(&root), 0);
+
+    let select = hive.open_key("Select").unwrap();
+    assert_eq!(hive.count_subkeys(&select), 0);
+    assert_eq!(hive.count_values(&select), 4);
+
+    // Index root lists report the total across their leaves
+    let services = hive.open_key("ControlSet001\\Services").unwrap();
+    assert_eq!(hive.count_subkeys(&services), 4);
+
+    for key in [root, select, services] {
+        assert_eq!(hive.count_subkeys(&key) as usize, hive.subkeys(&key).count());
+        assert_eq!(hive.count_values(&key) as usize, hive.values(&key).count());
+    }
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, ListKind};
+use hivedigger::extract_syskey_from_reader;
+
+// Function to build a hive where every key on the syskey path comes after several siblings in
+// a leaf of the given kind
+fn hive_with_siblings(list_kind: ListKind) -> Vec<u8> {
+    let with_siblings = |key: Key, target: Key| {
+        ["Alpha", "Bravo", "Charlie"]
+            .into_iter()
+            .fold(key.list(list_kind), |key, sibling| key.subkey(Key::new(sibling)))
+            .subkey(target)
+    };
+    let lsa = Key::new("Lsa").binary("JD", &[0x01, 0x23, 0xab, 0xcd]);
+    let control = with_siblings(Key::new("Control"), lsa);
+    let control_set = with_siblings(Key::new("CurrentControlSet"), control);
+    HiveBuilder::new().build(&with_siblings(Key::new("ROOT"), control_set))
+}
+
+#[test]
+fn every_entry_of_a_hash_leaf_resolves() {
+    let mut hive_file = Cursor::new(hive_with_siblings(ListKind::Lh));
+    assert_eq!(extract_syskey_from_reader(&mut hive_file).unwrap(), [0x01, 0x23, 0xab, 0xcd]);
+}
+
+#[test]
+fn every_entry_of_fast_and_index_leaves_resolves() {
+    for list_kind in [ListKind::Lf, ListKind::Li] {
+        let mut hive_file = Cursor::new(hive_with_siblings(list_kind));
+        assert_eq!(extract_syskey_from_reader(&mut hive_file).unwrap(), [0x01, 0x23, 0xab, 0xcd], "{:?}", list_kind);
+    }
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{utf16z, HiveBuilder, Key};
+use hivedigger::{Hive, RegFormat, RegistryValue};
+
+const REG_LINK: u32 = 6;
+const TARGET: &str = "\\Registry\\Machine\\System\\ControlSet001";
+
+fn build_hive() -> Hive<Cursor<Vec<u8>>> {
+    // As stored by Windows, without a terminator
+    let unterminated: Vec<u8> = TARGET.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let root = Key::new("ROOT")
+        .subkey(Key::new("CurrentControlSet").value("SymbolicLinkValue", REG_LINK, unterminated))
+        .subkey(Key::new("Terminated").value("SymbolicLinkValue", REG_LINK, utf16z(TARGET)));
+    Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap()
+}
+
+#[test]
+fn link_values_decode_to_their_target_path() {
+    let mut hive = build_hive();
+    for key_path in ["CurrentControlSet", "Terminated"] {
+        let value = hive.value(key_path, "SymbolicLinkValue").unwrap();
+        assert_eq!(value, RegistryValue::Link(TARGET.to_string()), "{}", key_path);
+        assert_eq!(value.data_type(), REG_LINK);
+        assert_eq!(value.to_string(), TARGET);
+    }
+}
+
+#[test]
+fn link_targets_show_as_text_in_json_and_stay_hex_in_reg_files() {
+    let mut hive = build_hive();
+    let key_node = hive.open_key("CurrentControlSet").unwrap();
+    let subtree = hive.subtree_to_value(&key_node).unwrap();
+    assert_eq!(
+        subtree["values"]["SymbolicLinkValue"],
+        serde_json::json!({ "type": "REG_LINK", "data": TARGET })
+    );
+
+    // .reg files have no syntax for links, so regedit (and this export) writes them as hex(6)
+    let mut export = Vec::new();
+    hive.export_reg(&mut export, "HKEY_LOCAL_MACHINE\\SYSTEM", RegFormat::RegEdit4).unwrap();
+    assert!(String::from_utf8(export).unwrap().contains("\"SymbolicLinkValue\"=hex(6):5c,00,52,00"));
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, KeyNodeFlags};
+
+// Function to get the cell data at a reported offset: the cell size comes first, then the record
+fn cell_data(hive_file: &[u8], offset: u32) -> &[u8] {
+    &hive_file[4096 + offset as usize + 4..]
+}
+
+#[test]
+fn reported_offsets_lead_back_to_the_records() {
+    let hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    let lsa = hive.open_key("ControlSet001\\Control\\Lsa").unwrap();
+
+    // Each subkey's offset holds an "nk" record carrying its name (stored as ASCII)
+    let subkeys = hive.subkeys(&lsa).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(subkeys.len(), 4);
+    for (name, subkey) in &subkeys {
+        let data = cell_data(&hive_file, subkey.offset);
+        assert_eq!(&data[0..2], b"nk");
+        assert!(subkey.flags().contains(KeyNodeFlags::COMP_NAME));
+        assert_eq!(&data[76..76 + name.len()], name.as_bytes());
+        assert_eq!(hive.open_key(&format!("ControlSet001\\Control\\Lsa\\{}", name)).unwrap().offset, subkey.offset);
+    }
+
+    // And each value's offset holds a "vk" record carrying its name
+    let values = hive.values(&lsa).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(values.len(), 2);
+    for (name, value) in &values {
+        let data = cell_data(&hive_file, value.offset);
+        assert_eq!(&data[0..2], b"vk");
+        assert_eq!(&data[20..20 + name.len()], name.as_bytes());
+    }
+}
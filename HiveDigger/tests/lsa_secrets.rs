@@ -0,0 +1,60 @@
+#![cfg(feature = "lsa")]
+
+use hivedigger::lsa::{parse_secret, LsaSecret};
+
+// Function to wrap a secret in the LSA_SECRET_BLOB layout it decrypts to
+fn secret_blob(secret: &[u8]) -> Vec<u8> {
+    let mut blob = (secret.len() as u32).to_le_bytes().to_vec();
+    blob.extend([0u8; 12]);
+    blob.extend(secret);
+    // Decrypted blobs are padded to the cipher's block size
+    blob.resize(blob.len().div_ceil(16) * 16, 0);
+    blob
+}
+
+fn utf16(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+#[test]
+fn default_password_is_decoded_as_text() {
+    let blob = secret_blob(&utf16("Summer2024!\0"));
+    assert_eq!(parse_secret("DefaultPassword", &blob), LsaSecret::DefaultPassword("Summer2024!".to_string()));
+}
+
+#[test]
+fn machine_account_password_comes_with_its_nt_hash() {
+    // The NT hash of "password" is the well-known 8846f7eaee8fb117ad06bdd830b7586c
+    let password = utf16("password");
+    let LsaSecret::MachineAccount { password: parsed, nt_hash } = parse_secret("$MACHINE.ACC", &secret_blob(&password)) else {
+        panic!("expected a machine account secret");
+    };
+    assert_eq!(parsed, password);
+    assert_eq!(
+        nt_hash,
+        [0x88, 0x46, 0xf7, 0xea, 0xee, 0x8f, 0xb1, 0x17, 0xad, 0x06, 0xbd, 0xd8, 0x30, 0xb7, 0x58, 0x6c]
+    );
+}
+
+#[test]
+fn dpapi_system_is_split_into_its_keys() {
+    let mut secret = 1u32.to_le_bytes().to_vec();
+    secret.extend([0x11; 20]);
+    secret.extend([0x22; 20]);
+    assert_eq!(
+        parse_secret("DPAPI_SYSTEM", &secret_blob(&secret)),
+        LsaSecret::DpapiSystem {
+            machine_key: [0x11; 20],
+            user_key: [0x22; 20],
+        }
+    );
+}
+
+#[test]
+fn unknown_secrets_and_bad_blobs_stay_raw() {
+    assert_eq!(parse_secret("_SC_Spooler", &secret_blob(b"abc")), LsaSecret::Raw(b"abc".to_vec()));
+    // A length running past the end of the blob
+    let mut blob = secret_blob(b"abc");
+    blob[0] = 0xff;
+    assert_eq!(parse_secret("DefaultPassword", &blob), LsaSecret::Raw(blob.clone()));
+}
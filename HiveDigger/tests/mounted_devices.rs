@@ -0,0 +1,34 @@
+mod common;
+
+use common::fixtures::{fixture_path, USB_DEVICE_PATH};
+use hivedigger::{
+    artifacts::{mounted_devices, MountedDeviceData},
+    Hive,
+};
+
+#[test]
+fn mounted_devices_decodes_mbr_gpt_and_device_entries() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let devices = mounted_devices(&mut hive).unwrap();
+    assert_eq!(devices.len(), 5);
+
+    let drive = |letter: char| devices.iter().find(|device| device.drive_letter == Some(letter)).unwrap();
+    assert_eq!(
+        drive('C').data,
+        MountedDeviceData::Mbr {
+            disk_signature: 0x9a3c1f5e,
+            partition_offset: 0x100000,
+        }
+    );
+    assert_eq!(
+        drive('D').data,
+        MountedDeviceData::Gpt {
+            partition_guid: "{1c8e3a5b-2f4d-4b6a-9c11-02457e6d3fa0}".to_string(),
+        }
+    );
+    assert_eq!(drive('E').data, MountedDeviceData::Device(USB_DEVICE_PATH.to_string()));
+
+    let volume = devices.iter().find(|device| device.volume_guid.is_some()).unwrap();
+    assert_eq!(volume.drive_letter, None);
+    assert_eq!(volume.volume_guid.as_deref(), Some("{6f1c4b2e-93a5-11ee-b9d1-0242ac120002}"));
+}
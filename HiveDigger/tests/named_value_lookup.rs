@@ -0,0 +1,74 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveError, HiveOptions, RegistryValue};
+
+// Function to build a hive whose Run key holds three values, the middle one with a broken "vk"
+// signature
+fn hive_with_bad_value(options: HiveOptions) -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Run")
+            .sz("Before", "C:\\before.exe")
+            .sz("Broken", "C:\\broken.exe")
+            .sz("After", "C:\\after.exe"),
+    );
+    let mut hive_file = HiveBuilder::new().build(&root);
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    let run = hive.open_key("Run").unwrap();
+    let (_, broken) = hive.values(&run).nth(1).unwrap().unwrap();
+
+    let signature = 4096 + broken.offset as usize + 4;
+    hive_file[signature..signature + 2].copy_from_slice(b"zz");
+    Hive::from_reader_with_options(Cursor::new(hive_file), options).unwrap()
+}
+
+fn skip_errors() -> HiveOptions {
+    HiveOptions {
+        skip_errors: true,
+        ..HiveOptions::default()
+    }
+}
+
+#[test]
+fn one_shot_lookups_decode_or_report_the_missing_value() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    assert_eq!(
+        hive.value("ControlSet001\\Control\\ComputerName\\ComputerName", "computername").unwrap(),
+        RegistryValue::String("WORKSTATION-01".to_string())
+    );
+    assert_eq!(hive.value("Select", "Current").unwrap(), RegistryValue::Dword(1));
+    assert!(matches!(
+        hive.value("Select", "Missing"),
+        Err(HiveError::ValueNotFound { key_path, value_name }) if key_path == "Select" && value_name == "Missing"
+    ));
+}
+
+#[test]
+fn values_either_side_of_a_bad_record_are_still_found_with_skip_errors() {
+    let mut hive = hive_with_bad_value(skip_errors());
+    assert_eq!(hive.value("Run", "before").unwrap(), RegistryValue::String("C:\\before.exe".to_string()));
+    assert_eq!(hive.value("Run", "AFTER").unwrap(), RegistryValue::String("C:\\after.exe".to_string()));
+}
+
+#[test]
+fn lookup_that_only_misses_because_of_a_bad_record_is_an_error() {
+    let mut hive = hive_with_bad_value(skip_errors());
+    assert!(matches!(hive.value("Run", "Broken"), Err(HiveError::Read { record: "vk", .. })));
+    assert!(matches!(hive.value("Run", "Missing"), Err(HiveError::Read { record: "vk", .. })));
+}
+
+#[test]
+fn bad_record_before_the_match_fails_without_skip_errors_or_in_strict_mode() {
+    let strict = HiveOptions {
+        strict: true,
+        ..skip_errors()
+    };
+    for options in [HiveOptions::default(), strict] {
+        let mut hive = hive_with_bad_value(options);
+        assert_eq!(hive.value("Run", "before").unwrap(), RegistryValue::String("C:\\before.exe".to_string()));
+        assert!(matches!(hive.value("Run", "After"), Err(HiveError::Read { record: "vk", .. })));
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::io::{Cursor, ErrorKind};
+
+use common::builder::{HiveBuilder, Key, ListKind};
+use hivedigger::{extract_syskey_from_reader, Hive, HiveError};
+
+const SIBLINGS: [&str; 9] = ["Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India"];
+
+// Function to build a hive where every key on the syskey path comes after nine siblings under
+// an index root of index roots, putting it in the last leaf of the last nested index root
+fn hive_with_nested_index_roots() -> Vec<u8> {
+    let with_siblings = |key: Key, target: Key| {
+        SIBLINGS
+            .into_iter()
+            .fold(key.list(ListKind::NestedRi), |key, sibling| key.subkey(Key::new(sibling)))
+            .subkey(target)
+    };
+    let lsa = Key::new("Lsa").binary("JD", &[0x01, 0x23, 0xab, 0xcd]);
+    let control = with_siblings(Key::new("Control"), lsa);
+    let control_set = with_siblings(Key::new("CurrentControlSet"), control);
+    HiveBuilder::new().build(&with_siblings(Key::new("ROOT"), control_set))
+}
+
+// Function to get the file offset of the data of the root key's subkey list, an index root
+fn root_index_root(hive_file: &[u8]) -> usize {
+    let root_offset = Hive::from_bytes(hive_file).unwrap().root().unwrap().offset;
+    let field = 4096 + root_offset as usize + 4 + 28;
+    let list_offset = u32::from_le_bytes(hive_file[field..field + 4].try_into().unwrap());
+    4096 + list_offset as usize + 4
+}
+
+// Function to get the file offset of the data of an index root's first element's list
+fn first_element(hive_file: &[u8], list: usize) -> usize {
+    let element_offset = u32::from_le_bytes(hive_file[list + 4..list + 8].try_into().unwrap());
+    4096 + element_offset as usize + 4
+}
+
+#[test]
+fn keys_under_two_levels_of_index_roots_resolve() {
+    let hive_file = hive_with_nested_index_roots();
+    let nested = first_element(&hive_file, root_index_root(&hive_file));
+    assert_eq!(&hive_file[nested..nested + 2], b"ri");
+
+    assert_eq!(extract_syskey_from_reader(&mut Cursor::new(hive_file.clone())).unwrap(), [0x01, 0x23, 0xab, 0xcd]);
+
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    assert!(hive.open_key("CurrentControlSet\\Control\\Lsa").is_ok());
+    let root = hive.root().unwrap();
+    assert_eq!(hive.subkey_names(&root).unwrap().len(), SIBLINGS.len() + 1);
+}
+
+#[test]
+fn index_root_count_past_its_cell_is_an_error() {
+    let mut hive_file = hive_with_nested_index_roots();
+    let index_root = root_index_root(&hive_file);
+    hive_file[index_root + 2..index_root + 4].copy_from_slice(&0x4000u16.to_le_bytes());
+
+    let Err(HiveError::Io(e)) = extract_syskey_from_reader(&mut Cursor::new(hive_file)) else {
+        panic!("an oversized index root count was read past its cell");
+    };
+    assert_eq!(e.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn damaged_list_under_an_index_root_is_an_error_not_absence() {
+    // The first leaf holds Alpha and Bravo; CurrentControlSet is in a later one
+    let mut hive_file = hive_with_nested_index_roots();
+    let nested = first_element(&hive_file, root_index_root(&hive_file));
+    let leaf = first_element(&hive_file, nested);
+    hive_file[leaf..leaf + 2].copy_from_slice(b"zz");
+
+    let Err(HiveError::Io(e)) = extract_syskey_from_reader(&mut Cursor::new(hive_file)) else {
+        panic!("a damaged leaf was passed over");
+    };
+    assert_ne!(e.kind(), ErrorKind::NotFound);
+}
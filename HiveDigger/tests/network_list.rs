@@ -0,0 +1,45 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::fixtures::fixture_path;
+use hivedigger::{
+    artifacts::{network_profiles, NetworkKind, NetworkProfile},
+    Hive,
+};
+
+#[test]
+fn profiles_from_the_fixture() {
+    let mut hive = Hive::open(&fixture_path("SOFTWARE")).unwrap();
+    let profiles = network_profiles(&mut hive).unwrap();
+
+    assert_eq!(
+        profiles,
+        [
+            NetworkProfile {
+                guid: "{3E1F6C2A-8B4D-4F7E-9A10-5C2D7B8E9F01}".to_string(),
+                profile_name: Some("HomeWiFi".to_string()),
+                description: Some("HomeWiFi".to_string()),
+                kind: Some(NetworkKind::Wireless),
+                managed: Some(false),
+                date_created: NaiveDate::from_ymd_opt(2023, 3, 12).unwrap().and_hms_milli_opt(18, 45, 10, 250),
+                date_last_connected: NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_opt(8, 5, 0),
+            },
+            NetworkProfile {
+                guid: "{9C0B7D4E-2F1A-4B6C-8D3E-0A5F6B7C8D92}".to_string(),
+                profile_name: Some("corp.example.com".to_string()),
+                description: Some("corp.example.com".to_string()),
+                kind: Some(NetworkKind::Wired),
+                managed: Some(true),
+                date_created: NaiveDate::from_ymd_opt(2019, 4, 17).unwrap().and_hms_opt(9, 30, 0),
+                // Never connected: stored as zeros
+                date_last_connected: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn hive_without_network_list_has_no_profiles() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    assert!(network_profiles(&mut hive).unwrap().is_empty());
+}
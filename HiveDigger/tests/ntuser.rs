@@ -0,0 +1,60 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use common::fixtures::fixture_path;
+use hivedigger::{
+    artifacts::{recent_docs, run_mru, typed_paths, MruEntry},
+    Hive,
+};
+
+// Function to summarise MRU entries as (position, value name, value)
+fn summary(entries: Vec<MruEntry>) -> Vec<(usize, String, String)> {
+    entries.into_iter().map(|entry| (entry.position, entry.value_name, entry.value)).collect()
+}
+
+#[test]
+fn run_mru_follows_mru_list_and_drops_the_suffix() {
+    let mut hive = Hive::open(&fixture_path("NTUSER.DAT")).unwrap();
+    assert_eq!(
+        summary(run_mru(&mut hive).unwrap()),
+        [
+            (0, "b".to_string(), "notepad C:\\Users\\analyst\\notes.txt".to_string()),
+            (1, "a".to_string(), "cmd".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn typed_paths_are_ordered_by_url_number() {
+    let mut hive = Hive::open(&fixture_path("NTUSER.DAT")).unwrap();
+    assert_eq!(
+        summary(typed_paths(&mut hive).unwrap()),
+        [
+            (0, "url1".to_string(), "C:\\Users\\analyst\\Documents".to_string()),
+            (1, "url2".to_string(), "\\\\fileserver\\share".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn recent_docs_follow_mru_list_ex_up_to_its_terminator() {
+    let mut hive = Hive::open(&fixture_path("NTUSER.DAT")).unwrap();
+    assert_eq!(
+        summary(recent_docs(&mut hive).unwrap()),
+        [
+            (0, "1".to_string(), "notes.txt".to_string()),
+            (1, "0".to_string(), "report.docx".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn unused_features_give_empty_lists() {
+    let root = Key::new("ROOT").subkey(Key::new("Software"));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    assert!(run_mru(&mut hive).unwrap().is_empty());
+    assert!(typed_paths(&mut hive).unwrap().is_empty());
+    assert!(recent_docs(&mut hive).unwrap().is_empty());
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError};
+
+// Offset of the last 4096-byte bin a hive can have: the hive bins size is a u32 multiple of
+// 4096, so the largest hive ends exactly at 4 GiB
+const LAST_BIN_OFFSET: u32 = 0xFFFF_E000;
+
+// Struct representing a file of `length` bytes that is all zeros apart from a few segments, for
+// hives larger than 4 GiB without holding them in memory
+struct SparseFile {
+    length: u64,
+    segments: Vec<(u64, Vec<u8>)>,
+    position: u64,
+}
+
+impl Read for SparseFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = buf.len().min(self.length.saturating_sub(self.position) as usize);
+        let (start, end) = (self.position, self.position + count as u64);
+        buf[..count].fill(0);
+        for (segment_start, segment) in &self.segments {
+            let segment_end = segment_start + segment.len() as u64;
+            let (overlap_start, overlap_end) = (start.max(*segment_start), end.min(segment_end));
+            if overlap_start < overlap_end {
+                buf[(overlap_start - start) as usize..(overlap_end - start) as usize].copy_from_slice(
+                    &segment[(overlap_start - segment_start) as usize..(overlap_end - segment_start) as usize],
+                );
+            }
+        }
+        self.position = end;
+        Ok(count)
+    }
+}
+
+impl Seek for SparseFile {
+    fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64> {
+        self.position = match position {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::End(delta) => self.length.checked_add_signed(delta).unwrap(),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta).unwrap(),
+        };
+        Ok(self.position)
+    }
+}
+
+// Function to set a u32 field of a byte buffer
+fn patch(bytes: &mut [u8], position: usize, field: u32) {
+    bytes[position..position + 4].copy_from_slice(&field.to_le_bytes());
+}
+
+// Function to recompute a base block's checksum after patching it
+fn fix_checksum(hive_file: &mut [u8]) {
+    let checksum = hive_file[..508]
+        .chunks_exact(4)
+        .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+    patch(hive_file, 508, checksum);
+}
+
+#[test]
+fn root_key_at_the_end_of_the_largest_hive_is_read() {
+    let hive_file = HiveBuilder::new().build(&Key::new("ROOT").subkey(Key::new("Near").dword("Value", 7)));
+    let root_offset = u32::from_le_bytes(hive_file[36..40].try_into().unwrap());
+
+    // The first bin is stretched to end where the last one starts, and a copy of it is placed
+    // there with the root key also copied to its end, so the key node and its name are the last
+    // bytes of a 4 GiB file. The base block points at that copy; its subkey list still leads
+    // back to the first bin.
+    let root_cell = 4096 + root_offset as usize;
+    let root_cell_size = i32::from_le_bytes(hive_file[root_cell..root_cell + 4].try_into().unwrap()).unsigned_abs();
+    let far_root_offset = LAST_BIN_OFFSET + 0x1000 - root_cell_size;
+    let mut base_block = hive_file[..4096].to_vec();
+    patch(&mut base_block, 36, far_root_offset);
+    patch(&mut base_block, 40, LAST_BIN_OFFSET + 0x1000);
+    fix_checksum(&mut base_block);
+    let mut first_bin = hive_file[4096..8192].to_vec();
+    patch(&mut first_bin, 8, LAST_BIN_OFFSET);
+    let mut last_bin = hive_file[4096..8192].to_vec();
+    patch(&mut last_bin, 4, LAST_BIN_OFFSET);
+    last_bin[4096 - root_cell_size as usize..].copy_from_slice(&hive_file[root_cell..root_cell + root_cell_size as usize]);
+    let last_bin_position = 4096 + LAST_BIN_OFFSET as u64;
+    assert_eq!(last_bin_position + 4096, 1 << 32);
+
+    let file = SparseFile {
+        length: last_bin_position + 4096,
+        segments: vec![(0, base_block), (4096, first_bin), (last_bin_position, last_bin)],
+        position: 0,
+    };
+    let mut hive = Hive::from_reader(file).unwrap();
+
+    let root = hive.root().unwrap();
+    assert_eq!(root.offset, far_root_offset);
+    assert_eq!(hive.root_name().unwrap(), "ROOT");
+    assert_eq!(hive.subkey_names(&root).unwrap(), ["Near"]);
+    assert_eq!(hive.value("Near", "Value").unwrap().to_string(), "0x00000007 (7)");
+    assert_eq!(hive.bin_at(root.offset).unwrap().offset(), LAST_BIN_OFFSET);
+}
+
+#[test]
+fn cell_offset_that_would_wrap_to_the_base_block_is_an_error() {
+    let mut hive_file = HiveBuilder::new().build(&Key::new("ROOT").subkey(Key::new("Near").dword("Value", 7)));
+    let near_offset = Hive::from_bytes(&hive_file).unwrap().open_key("Near").unwrap().offset;
+
+    // In u32 arithmetic 4096 + offset + 4 would wrap to 0, the "regf" signature
+    let wrapping_offset = u32::MAX - 4099;
+    patch(&mut hive_file, 4096 + near_offset as usize + 4 + 40, wrapping_offset);
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let near = hive.open_key("Near").unwrap();
+
+    // The value list is looked for past the end of the file instead
+    let values: Vec<_> = hive.values(&near).collect();
+    assert!(matches!(
+        values[..],
+        [Err(HiveError::Read { record: "cell", offset, .. })] if offset == wrapping_offset
+    ));
+    assert!(hive.value("Near", "Value").is_err());
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveError};
+
+#[test]
+fn key_reopened_by_offset_is_the_same_key() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let jd = hive.open_key("ControlSet001\\Control\\Lsa\\JD").unwrap();
+
+    // A fresh handle, as a later session holding only the recorded offset would have
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let reopened = hive.open_key_node_at(jd.offset).unwrap();
+    assert_eq!(reopened.offset, jd.offset);
+    assert_eq!(hive.class_name(&reopened).unwrap().as_deref(), Some("4b3d8a1e"));
+    assert_eq!(hive.path_of(reopened.offset).as_deref(), Some("ControlSet001\\Control\\Lsa\\JD"));
+}
+
+#[test]
+fn value_offset_is_not_a_key_node() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let lsa = hive.open_key("ControlSet001\\Control\\Lsa").unwrap();
+    let (_, value) = hive.values(&lsa).next().unwrap().unwrap();
+
+    let error = hive.open_key_node_at(value.offset).unwrap_err();
+    assert!(matches!(error, HiveError::UnexpectedCell { offset, expected: "nk" } if offset == value.offset));
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError, RegistryValue};
+
+// Function to get the file position of a field inside the data of the cell at `cell_offset`
+fn field(cell_offset: u32, position: usize) -> usize {
+    4096 + cell_offset as usize + 4 + position
+}
+
+fn build_hive() -> Vec<u8> {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Run")
+            .sz("Updater", "C:\\updater.exe")
+            .subkey(Key::new("Child1"))
+            .subkey(Key::new("Child2")),
+    );
+    HiveBuilder::new().build(&root)
+}
+
+#[test]
+fn absent_subkeys_and_values_are_none() {
+    let mut hive = Hive::from_reader(Cursor::new(build_hive())).unwrap();
+    let run = hive.open_key("Run").unwrap();
+
+    let child = hive.subkey(&run, "child2").unwrap().unwrap();
+    assert_eq!(child.offset, hive.open_key("Run\\Child2").unwrap().offset);
+    assert!(hive.subkey(&run, "Child3").unwrap().is_none());
+
+    let updater = hive.value_opt(&run, "UPDATER").unwrap().unwrap();
+    assert_eq!(hive.read_value(&updater).unwrap(), RegistryValue::String("C:\\updater.exe".to_string()));
+    assert!(hive.value_opt(&run, "Helper").unwrap().is_none());
+    assert!(hive.value_opt(&child, "").unwrap().is_none());
+}
+
+#[test]
+fn corrupt_lists_are_errors_not_absence() {
+    let mut hive_file = build_hive();
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    let run = hive.open_key("Run").unwrap();
+    let child1 = hive.open_key("Run\\Child1").unwrap();
+    let (_, updater) = hive.values(&run).next().unwrap().unwrap();
+
+    // Break the first subkey's "nk" signature and the value's "vk" signature
+    hive_file[field(child1.offset, 0)..field(child1.offset, 2)].copy_from_slice(b"zz");
+    hive_file[field(updater.offset, 0)..field(updater.offset, 2)].copy_from_slice(b"zz");
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+
+    assert!(matches!(hive.subkey(&run, "Child2"), Err(HiveError::Read { record: "nk", .. })));
+    assert!(hive.value_opt(&run, "Updater").is_err());
+}
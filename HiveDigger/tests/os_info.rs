@@ -0,0 +1,40 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::fixtures::fixture_path;
+use hivedigger::{
+    artifacts::{os_info, OsInfo},
+    time::{filetime_to_datetime, unix_epoch_to_datetime},
+    Hive,
+};
+
+#[test]
+fn known_timestamps_convert_in_each_epoch() {
+    let installed = Utc.with_ymd_and_hms(2019, 4, 17, 18, 40, 0).unwrap();
+
+    assert_eq!(unix_epoch_to_datetime(1555526400), installed);
+    assert_eq!(filetime_to_datetime(132000000000000000), installed);
+    assert_eq!(unix_epoch_to_datetime(0), Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap());
+    assert_eq!(unix_epoch_to_datetime(u32::MAX), Utc.with_ymd_and_hms(2106, 2, 7, 6, 28, 15).unwrap());
+
+    // The wrong conversion gives a date minutes after 1601 instead
+    assert_eq!(filetime_to_datetime(1555526400).format("%Y-%m-%d %H:%M:%S").to_string(), "1601-01-01 00:02:35");
+}
+
+#[test]
+fn os_info_reads_install_date_and_time_in_their_own_epochs() {
+    let mut hive = Hive::open(&fixture_path("SOFTWARE")).unwrap();
+    let installed = Utc.with_ymd_and_hms(2019, 4, 17, 18, 40, 0).unwrap();
+
+    assert_eq!(
+        os_info(&mut hive).unwrap(),
+        OsInfo {
+            product_name: Some("Windows 10 Pro".to_string()),
+            display_version: Some("22H2".to_string()),
+            current_build: Some("19045".to_string()),
+            registered_owner: Some("analyst".to_string()),
+            install_date: Some(installed),
+            install_time: Some(installed),
+        }
+    );
+}
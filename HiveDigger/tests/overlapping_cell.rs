@@ -0,0 +1,83 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError, HiveOptions, RegistryValue, ValidationIssue};
+
+fn strict() -> HiveOptions {
+    HiveOptions {
+        strict: true,
+        ..HiveOptions::default()
+    }
+}
+
+// Function to build a hive with a key of three binary values, returning it with the file
+// offset of each value's vk record data
+fn build_hive() -> (Vec<u8>, Vec<usize>) {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Key")
+            .binary("Victim", &[0x11; 32])
+            .binary("Tampered", &[0x22; 8])
+            .binary("Intact", &[0x33; 8]),
+    );
+    let hive_file = HiveBuilder::new().build(&root);
+    let mut hive = Hive::from_bytes(&hive_file).unwrap();
+    let key_node = hive.open_key("Key").unwrap();
+    let vk_records = hive
+        .values(&key_node)
+        .map(|value| 4096 + value.unwrap().1.offset as usize + 4)
+        .collect();
+    (hive_file, vk_records)
+}
+
+// Function to read the u32 field at a position in the file
+fn field(hive_file: &[u8], position: usize) -> u32 {
+    u32::from_le_bytes(hive_file[position..position + 4].try_into().unwrap())
+}
+
+#[test]
+fn data_starting_inside_another_cell_is_flagged_in_strict_mode() {
+    let (mut hive_file, vk_records) = build_hive();
+    // Point Tampered's data 8 bytes into Victim's data cell
+    let victim_data = field(&hive_file, vk_records[0] + 8);
+    let tampered_data = victim_data + 8;
+    hive_file[vk_records[1] + 8..vk_records[1] + 12].copy_from_slice(&tampered_data.to_le_bytes());
+
+    // Without the cell map the data reads as whatever lies there
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    assert_eq!(hive.value("Key", "Tampered").unwrap(), RegistryValue::Binary(vec![0x11; 8]));
+
+    let mut hive = Hive::from_reader_with_options(Cursor::new(hive_file), strict()).unwrap();
+    let key_node = hive.open_key("Key").unwrap();
+    let key_values: Vec<_> = hive.values(&key_node).map(|value| value.unwrap().1).collect();
+    assert!(matches!(
+        hive.value_data(&key_values[1]),
+        Err(HiveError::OverlappingCell { a, b }) if a == tampered_data && b == victim_data
+    ));
+    assert_eq!(hive.value_data(&key_values[0]).unwrap(), [0x11; 32]);
+    assert_eq!(hive.value_data(&key_values[2]).unwrap(), [0x33; 8]);
+}
+
+#[test]
+fn data_running_into_the_next_cell_is_reported_by_validate() {
+    let (mut hive_file, vk_records) = build_hive();
+    // Claim 64 bytes of data for Tampered's 8-byte data cell
+    hive_file[vk_records[1] + 4..vk_records[1] + 8].copy_from_slice(&64u32.to_le_bytes());
+    let tampered_data = field(&hive_file, vk_records[1] + 8);
+    // Allocated cells store their size negated
+    let data_cell = 4096 + tampered_data as usize;
+    let next_cell = tampered_data + i32::from_le_bytes(hive_file[data_cell..data_cell + 4].try_into().unwrap()).unsigned_abs();
+
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let report = hive.validate().unwrap();
+    assert_eq!(
+        report.issues,
+        [ValidationIssue::OverlappingCell {
+            path: "Key".to_string(),
+            value_name: "Tampered".to_string(),
+            a: tampered_data,
+            b: next_cell,
+        }]
+    );
+}
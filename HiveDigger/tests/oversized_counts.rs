@@ -0,0 +1,62 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError};
+
+// Function to get the file position of a field inside the data of the cell at `cell_offset`
+fn field(cell_offset: u32, position: usize) -> usize {
+    4096 + cell_offset as usize + 4 + position
+}
+
+fn build_hive() -> (Vec<u8>, u32) {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Run")
+            .sz("Updater", "C:\\updater.exe")
+            .sz("Helper", "C:\\helper.exe")
+            .subkey(Key::new("Child1"))
+            .subkey(Key::new("Child2")),
+    );
+    let hive_file = HiveBuilder::new().build(&root);
+    let run_offset = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap().open_key("Run").unwrap().offset;
+    (hive_file, run_offset)
+}
+
+#[test]
+fn a_value_count_beyond_the_value_list_cell_is_rejected() {
+    let (mut hive_file, run_offset) = build_hive();
+    let count = field(run_offset, 36);
+    hive_file[count..count + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let run = hive.open_key("Run").unwrap();
+
+    // Two 4-byte offsets in a 16-byte cell leave room for three
+    let values: Vec<_> = hive.values(&run).collect();
+    assert_eq!(values.len(), 1);
+    assert!(matches!(
+        values[0],
+        Err(HiveError::CountExceedsCell { count: u32::MAX, capacity: 12, .. })
+    ));
+    assert!(matches!(hive.value("Run", "Updater"), Err(HiveError::CountExceedsCell { .. })));
+    assert_eq!(hive.count_values(&run), u32::MAX);
+}
+
+#[test]
+fn a_subkey_count_beyond_the_leaf_cell_is_rejected() {
+    let (mut hive_file, run_offset) = build_hive();
+    let list = field(run_offset, 28);
+    let list_offset = u32::from_le_bytes(hive_file[list..list + 4].try_into().unwrap());
+    let count = field(list_offset, 2);
+    hive_file[count..count + 2].copy_from_slice(&u16::MAX.to_le_bytes());
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let run = hive.open_key("Run").unwrap();
+
+    let subkeys: Vec<_> = hive.subkeys(&run).collect();
+    assert_eq!(subkeys.len(), 1);
+    assert!(matches!(
+        subkeys[0],
+        Err(HiveError::CountExceedsCell { count: 65535, offset, .. }) if offset == list_offset
+    ));
+    assert!(hive.open_key("Run\\Child1").is_err());
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, ListKind};
+use hivedigger::{Cell, Hive, HiveError};
+
+const DESCRIPTOR: [u8; 8] = [1, 0, 4, 0x80, 0, 0, 0, 0];
+
+// Function to build a hive with one key per subkey list kind, a small and a big data value, and
+// a security descriptor on the root
+fn hive_file() -> Vec<u8> {
+    let three_subkeys = |name: &str, list_kind| {
+        Key::new(name)
+            .list(list_kind)
+            .subkey(Key::new("One"))
+            .subkey(Key::new("Two"))
+            .subkey(Key::new("Three"))
+    };
+    let root = Key::new("ROOT")
+        .security(0)
+        .sz("Small", "value")
+        .binary("Big", &[0x5a; 20000])
+        .subkey(three_subkeys("Li", ListKind::Li))
+        .subkey(three_subkeys("Lf", ListKind::Lf))
+        .subkey(three_subkeys("Lh", ListKind::Lh))
+        .subkey(three_subkeys("Ri", ListKind::Ri));
+    HiveBuilder::new().security_descriptor(&DESCRIPTOR).build(&root)
+}
+
+// Function to read a u32 field `field` bytes into the cell data at `offset`
+fn field(hive_file: &[u8], offset: u32, field: usize) -> u32 {
+    let position = 4096 + offset as usize + 4 + field;
+    u32::from_le_bytes(hive_file[position..position + 4].try_into().unwrap())
+}
+
+#[test]
+fn key_node_and_key_value_cells() {
+    let hive_file = hive_file();
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let root = hive.root().unwrap();
+    assert!(matches!(hive.parse_cell(root.offset).unwrap(), Cell::KeyNode { name, .. } if name == "ROOT"));
+
+    let values = hive.values(&root).collect::<Result<Vec<_>, _>>().unwrap();
+    for (value_name, key_value) in values {
+        let Cell::KeyValue { name, key_value: parsed } = hive.parse_cell(key_value.offset).unwrap() else {
+            panic!("expected a key value");
+        };
+        assert_eq!(name, value_name);
+        assert_eq!(parsed.data_type(), key_value.data_type());
+    }
+}
+
+#[test]
+fn subkey_list_cells_of_each_kind() {
+    let hive_file = hive_file();
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+
+    for (key_name, signature) in [("Li", b"li"), ("Lf", b"lf"), ("Lh", b"lh")] {
+        let key_node = hive.open_key(key_name).unwrap();
+        let subkey_offsets: Vec<u32> = hive.subkeys(&key_node).map(|subkey| subkey.unwrap().1.offset).collect();
+        let list_offset = field(&hive_file, key_node.offset, 28);
+        let Cell::SubkeyList { signature: parsed, offsets } = hive.parse_cell(list_offset).unwrap() else {
+            panic!("expected a subkey list for {}", key_name);
+        };
+        assert_eq!(&parsed, signature);
+        assert_eq!(offsets, subkey_offsets);
+    }
+
+    // An index root lists leaves, which in turn list the key nodes
+    let ri = hive.open_key("Ri").unwrap();
+    let Cell::SubkeyList { signature, offsets } = hive.parse_cell(field(&hive_file, ri.offset, 28)).unwrap() else {
+        panic!("expected an index root");
+    };
+    assert_eq!(&signature, b"ri");
+    assert_eq!(offsets.len(), 2);
+    for leaf_offset in offsets {
+        assert!(matches!(hive.parse_cell(leaf_offset).unwrap(), Cell::SubkeyList { signature, .. } if &signature == b"lh"));
+    }
+}
+
+#[test]
+fn security_big_data_and_raw_data_cells() {
+    let hive_file = hive_file();
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    let root = hive.root().unwrap();
+
+    let security_offset = field(&hive_file, root.offset, 44);
+    let Cell::SecurityDescriptor {
+        flink,
+        blink,
+        reference_count,
+        descriptor,
+    } = hive.parse_cell(security_offset).unwrap()
+    else {
+        panic!("expected a security descriptor");
+    };
+    // The only descriptor links to itself
+    assert_eq!((flink, blink), (security_offset, security_offset));
+    assert!(reference_count >= 1);
+    assert_eq!(descriptor, DESCRIPTOR);
+
+    let values = hive.values(&root).collect::<Result<Vec<_>, _>>().unwrap();
+    let (_, big) = values.iter().find(|(name, _)| name == "Big").unwrap();
+    let Cell::BigData { segment_count, .. } = hive.parse_cell(field(&hive_file, big.offset, 8)).unwrap() else {
+        panic!("expected big data");
+    };
+    assert_eq!(segment_count, 2);
+
+    // The value list has no signature: it is two raw cell offsets
+    let Cell::ValueData(value_list) = hive.parse_cell(field(&hive_file, root.offset, 40)).unwrap() else {
+        panic!("expected raw data");
+    };
+    let value_offsets: Vec<u32> = values.iter().map(|(_, key_value)| key_value.offset).collect();
+    let listed: Vec<u32> = value_list[..8].chunks_exact(4).map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap())).collect();
+    assert_eq!(listed, value_offsets);
+}
+
+#[test]
+fn security_descriptor_size_past_the_cell_is_invalid() {
+    let mut hive_file = hive_file();
+    let root_offset = Hive::from_bytes(&hive_file).unwrap().root().unwrap().offset;
+    let security_offset = field(&hive_file, root_offset, 44);
+    let position = 4096 + security_offset as usize + 4 + 16;
+    hive_file[position..position + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    assert!(matches!(
+        hive.parse_cell(security_offset),
+        Err(HiveError::InvalidCell { offset }) if offset == security_offset
+    ));
+}
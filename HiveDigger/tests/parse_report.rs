@@ -0,0 +1,57 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, RegFormat, Warning, WarningKind};
+
+// Function to build a hive with a key of two subkeys, recording the given subkey count for it
+fn hive_with_subkey_count(number_of_subkeys: u32) -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("ROOT")
+        .subkey(Key::new("Parent").subkey(Key::new("One").dword("Value", 1)).subkey(Key::new("Two")))
+        .subkey(Key::new("Sibling").sz("Name", "sibling"));
+    let mut hive_file = HiveBuilder::new().build(&root);
+    let offset = Hive::from_bytes(&hive_file).unwrap().open_key("Parent").unwrap().offset;
+    let field = 4096 + offset as usize + 4 + 20;
+    hive_file[field..field + 4].copy_from_slice(&number_of_subkeys.to_le_bytes());
+    Hive::from_reader(Cursor::new(hive_file)).unwrap()
+}
+
+#[test]
+fn clean_hive_has_no_warnings() {
+    let mut hive = hive_with_subkey_count(2);
+    let (keys, report) = hive.walk_with_report().unwrap();
+    assert_eq!(keys.len(), 5);
+    assert!(report.is_clean());
+}
+
+#[test]
+fn one_mismatched_count_is_exactly_one_warning() {
+    let expected = [Warning {
+        path: "Parent".to_string(),
+        kind: WarningKind::SubkeyCountMismatch { recorded: 3, found: 2 },
+    }];
+
+    let mut hive = hive_with_subkey_count(3);
+    let (keys, report) = hive.walk_with_report().unwrap();
+    assert_eq!(keys.len(), 5);
+    assert_eq!(report.warnings, expected);
+
+    let mut export = Vec::new();
+    let report = hive.export_reg_with_report(&mut export, "HKEY_LOCAL_MACHINE\\TEST", RegFormat::RegEdit4).unwrap();
+    assert_eq!(report.warnings, expected);
+}
+
+#[test]
+fn zero_count_over_a_populated_list_is_a_warning() {
+    let mut hive = hive_with_subkey_count(0);
+    let (keys, report) = hive.walk_with_report().unwrap();
+    assert_eq!(keys.len(), 5);
+    assert_eq!(
+        report.warnings,
+        [Warning {
+            path: "Parent".to_string(),
+            kind: WarningKind::SubkeyCountMismatch { recorded: 0, found: 2 },
+        }]
+    );
+}
@@ -0,0 +1,26 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::Hive;
+
+#[test]
+fn path_of_names_a_key_from_its_offset() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let path = "ControlSet001\\Control\\ComputerName\\ComputerName";
+    let key_node = hive.open_key(path).unwrap();
+
+    assert_eq!(hive.path_of(key_node.offset).as_deref(), Some(path));
+    let root = hive.root().unwrap();
+    assert_eq!(hive.path_of(root.offset).as_deref(), Some(""));
+}
+
+#[test]
+fn path_of_is_none_for_cells_that_are_not_reachable_keys() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let key_node = hive.open_key("Select").unwrap();
+    let (_, key_value) = hive.values(&key_node).next().unwrap().unwrap();
+
+    assert_eq!(hive.path_of(key_value.offset), None);
+    assert_eq!(hive.path_of(key_node.offset + 4), None);
+    assert_eq!(hive.path_of(u32::MAX - 3), None);
+}
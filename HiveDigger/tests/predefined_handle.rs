@@ -0,0 +1,52 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, KeyNodeFlags};
+
+// HKEY_PERFORMANCE_DATA, the handle Windows links Perflib keys to
+const HKEY_PERFORMANCE_DATA: u32 = 0x80000004;
+
+// Function to build a hive whose "Perf" key is a predefined handle. Its subkey and value list
+// offsets point nowhere, as they mean nothing for such a key.
+fn hive_with_predefined_handle() -> Vec<u8> {
+    let root = Key::new("ROOT")
+        .subkey(Key::new("Perf").dword("Counter", 1).subkey(Key::new("Child")))
+        .subkey(Key::new("After"));
+    let mut hive_file = HiveBuilder::new().build(&root);
+    let perf_offset = Hive::from_bytes(&hive_file).unwrap().open_key("Perf").unwrap().offset;
+
+    let nk = 4096 + perf_offset as usize + 4;
+    let flags = u16::from_le_bytes([hive_file[nk + 2], hive_file[nk + 3]]) | 0x0040;
+    hive_file[nk + 2..nk + 4].copy_from_slice(&flags.to_le_bytes());
+    hive_file[nk + 20..nk + 24].copy_from_slice(&7u32.to_le_bytes());
+    hive_file[nk + 28..nk + 32].copy_from_slice(&0x7fff_fff0u32.to_le_bytes());
+    hive_file[nk + 36..nk + 40].copy_from_slice(&HKEY_PERFORMANCE_DATA.to_le_bytes());
+    hive_file[nk + 40..nk + 44].copy_from_slice(&0x7fff_fff0u32.to_le_bytes());
+    hive_file
+}
+
+#[test]
+fn predefined_handle_key_has_no_subkeys_or_values() {
+    let mut hive = Hive::from_reader(Cursor::new(hive_with_predefined_handle())).unwrap();
+    let perf = hive.open_key("Perf").unwrap();
+
+    assert!(perf.flags().contains(KeyNodeFlags::PREDEF_HANDLE));
+    assert_eq!(perf.predefined_handle(), Some(HKEY_PERFORMANCE_DATA));
+    assert_eq!(hive.subkeys(&perf).count(), 0);
+    assert_eq!(hive.values(&perf).count(), 0);
+    assert_eq!(hive.count_subkeys(&perf), 0);
+    assert_eq!(hive.count_values(&perf), 0);
+}
+
+#[test]
+fn walk_passes_over_a_predefined_handle_key() {
+    let mut hive = Hive::from_reader(Cursor::new(hive_with_predefined_handle())).unwrap();
+    let paths: Vec<String> = hive.walk().map(|key| key.unwrap().0).collect();
+
+    assert_eq!(paths, ["", "Perf", "After"]);
+    assert!(hive.open_key("After").unwrap().predefined_handle().is_none());
+    let report = hive.validate().unwrap();
+    assert!(report.is_valid(), "{:?}", report.issues);
+}
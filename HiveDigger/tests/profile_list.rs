@@ -0,0 +1,88 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{
+    builder::{HiveBuilder, Key},
+    fixtures::fixture_path,
+};
+use hivedigger::{
+    artifacts::{list_user_profiles, ProfileKind, UserProfile},
+    Hive,
+};
+
+// Function to build a SOFTWARE hive holding the given ProfileList subkeys
+fn software_with_profiles(profiles: Vec<Key>) -> Vec<u8> {
+    let profile_list = profiles.into_iter().fold(Key::new("ProfileList"), Key::subkey);
+    let root = Key::new("ROOT").subkey(
+        Key::new("Microsoft")
+            .subkey(Key::new("Windows NT").subkey(Key::new("CurrentVersion").subkey(profile_list))),
+    );
+    HiveBuilder::new().build(&root)
+}
+
+#[test]
+fn fixture_profiles_map_sids_to_paths() {
+    let mut hive = Hive::open(&fixture_path("SOFTWARE")).unwrap();
+
+    assert_eq!(
+        list_user_profiles(&mut hive).unwrap(),
+        [
+            UserProfile {
+                sid: "S-1-5-18".to_string(),
+                kind: ProfileKind::System,
+                profile_image_path: Some("%systemroot%\\system32\\config\\systemprofile".to_string()),
+                backup: false,
+            },
+            UserProfile {
+                sid: "S-1-5-21-1004336348-1177238915-682003330-1001".to_string(),
+                kind: ProfileKind::User,
+                profile_image_path: Some("C:\\Users\\analyst".to_string()),
+                backup: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn service_backup_and_binary_sid_profiles() {
+    // S-1-5-21-1-2-3-500 as a binary SID: revision, sub-authority count, authority, then each
+    // sub-authority little-endian
+    let mut binary_sid = vec![1, 5, 0, 0, 0, 0, 0, 5];
+    for sub_authority in [21u32, 1, 2, 3, 500] {
+        binary_sid.extend(sub_authority.to_le_bytes());
+    }
+    let hive_file = software_with_profiles(vec![
+        Key::new("S-1-5-19").expand_sz("ProfileImagePath", "%systemroot%\\ServiceProfiles\\LocalService"),
+        Key::new("S-1-5-20").expand_sz("ProfileImagePath", "%systemroot%\\ServiceProfiles\\NetworkService"),
+        Key::new("S-1-5-21-1-2-3-1002.bak").expand_sz("ProfileImagePath", "C:\\Users\\bob"),
+        Key::new("Administrator").binary("Sid", &binary_sid),
+        Key::new("NoSid").expand_sz("ProfileImagePath", "C:\\Users\\nobody"),
+    ]);
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+
+    let profiles: Vec<_> = list_user_profiles(&mut hive)
+        .unwrap()
+        .into_iter()
+        .map(|profile| (profile.sid, profile.kind, profile.profile_image_path, profile.backup))
+        .collect();
+    assert_eq!(
+        profiles,
+        [
+            (
+                "S-1-5-19".to_string(),
+                ProfileKind::LocalService,
+                Some("%systemroot%\\ServiceProfiles\\LocalService".to_string()),
+                false
+            ),
+            (
+                "S-1-5-20".to_string(),
+                ProfileKind::NetworkService,
+                Some("%systemroot%\\ServiceProfiles\\NetworkService".to_string()),
+                false
+            ),
+            ("S-1-5-21-1-2-3-1002".to_string(), ProfileKind::User, Some("C:\\Users\\bob".to_string()), true),
+            ("S-1-5-21-1-2-3-500".to_string(), ProfileKind::User, None, false),
+        ]
+    );
+}
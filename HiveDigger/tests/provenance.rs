@@ -0,0 +1,62 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveError, HiveOptions, OpenManifest};
+
+#[test]
+fn clean_open_is_recorded() {
+    let hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    assert_eq!(
+        *hive.provenance(),
+        OpenManifest {
+            checksum_valid: Some(true),
+            dirty: Some(false),
+            ..OpenManifest::default()
+        }
+    );
+}
+
+#[test]
+fn checksum_failure_and_recovery_are_both_recorded() {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    // Mark the hive dirty without fixing the checksum
+    hive_file[4] ^= 1;
+    assert!(matches!(
+        Hive::from_reader(Cursor::new(hive_file.clone())),
+        Err(HiveError::InvalidChecksum { .. })
+    ));
+
+    let options = HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    };
+    let hive = Hive::from_reader_with_options(Cursor::new(hive_file), options).unwrap();
+    let provenance = hive.provenance();
+    assert_eq!(provenance.checksum_valid, Some(false));
+    assert_eq!(provenance.dirty, Some(true));
+    assert!(provenance.recovery_enabled);
+    assert!(provenance.base_block_rebuilt);
+    assert!(!provenance.root_relocated);
+    assert!(hive.recovered());
+
+    let json = provenance.to_json();
+    assert_eq!(json["checksum_valid"], false);
+    assert_eq!(json["base_block_rebuilt"], true);
+    assert_eq!(json["logs_applied"], serde_json::json!([]));
+}
+
+#[test]
+fn unreadable_base_block_leaves_the_stored_fields_unknown() {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    hive_file[..4].copy_from_slice(b"xxxx");
+    let options = HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    };
+    let hive = Hive::from_reader_with_options(Cursor::new(hive_file), options).unwrap();
+    assert_eq!(hive.provenance().checksum_valid, None);
+    assert_eq!(hive.provenance().dirty, None);
+    assert!(hive.provenance().base_block_rebuilt);
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, REG_QWORD};
+use hivedigger::{time::filetime_to_datetime, Hive, RegistryValue, WarningKind};
+
+#[test]
+fn qword_values_decode_as_little_endian_integers() {
+    // InstallTime is a FILETIME stored as a QWORD
+    let root = Key::new("ROOT").qword("InstallTime", 132000000000000000);
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let root = hive.root().unwrap();
+    let (_, key_value) = hive.values(&root).next().unwrap().unwrap();
+
+    assert!(!key_value.is_resident());
+    assert_eq!(key_value.data_len(), 8);
+    assert_eq!(hive.read_value(&key_value).unwrap(), RegistryValue::Qword(132000000000000000));
+    assert_eq!(filetime_to_datetime(132000000000000000).to_rfc3339(), "2019-04-17T18:40:00+00:00");
+}
+
+#[test]
+fn qword_values_of_the_wrong_size_stay_raw_and_are_reported() {
+    let root = Key::new("ROOT").value("Truncated", REG_QWORD, vec![1, 2, 3, 4, 5, 6, 7]);
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+
+    assert_eq!(
+        hive.value("", "Truncated").unwrap(),
+        RegistryValue::Other {
+            data_type: REG_QWORD,
+            data: vec![1, 2, 3, 4, 5, 6, 7],
+        }
+    );
+    let (_, report) = hive.walk_with_report().unwrap();
+    assert_eq!(
+        report.warnings[0].kind,
+        WarningKind::IntegerSizeMismatch {
+            value_name: "Truncated".to_string(),
+            data_type: REG_QWORD,
+            size: 7,
+        }
+    );
+    assert_eq!(report.warnings[0].to_string(), "(root)\\Truncated: 8-byte integer value holds 7 bytes");
+}
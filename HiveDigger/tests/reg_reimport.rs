@@ -0,0 +1,166 @@
+mod common;
+
+use std::{collections::BTreeMap, io::Cursor};
+
+use common::builder::{utf16z, HiveBuilder, Key, REG_BINARY, REG_DWORD, REG_EXPAND_SZ, REG_MULTI_SZ, REG_SZ};
+use hivedigger::{Hive, RegFormat};
+
+const KEY_PREFIX: &str = "HKEY_LOCAL_MACHINE\\TEST";
+
+// Values of a key as (name, type, data), with data as the hive stores it
+type ImportedValues = Vec<(String, u32, Vec<u8>)>;
+
+fn build_hive() -> Vec<u8> {
+    let root = Key::new("ROOT")
+        .sz("", "default")
+        .subkey(
+            Key::new("Software")
+                .sz("Path", "C:\\Program Files\\\"Quoted\"")
+                .sz("Accented", "café")
+                .dword("Start", 3)
+                .qword("Installed", 0x01d5_3a2b_0000_0000)
+                .binary("Blob", &[0x5a; 60])
+                .expand_sz("Expand", "%SystemRoot%\\system32")
+                .multi_sz("Multi", &["one", "two"])
+                .value("Vendor", 0x12345678, vec![1, 2, 3])
+                .subkey(Key::new("Nested").dword("Depth", 2)),
+        )
+        .subkey(Key::new("Empty"));
+    HiveBuilder::new().build(&root)
+}
+
+// Function to export the test hive in the given format
+fn export(format: RegFormat) -> Vec<u8> {
+    let mut hive = Hive::from_reader(Cursor::new(build_hive())).unwrap();
+    let mut export = Vec::new();
+    hive.export_reg(&mut export, KEY_PREFIX, format).unwrap();
+    export
+}
+
+// Function to read every key's values straight from the test hive, keyed by full path
+fn hive_contents() -> BTreeMap<String, ImportedValues> {
+    let mut hive = Hive::from_reader(Cursor::new(build_hive())).unwrap();
+    let keys: Vec<_> = hive.walk().map(|key| key.unwrap()).collect();
+
+    let mut contents = BTreeMap::new();
+    for (path, key_node) in keys {
+        let values: Vec<_> = hive.values(&key_node).map(|value| value.unwrap()).collect();
+        let values = values
+            .into_iter()
+            .map(|(name, key_value)| {
+                let data = hive.value_data(&key_value).unwrap();
+                (name, key_value.data_type(), data)
+            })
+            .collect();
+        let full_path = if path.is_empty() { KEY_PREFIX.to_string() } else { format!("{KEY_PREFIX}\\{path}") };
+        contents.insert(full_path, values);
+    }
+    contents
+}
+
+// Function to import a .reg file the way regedit does, understanding both the UTF-16 version 5
+// format and the UTF-8 REGEDIT4 one, whose string types hold 8-bit strings that are widened
+// to UTF-16 when stored
+fn import_reg(file: &[u8]) -> BTreeMap<String, ImportedValues> {
+    let (text, narrow_strings) = match file {
+        [0xFF, 0xFE, utf16 @ ..] => {
+            let units: Vec<u16> = utf16.chunks_exact(2).map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]])).collect();
+            let text = String::from_utf16(&units).unwrap();
+            assert!(text.starts_with("Windows Registry Editor Version 5.00\r\n"));
+            (text, false)
+        }
+        _ => {
+            let text = String::from_utf8(file.to_vec()).unwrap();
+            assert!(text.starts_with("REGEDIT4\r\n"));
+            (text, true)
+        }
+    };
+
+    // Join hex data continued onto following lines
+    let text = text.replace("\\\r\n  ", "");
+
+    let mut contents = BTreeMap::new();
+    let mut current_key = None;
+    for line in text.split("\r\n").skip(1).filter(|line| !line.is_empty()) {
+        if let Some(key_path) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            contents.insert(key_path.to_string(), Vec::new());
+            current_key = Some(key_path.to_string());
+            continue;
+        }
+
+        let (name, data) = parse_value_name(line);
+        let (data_type, data) = parse_value_data(data, narrow_strings);
+        let key_path = current_key.as_ref().expect("value outside a key");
+        contents.get_mut(key_path).unwrap().push((name, data_type, data));
+    }
+    contents
+}
+
+// Function to split a value line into its unescaped name and the text after the `=`
+fn parse_value_name(line: &str) -> (String, &str) {
+    if let Some(data) = line.strip_prefix("@=") {
+        return (String::new(), data);
+    }
+
+    let (name, end) = unescape_quoted(line);
+    (name, line[end..].strip_prefix('=').expect("no = after the value name"))
+}
+
+// Function to unescape the quoted string at the start of `text`, also returning the index just
+// past its closing quote
+fn unescape_quoted(text: &str) -> (String, usize) {
+    assert!(text.starts_with('"'));
+    let mut string = String::new();
+    let mut characters = text.char_indices().skip(1);
+    while let Some((index, character)) = characters.next() {
+        match character {
+            '\\' => string.push(characters.next().unwrap().1),
+            '"' => return (string, index + 1),
+            _ => string.push(character),
+        }
+    }
+    panic!("unterminated string in {text}");
+}
+
+// Function to turn the data part of a value line into the type and bytes the hive would store
+fn parse_value_data(data: &str, narrow_strings: bool) -> (u32, Vec<u8>) {
+    if data.starts_with('"') {
+        let (string, end) = unescape_quoted(data);
+        assert_eq!(end, data.len());
+        return (REG_SZ, utf16z(&string));
+    }
+    if let Some(dword) = data.strip_prefix("dword:") {
+        return (REG_DWORD, u32::from_str_radix(dword, 16).unwrap().to_le_bytes().to_vec());
+    }
+
+    let (data_type, hex) = if let Some(hex) = data.strip_prefix("hex:") {
+        (REG_BINARY, hex)
+    } else {
+        let (data_type, hex) = data.strip_prefix("hex(").unwrap().split_once("):").unwrap();
+        (u32::from_str_radix(data_type, 16).unwrap(), hex)
+    };
+    let bytes: Vec<u8> = hex.split(',').map(|byte| u8::from_str_radix(byte, 16).unwrap()).collect();
+
+    if narrow_strings && matches!(data_type, REG_SZ | REG_EXPAND_SZ | REG_MULTI_SZ) {
+        let string = String::from_utf8(bytes).unwrap();
+        return (data_type, string.encode_utf16().flat_map(|unit| unit.to_le_bytes()).collect());
+    }
+    (data_type, bytes)
+}
+
+#[test]
+fn version_5_export_reimports_to_the_same_values() {
+    let export = export(RegFormat::Utf16V5);
+    assert_eq!(import_reg(&export), hive_contents());
+}
+
+#[test]
+fn regedit4_export_reimports_to_the_same_values() {
+    let export = export(RegFormat::RegEdit4);
+    let text = String::from_utf8(export.clone()).unwrap();
+
+    // 8-bit string data, and QWORDs as raw bytes since neither format has a QWORD syntax
+    assert!(text.contains("\"Expand\"=hex(2):25,53,79,73,74,65,6d,52,6f,6f,74,25,5c"));
+    assert!(text.contains("\"Installed\"=hex(b):00,00,00,00,2b,3a,d5,01"));
+    assert_eq!(import_reg(&export), hive_contents());
+}
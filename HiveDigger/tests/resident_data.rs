@@ -0,0 +1,26 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{utf16z, HiveBuilder, Key};
+use hivedigger::Hive;
+
+#[test]
+fn resident_dword_and_external_string() {
+    let root = Key::new("ROOT").dword("Start", 3).sz("ImagePath", "svchost.exe");
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let root = hive.root().unwrap();
+    let values = hive.values(&root).collect::<Result<Vec<_>, _>>().unwrap();
+
+    // Four bytes fit in the data offset field, so the DWORD keeps its data in the vk record
+    let (_, dword) = &values[0];
+    assert!(dword.is_resident());
+    assert_eq!(dword.data_len(), 4);
+    assert_eq!(hive.value_data(dword).unwrap(), 3u32.to_le_bytes());
+
+    // The string needs a cell of its own, and its length carries no resident bit
+    let (_, string) = &values[1];
+    assert!(!string.is_resident());
+    assert_eq!(string.data_len() as usize, utf16z("svchost.exe").len());
+    assert_eq!(hive.value_data(string).unwrap(), utf16z("svchost.exe"));
+}
@@ -0,0 +1,128 @@
+use hivedigger::{
+    resource::{
+        decode_full_resource_descriptor, decode_resource_list, FullResourceDescriptor, PartialResourceDescriptor, Resource,
+    },
+    HiveError,
+};
+
+// Function to lay out a CM_PARTIAL_RESOURCE_DESCRIPTOR: type, share disposition, flags and the
+// union padded to `size` bytes in all
+fn partial_descriptor(size: usize, resource_type: u8, flags: u16, union: &[u8]) -> Vec<u8> {
+    let mut descriptor = vec![resource_type, 1];
+    descriptor.extend(flags.to_le_bytes());
+    descriptor.extend(union);
+    descriptor.resize(size, 0);
+    descriptor
+}
+
+// Function to lay out a CM_FULL_RESOURCE_DESCRIPTOR around its partial descriptors
+fn full_descriptor(interface_type: u32, partial_descriptors: &[Vec<u8>]) -> Vec<u8> {
+    let mut descriptor = interface_type.to_le_bytes().to_vec();
+    descriptor.extend(0u32.to_le_bytes());
+    descriptor.extend(1u16.to_le_bytes());
+    descriptor.extend(1u16.to_le_bytes());
+    descriptor.extend((partial_descriptors.len() as u32).to_le_bytes());
+    for partial_descriptor in partial_descriptors {
+        descriptor.extend(partial_descriptor);
+    }
+    descriptor
+}
+
+// A serial port's resources as a 64-bit system stores them under HARDWARE\RESOURCEMAP: an I/O
+// port range, an interrupt and a memory range
+fn serial_port_resources() -> Vec<u8> {
+    let mut port = 0x3f8u64.to_le_bytes().to_vec();
+    port.extend(8u32.to_le_bytes());
+    let mut interrupt = 4u16.to_le_bytes().to_vec();
+    interrupt.extend(0u16.to_le_bytes());
+    interrupt.extend(4u32.to_le_bytes());
+    interrupt.extend(u64::MAX.to_le_bytes());
+    let mut memory = 0xfebf_0000u64.to_le_bytes().to_vec();
+    memory.extend(0x1000u32.to_le_bytes());
+
+    let mut resource_list = 1u32.to_le_bytes().to_vec();
+    resource_list.extend(full_descriptor(
+        1,
+        &[
+            partial_descriptor(20, 1, 0x0005, &port),
+            partial_descriptor(20, 2, 0x0000, &interrupt),
+            partial_descriptor(20, 3, 0x0000, &memory),
+        ],
+    ));
+    resource_list
+}
+
+#[test]
+fn resource_list_decodes_each_descriptor_type() {
+    let resource_list = decode_resource_list(&serial_port_resources()).unwrap();
+
+    assert_eq!(
+        resource_list.full_descriptors,
+        [FullResourceDescriptor {
+            interface_type: 1,
+            bus_number: 0,
+            version: 1,
+            revision: 1,
+            partial_descriptors: vec![
+                PartialResourceDescriptor {
+                    share_disposition: 1,
+                    flags: 0x0005,
+                    resource: Resource::Port { start: 0x3f8, length: 8 },
+                },
+                PartialResourceDescriptor {
+                    share_disposition: 1,
+                    flags: 0,
+                    resource: Resource::Interrupt {
+                        level: 4,
+                        group: 0,
+                        vector: 4,
+                        affinity: u64::MAX,
+                    },
+                },
+                PartialResourceDescriptor {
+                    share_disposition: 1,
+                    flags: 0,
+                    resource: Resource::Memory {
+                        start: 0xfebf_0000,
+                        length: 0x1000,
+                    },
+                },
+            ],
+        }]
+    );
+}
+
+#[test]
+fn full_descriptor_from_a_32_bit_system() {
+    // 16-byte partial descriptors: a DMA channel and large memory scaled by 2^8
+    let mut large_memory = 0x1_0000_0000u64.to_le_bytes().to_vec();
+    large_memory.extend(0x10u32.to_le_bytes());
+    let data = full_descriptor(
+        5,
+        &[
+            partial_descriptor(16, 4, 0, &[2, 0, 0, 0, 0, 0, 0, 0]),
+            partial_descriptor(16, 7, 0x0200, &large_memory),
+        ],
+    );
+    let full_descriptor = decode_full_resource_descriptor(&data).unwrap();
+
+    assert_eq!(full_descriptor.interface_type, 5);
+    let resources: Vec<&Resource> = full_descriptor.partial_descriptors.iter().map(|descriptor| &descriptor.resource).collect();
+    assert_eq!(
+        resources,
+        [
+            &Resource::Dma { channel: 2, port: 0 },
+            &Resource::Memory {
+                start: 0x1_0000_0000,
+                length: 0x1000,
+            },
+        ]
+    );
+}
+
+#[test]
+fn truncated_resource_list_is_invalid_value_data() {
+    let mut data = serial_port_resources();
+    data.truncate(data.len() - 6);
+    assert!(matches!(decode_resource_list(&data), Err(HiveError::InvalidValueData(_))));
+}
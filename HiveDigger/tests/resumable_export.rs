@@ -0,0 +1,83 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{
+    builder::{HiveBuilder, Key},
+    fixtures::{fixture_path, FIXTURES},
+};
+use hivedigger::{Hive, HiveError, RegFormat};
+
+#[test]
+fn walking_after_each_key_gives_the_rest_of_the_walk() {
+    for fixture in FIXTURES {
+        let mut hive = Hive::open(&fixture_path(fixture.name)).unwrap();
+        let keys: Vec<(String, u32)> = hive.walk().map(|key| key.unwrap()).map(|(path, key_node)| (path, key_node.offset)).collect();
+
+        for (position, (path, offset)) in keys.iter().enumerate() {
+            let rest: Vec<(String, u32)> = hive
+                .walk_after(*offset)
+                .unwrap()
+                .map(|key| key.unwrap())
+                .map(|(path, key_node)| (path, key_node.offset))
+                .collect();
+            assert_eq!(rest, keys[position + 1..], "{} after {:?}", fixture.name, path);
+        }
+    }
+}
+
+#[test]
+fn chunked_export_matches_a_single_pass() {
+    for fixture in FIXTURES {
+        let mut hive = Hive::open(&fixture_path(fixture.name)).unwrap();
+        let mut single_pass = Vec::new();
+        hive.export_reg(&mut single_pass, "HKEY_LOCAL_MACHINE\\TEST", RegFormat::Utf16V5).unwrap();
+
+        for max_keys in [1, 3, 1000] {
+            // A fresh handle for every chunk, as after the job was interrupted and restarted
+            let mut chunked = Vec::new();
+            let mut checkpoint = None;
+            loop {
+                let mut hive = Hive::open(&fixture_path(fixture.name)).unwrap();
+                checkpoint = hive
+                    .export_reg_resumable(&mut chunked, "HKEY_LOCAL_MACHINE\\TEST", RegFormat::Utf16V5, checkpoint, max_keys)
+                    .unwrap();
+                if checkpoint.is_none() {
+                    break;
+                }
+            }
+            assert_eq!(chunked, single_pass, "{} in chunks of {}", fixture.name, max_keys);
+        }
+    }
+}
+
+#[test]
+fn checkpoint_must_be_a_key_in_the_tree() {
+    let root = Key::new("ROOT").subkey(Key::new("Software").sz("Name", "value"));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let software = hive.open_key("Software").unwrap();
+    let (_, key_value) = hive.values(&software).next().unwrap().unwrap();
+
+    assert!(matches!(
+        hive.walk_after(key_value.offset),
+        Err(HiveError::UnexpectedCell { expected: "nk", .. })
+    ));
+}
+
+#[test]
+fn checkpoint_not_listed_under_its_parent_is_rejected() {
+    let root = Key::new("ROOT").subkey(Key::new("Classes")).subkey(Key::new("Software"));
+    let mut bytes = HiveBuilder::new().build(&root);
+    let mut hive = Hive::from_reader(Cursor::new(bytes.clone())).unwrap();
+    let classes = hive.open_key("Classes").unwrap().offset;
+    let software = hive.open_key("Software").unwrap().offset;
+
+    // Point Software's parent offset at Classes, which doesn't list it
+    let parent_field = 4096 + software as usize + 4 + 16;
+    bytes[parent_field..parent_field + 4].copy_from_slice(&classes.to_le_bytes());
+    let mut hive = Hive::from_reader(Cursor::new(bytes)).unwrap();
+    assert!(matches!(
+        hive.walk_after(software),
+        Err(HiveError::InvalidCheckpoint { offset }) if offset == software
+    ));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, KeyNodeFlags};
+
+#[test]
+fn root_is_the_key_the_base_block_points_at() {
+    let hive_file = std::fs::read(fixture_path("Amcache.hve")).unwrap();
+    let root_cell_offset = u32::from_le_bytes(hive_file[36..40].try_into().unwrap());
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+
+    let root = hive.root().unwrap();
+    assert_eq!(root.offset, root_cell_offset);
+    assert!(root.flags().contains(KeyNodeFlags::HIVE_ENTRY));
+    assert_eq!(hive.root_name().unwrap(), "{11517B7C-E79D-4e20-961B-75A811715ADD}");
+    assert_eq!(hive.subkey_names(&root).unwrap(), ["Root"]);
+
+    // Cached after the first read
+    assert_eq!(hive.root().unwrap().offset, root.offset);
+}
+
+#[test]
+fn root_name_may_be_empty() {
+    let root = Key::new("").subkey(Key::new("Software"));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    assert_eq!(hive.root_name().unwrap(), "");
+    let root = hive.root().unwrap();
+    assert_eq!(hive.subkey_names(&root).unwrap(), ["Software"]);
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveOptions};
+
+fn build_hive(root_label: Option<&str>) -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("CMI-CreateHive{2A7FB991-7BBE-4F9D-B91E-7CB51D4737F5}")
+        .subkey(Key::new("Select").dword("Current", 1))
+        .subkey(Key::new("ControlSet001").subkey(Key::new("Control")));
+    let options = HiveOptions {
+        root_label: root_label.map(str::to_string),
+        ..HiveOptions::default()
+    };
+    Hive::from_reader_with_options(Cursor::new(HiveBuilder::new().build(&root)), options).unwrap()
+}
+
+#[test]
+fn full_paths_start_with_the_root_label() {
+    let mut hive = build_hive(Some("HKEY_LOCAL_MACHINE\\SYSTEM"));
+    let paths: Vec<String> = hive.walk().map(|key| key.unwrap().0).collect();
+    let full_paths: Vec<String> = paths.iter().map(|path| hive.full_path(path)).collect();
+    assert_eq!(
+        full_paths,
+        [
+            "HKEY_LOCAL_MACHINE\\SYSTEM",
+            "HKEY_LOCAL_MACHINE\\SYSTEM\\Select",
+            "HKEY_LOCAL_MACHINE\\SYSTEM\\ControlSet001",
+            "HKEY_LOCAL_MACHINE\\SYSTEM\\ControlSet001\\Control",
+        ]
+    );
+
+    // The raw root name never shows up
+    let control = hive.open_key("ControlSet001\\Control").unwrap();
+    let path = hive.path_of(control.offset).unwrap();
+    assert_eq!(hive.full_path(&path), "HKEY_LOCAL_MACHINE\\SYSTEM\\ControlSet001\\Control");
+}
+
+#[test]
+fn open_key_accepts_full_paths() {
+    let mut hive = build_hive(Some("HKLM\\SYSTEM"));
+    let control = hive.open_key("ControlSet001\\Control").unwrap();
+    assert_eq!(hive.open_key("HKLM\\SYSTEM\\ControlSet001\\Control").unwrap().offset, control.offset);
+    assert_eq!(hive.open_key("hklm\\system\\controlset001\\control").unwrap().offset, control.offset);
+    assert_eq!(hive.open_key("HKLM\\SYSTEM").unwrap().offset, hive.root().unwrap().offset);
+    // Only whole components of the label are stripped
+    assert!(hive.open_key("HKLM\\SYSTEMX\\Select").is_err());
+}
+
+#[test]
+fn paths_are_unchanged_without_a_root_label() {
+    let mut hive = build_hive(None);
+    assert_eq!(hive.full_path(""), "");
+    assert_eq!(hive.full_path("Select"), "Select");
+
+    let root = hive.root().unwrap();
+    let mut dot = Vec::new();
+    hive.export_dot(&mut dot, "", &root, 0, false).unwrap();
+    assert!(String::from_utf8(dot).unwrap().contains("[label=\"(root)\"]"));
+
+    let mut hive = build_hive(Some("HKLM\\SYSTEM"));
+    let mut dot = Vec::new();
+    hive.export_dot(&mut dot, "", &root, 0, false).unwrap();
+    assert!(String::from_utf8(dot).unwrap().contains("[label=\"HKLM\\\\SYSTEM\"]"));
+}
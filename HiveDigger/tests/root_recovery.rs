@@ -0,0 +1,77 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveOptions, ValidationIssue};
+
+// Function to read the SYSTEM fixture with its base block's root cell offset replaced and the
+// checksum recomputed, so only the root offset is wrong. Returns the hive and the real root offset.
+fn system_with_root_offset(root_cell_offset: u32) -> (Vec<u8>, u32) {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    let real_root_offset = u32::from_le_bytes(hive_file[36..40].try_into().unwrap());
+    hive_file[36..40].copy_from_slice(&root_cell_offset.to_le_bytes());
+    let checksum = hive_file[..508]
+        .chunks_exact(4)
+        .fold(0u32, |checksum, chunk| checksum ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+    hive_file[508..512].copy_from_slice(&checksum.to_le_bytes());
+    (hive_file, real_root_offset)
+}
+
+fn recovery() -> HiveOptions {
+    HiveOptions {
+        recovery: true,
+        ..HiveOptions::default()
+    }
+}
+
+#[test]
+fn zeroed_root_offset_is_found_by_scanning_the_bins() {
+    let (hive_file, real_root_offset) = system_with_root_offset(0);
+
+    // Without recovery the header checks out but the root can't be read
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    assert!(!hive.recovered());
+    assert!(hive.open_key("Select").is_err());
+
+    let mut hive = Hive::from_reader_with_options(Cursor::new(hive_file), recovery()).unwrap();
+    assert!(hive.recovered());
+    assert_eq!(hive.header_summary().unwrap().root_cell_offset, real_root_offset);
+    // The rest of the base block is kept rather than rebuilt
+    assert_eq!(hive.file_name(), "SYSTEM");
+    assert!(hive.open_key("ControlSet001\\Control\\Lsa").is_ok());
+
+    let report = hive.validate().unwrap();
+    assert_eq!(
+        report.issues,
+        [
+            ValidationIssue::HeaderRecovered,
+            ValidationIssue::RootKeyRelocated {
+                stored: 0,
+                found: real_root_offset,
+            },
+        ]
+    );
+}
+
+#[test]
+fn root_offset_pointing_at_another_key_is_replaced() {
+    let (hive_file, real_root_offset) = system_with_root_offset(0);
+    let select_offset = {
+        let mut hive = Hive::from_reader_with_options(Cursor::new(hive_file), recovery()).unwrap();
+        hive.open_key("Select").unwrap().offset
+    };
+
+    let (hive_file, _) = system_with_root_offset(select_offset);
+    let mut hive = Hive::from_reader_with_options(Cursor::new(hive_file), recovery()).unwrap();
+    assert!(hive.recovered());
+    assert_eq!(hive.header_summary().unwrap().root_cell_offset, real_root_offset);
+    assert_eq!(hive.value_as_u32("Select", "Current").unwrap(), 1);
+}
+
+#[test]
+fn intact_root_offset_is_left_alone() {
+    let hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    let hive = Hive::from_reader_with_options(Cursor::new(hive_file), recovery()).unwrap();
+    assert!(!hive.recovered());
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::fixtures::fixture_path;
+use hivedigger::{
+    artifacts::{parse_sam_f, AccountFlag},
+    Hive, HiveError,
+};
+
+// Function to build an F value with the given RID, account control bits and times
+fn account_f(rid: u32, account_control: u16, last_logon: u64, password_last_set: u64, account_expires: u64) -> Vec<u8> {
+    let mut f = vec![0u8; 0x50];
+    f[0x08..0x10].copy_from_slice(&last_logon.to_le_bytes());
+    f[0x18..0x20].copy_from_slice(&password_last_set.to_le_bytes());
+    f[0x20..0x28].copy_from_slice(&account_expires.to_le_bytes());
+    f[0x30..0x34].copy_from_slice(&rid.to_le_bytes());
+    f[0x38..0x3a].copy_from_slice(&account_control.to_le_bytes());
+    f[0x40..0x42].copy_from_slice(&3u16.to_le_bytes());
+    f[0x42..0x44].copy_from_slice(&12u16.to_le_bytes());
+    f
+}
+
+#[test]
+fn administrator_f_value_decodes_from_the_fixture() {
+    let mut hive = Hive::open(&fixture_path("SAM")).unwrap();
+    let data = hive.value_as_bytes("SAM\\Domains\\Account\\Users\\000001F4", "F").unwrap();
+    let account = parse_sam_f(&data).unwrap();
+
+    assert_eq!(account.rid, 500);
+    assert_eq!(account.last_logon, Some(Utc.with_ymd_and_hms(2019, 4, 17, 18, 40, 0).unwrap()));
+    assert_eq!(account.password_last_set, None);
+    assert_eq!(account.account_control, 0x0210);
+    assert_eq!(account.flags, [AccountFlag::NormalAccount, AccountFlag::PasswordDoesNotExpire]);
+    assert_eq!(account.logon_count, 7);
+    assert!(!account.is_disabled());
+}
+
+#[test]
+fn disabled_account_with_times_and_unknown_bits() {
+    // 2024-03-01 12:00:00 and 2023-11-15 08:30:00 UTC
+    let f = account_f(0x3e9, 0x0015, 133537680000000000, 133445106000000000, 0x7FFF_FFFF_FFFF_FFFF);
+    let account = parse_sam_f(&f).unwrap();
+
+    assert_eq!(account.rid, 1001);
+    assert!(account.is_disabled());
+    assert_eq!(
+        account.flags,
+        [AccountFlag::Disabled, AccountFlag::PasswordNotRequired, AccountFlag::NormalAccount]
+    );
+    assert_eq!(account.last_logon, Some(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()));
+    assert_eq!(account.password_last_set, Some(Utc.with_ymd_and_hms(2023, 11, 15, 8, 30, 0).unwrap()));
+    assert_eq!(account.account_expires, None);
+    assert_eq!(account.last_failed_logon, None);
+    assert_eq!(account.failed_logon_count, 3);
+    assert_eq!(account.logon_count, 12);
+}
+
+#[test]
+fn short_f_value_is_invalid_data() {
+    assert!(matches!(parse_sam_f(&[0u8; 0x40]), Err(HiveError::InvalidValueData(_))));
+}
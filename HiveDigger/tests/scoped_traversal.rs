@@ -0,0 +1,56 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{
+    search::{SearchOptions, SearchPattern},
+    Hive, HiveOptions, RegFormat,
+};
+
+fn system_hive() -> Hive<std::fs::File> {
+    // The SYSTEM fixture's big data value is only reachable outside ControlSet001
+    let options = HiveOptions {
+        skip_errors: true,
+        ..HiveOptions::default()
+    };
+    Hive::open_with_options(&fixture_path("SYSTEM"), options).unwrap()
+}
+
+#[test]
+fn walk_from_yields_only_the_subtree() {
+    let mut hive = system_hive();
+    let control_set = hive.open_key("ControlSet001").unwrap();
+    let paths: Vec<String> = hive
+        .walk_from("ControlSet001", &control_set)
+        .map(|key| key.unwrap().0)
+        .collect();
+
+    assert_eq!(paths[0], "ControlSet001");
+    assert!(paths.contains(&"ControlSet001\\Control\\Lsa\\JD".to_string()));
+    assert!(paths.contains(&"ControlSet001\\Services\\W32Time".to_string()));
+    assert!(paths.iter().all(|path| path.starts_with("ControlSet001")));
+    assert_eq!(paths.len(), 18);
+}
+
+#[test]
+fn statistics_search_and_export_can_be_scoped() {
+    let mut hive = system_hive();
+    let control_set = hive.open_key("ControlSet002").unwrap();
+
+    let statistics = hive.statistics_from("ControlSet002", &control_set).unwrap();
+    assert_eq!((statistics.key_count, statistics.value_count), (3, 1));
+
+    // W32Time also exists in ControlSet001, which is out of scope
+    let pattern = SearchPattern::substring("w32time", true);
+    let matches = hive
+        .search_from(&pattern, SearchOptions::default(), "ControlSet002", &control_set)
+        .unwrap();
+    let paths: Vec<&str> = matches.iter().map(|search_match| search_match.path.as_str()).collect();
+    assert_eq!(paths, ["ControlSet002\\Services\\W32Time"]);
+
+    let mut export = Vec::new();
+    hive.export_reg_from(&mut export, "HKEY_LOCAL_MACHINE\\SYSTEM", RegFormat::RegEdit4, "ControlSet002", &control_set)
+        .unwrap();
+    let export = String::from_utf8(export).unwrap();
+    assert!(export.contains("[HKEY_LOCAL_MACHINE\\SYSTEM\\ControlSet002\\Services\\W32Time]\r\n\"Start\"=dword:00000004"));
+    assert!(!export.contains("ControlSet001"));
+}
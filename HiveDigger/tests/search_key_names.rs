@@ -0,0 +1,43 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{
+    search::{SearchOptions, SearchPattern},
+    Hive,
+};
+
+// Function to build a hive with a key whose name holds a backslash next to an ordinary path
+fn hive() -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Software")
+            .subkey(Key::new("Tools\\Run").dword("Hidden", 1))
+            .subkey(Key::new("Tools").subkey(Key::new("Run"))),
+    );
+    Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap()
+}
+
+// Function to search key names only, giving (path, matched text) pairs
+fn search_keys(hive: &mut Hive<Cursor<Vec<u8>>>, pattern: &SearchPattern) -> Vec<(String, String)> {
+    let options = SearchOptions {
+        keys: true,
+        values: false,
+    };
+    let matches = hive.search(pattern, options).unwrap();
+    matches.into_iter().map(|search_match| (search_match.path, search_match.text)).collect()
+}
+
+#[test]
+fn key_names_are_matched_whole_even_with_a_backslash() {
+    let mut hive = hive();
+
+    let matches = search_keys(&mut hive, &SearchPattern::substring("tools\\run", true));
+    assert_eq!(matches, [("Software\\Tools\\Run".to_string(), "Tools\\Run".to_string())]);
+
+    // Only one key is named "Run"; the other merely ends in it
+    let matches = search_keys(&mut hive, &SearchPattern::regex("^run$", true).unwrap());
+    assert_eq!(matches, [("Software\\Tools\\Run".to_string(), "Run".to_string())]);
+    let matches = search_keys(&mut hive, &SearchPattern::regex("^Tools", false).unwrap());
+    assert_eq!(matches.len(), 2);
+}
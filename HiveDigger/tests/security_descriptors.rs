@@ -0,0 +1,68 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError, SecurityDescriptor};
+
+fn build_hive_file() -> Vec<u8> {
+    let root = Key::new("ROOT")
+        .security(0)
+        .subkey(Key::new("Software").security(1).subkey(Key::new("Vendor").security(1)))
+        .subkey(Key::new("Secrets").security(2))
+        .subkey(Key::new("Unprotected"));
+    HiveBuilder::new()
+        .security_descriptor(&[1, 0, 4, 0x80])
+        .security_descriptor(&[1, 0, 4, 0x90, 0, 0, 0, 0])
+        .security_descriptor(&[1, 0, 0x14, 0x8c])
+        .build(&root)
+}
+
+fn descriptors(hive: &mut Hive<Cursor<Vec<u8>>>, path: &str) -> Vec<SecurityDescriptor> {
+    let key_node = hive.open_key(path).unwrap();
+    hive.security_descriptors(&key_node).map(Result::unwrap).collect()
+}
+
+#[test]
+fn the_sk_list_is_walked_once_around_from_a_keys_descriptor() {
+    let mut hive = Hive::from_reader(Cursor::new(build_hive_file())).unwrap();
+
+    let from_root = descriptors(&mut hive, "");
+    assert_eq!(from_root.len(), 3);
+    let reference_counts: Vec<u32> = from_root.iter().map(|sk| sk.reference_count).collect();
+    assert_eq!(reference_counts, [1, 2, 1]);
+    assert_eq!(from_root[1].descriptor, [1, 0, 4, 0x90, 0, 0, 0, 0]);
+    // Each record links forward to the next and back to the previous, around the circle
+    for (position, sk) in from_root.iter().enumerate() {
+        assert_eq!(sk.flink, from_root[(position + 1) % 3].offset);
+        assert_eq!(sk.blink, from_root[(position + 2) % 3].offset);
+    }
+
+    // Keys sharing a descriptor start from the same record, and every walk covers the whole list
+    let from_software = descriptors(&mut hive, "Software");
+    let from_vendor = descriptors(&mut hive, "Software\\Vendor");
+    assert_eq!(from_software, from_vendor);
+    assert_eq!(from_software[0], from_root[1]);
+    assert_eq!(from_software[2], from_root[0]);
+    assert_eq!(descriptors(&mut hive, "Secrets")[0], from_root[2]);
+
+    assert!(descriptors(&mut hive, "Unprotected").is_empty());
+}
+
+#[test]
+fn a_list_that_never_closes_is_reported() {
+    let mut hive_file = build_hive_file();
+    let mut hive = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap();
+    let offsets: Vec<u32> = descriptors(&mut hive, "").iter().map(|sk| sk.offset).collect();
+
+    // Point the last record's flink back at the second one instead of the first
+    let flink = 4096 + offsets[2] as usize + 4 + 4;
+    hive_file[flink..flink + 4].copy_from_slice(&offsets[1].to_le_bytes());
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let root = hive.root().unwrap();
+    let walked: Vec<_> = hive.security_descriptors(&root).collect();
+
+    assert_eq!(walked.len(), 4);
+    assert!(walked[..3].iter().all(Result::is_ok));
+    assert!(matches!(walked[3], Err(HiveError::SecurityListCycle { offset }) if offset == offsets[1]));
+}
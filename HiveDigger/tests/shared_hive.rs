@@ -0,0 +1,68 @@
+mod common;
+
+use std::thread;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, RegistryValue, SharedHive};
+
+// Key paths of the SYSTEM fixture, each with the name of a value it holds
+const LOOKUPS: [(&str, &str); 4] = [
+    ("Select", "Current"),
+    ("ControlSet001\\Control\\ComputerName\\ComputerName", "ComputerName"),
+    ("ControlSet001\\Services\\TermService", "Start"),
+    ("ControlSet002\\Services\\W32Time", "Start"),
+];
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn threads_resolve_different_keys_of_one_hive() {
+    assert_send_sync::<SharedHive>();
+    let shared = SharedHive::open(&fixture_path("SYSTEM")).unwrap();
+
+    let resolved: Vec<RegistryValue> = thread::scope(|scope| {
+        let handles: Vec<_> = LOOKUPS
+            .iter()
+            .map(|&(key_path, value_name)| {
+                let shared = shared.clone();
+                scope.spawn(move || {
+                    // Many lookups each, so the threads' reads interleave
+                    let mut value = None;
+                    for _ in 0..50 {
+                        let key_node = shared.open_key(key_path).unwrap();
+                        let (_, key_value) = shared
+                            .values(&key_node)
+                            .unwrap()
+                            .into_iter()
+                            .find(|(name, _)| name == value_name)
+                            .unwrap();
+                        value = Some(shared.read_value(&key_value).unwrap());
+                    }
+                    value.unwrap()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    for ((key_path, value_name), value) in LOOKUPS.into_iter().zip(resolved) {
+        assert_eq!(value, hive.value(key_path, value_name).unwrap(), "{key_path}");
+    }
+}
+
+#[test]
+fn views_have_independent_cursors() {
+    let shared = SharedHive::open(&fixture_path("SYSTEM")).unwrap();
+    let mut walking = shared.view();
+    let mut walk = walking.walk();
+    let first = walk.next().unwrap().unwrap().0;
+
+    // Lookups through the shared handle in the middle of a walk don't disturb it
+    assert!(shared.exists("ControlSet001\\Services\\TermService"));
+    let rest: Vec<String> = walk.map(|key| key.unwrap().0).collect();
+
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let expected: Vec<String> = hive.walk().map(|key| key.unwrap().0).collect();
+    assert_eq!([vec![first], rest].concat(), expected);
+}
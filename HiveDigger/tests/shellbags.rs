@@ -0,0 +1,120 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::fixtures::fixture_path;
+use hivedigger::{
+    artifacts::{parse_bagmru, parse_shell_item, ShellItem},
+    time::{dos_datetime_to_datetime, filetime_to_datetime},
+    Hive,
+};
+
+#[test]
+fn bagmru_entries_come_out_as_folder_paths_in_mru_order() {
+    let mut hive = Hive::open(&fixture_path("UsrClass.dat")).unwrap();
+    let entries = parse_bagmru(&mut hive).unwrap();
+
+    let summary = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.path.as_str(),
+                entry.key_path.as_str(),
+                entry.value_name.as_str(),
+                entry.mru_position,
+                entry.node_slot,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            ("[shell item 0x71]", "BagMRU", "1", Some(0), None),
+            ("My Computer", "BagMRU", "0", Some(1), Some(1)),
+            ("My Computer\\C:", "BagMRU\\0", "0", Some(0), Some(2)),
+            ("My Computer\\C:\\Users", "BagMRU\\0\\0", "0", Some(0), Some(3)),
+            ("My Computer\\C:\\Users\\analyst", "BagMRU\\0\\0\\0", "0", Some(0), Some(4)),
+            // Missing from its key's MRUListEx, so listed after the others without a position
+            ("My Computer\\C:\\Users\\Program Files", "BagMRU\\0\\0\\0", "1", None, None),
+        ]
+    );
+
+    assert_eq!(entries[1].last_written, Some(filetime_to_datetime(0x01d9b19d_00000000)));
+    assert_eq!(entries[4].last_written, Some(filetime_to_datetime(0x01d9b1a0_00000000)));
+    assert_eq!(entries[5].last_written, None);
+}
+
+#[test]
+fn shell_items_decode_by_class_type() {
+    let mut hive = Hive::open(&fixture_path("UsrClass.dat")).unwrap();
+    let entries = parse_bagmru(&mut hive).unwrap();
+
+    assert_eq!(
+        entries[1].item,
+        ShellItem::RootFolder {
+            clsid: "20D04FE0-3AEA-1069-A2D8-08002B30309D".to_string(),
+            name: Some("My Computer"),
+        }
+    );
+    assert_eq!(entries[2].item, ShellItem::Volume { name: "C:\\".to_string() });
+    assert_eq!(
+        entries[5].item,
+        ShellItem::FileEntry {
+            short_name: "PROGRA~1".to_string(),
+            long_name: Some("Program Files".to_string()),
+            is_directory: true,
+            modified: Some(Utc.with_ymd_and_hms(2019, 4, 17, 9, 0, 0).unwrap()),
+            created: Some(Utc.with_ymd_and_hms(2019, 4, 17, 9, 0, 0).unwrap()),
+            accessed: Some(Utc.with_ymd_and_hms(2019, 4, 17, 9, 0, 0).unwrap()),
+        }
+    );
+    let ShellItem::FileEntry { modified, created, .. } = &entries[4].item else {
+        panic!("expected a file entry, got {:?}", entries[4].item);
+    };
+    assert_eq!(*modified, Some(Utc.with_ymd_and_hms(2023, 7, 1, 12, 0, 0).unwrap()));
+    assert_eq!(*created, Some(Utc.with_ymd_and_hms(2019, 4, 17, 10, 0, 0).unwrap()));
+
+    // Class types this crate doesn't decode keep their bytes, without the list terminator
+    assert_eq!(
+        entries[0].item,
+        ShellItem::Unknown {
+            class_type: 0x71,
+            raw: vec![0x0c, 0x00, 0x71, 0x80, 0x00, 0x00, 0x00, 0x00, 0xde, 0xad, 0xbe, 0xef],
+        }
+    );
+}
+
+#[test]
+fn truncated_shell_items_are_kept_as_unknown() {
+    // A file entry cut off before its modification time
+    let item = [0x08, 0x00, 0x31, 0x00, 0x00, 0x00, 0x00, 0x00];
+    assert_eq!(
+        parse_shell_item(&item),
+        ShellItem::Unknown {
+            class_type: 0x31,
+            raw: item.to_vec(),
+        }
+    );
+    assert_eq!(parse_shell_item(&[]), ShellItem::Unknown { class_type: 0, raw: Vec::new() });
+}
+
+#[test]
+fn hive_without_bagmru_has_no_entries() {
+    let mut hive = Hive::open(&fixture_path("NTUSER.DAT")).unwrap();
+    assert!(parse_bagmru(&mut hive).unwrap().is_empty());
+}
+
+#[test]
+fn dos_dates_and_times_convert_to_utc() {
+    assert_eq!(
+        dos_datetime_to_datetime(0x56e1, 0x6000),
+        Some(Utc.with_ymd_and_hms(2023, 7, 1, 12, 0, 0).unwrap())
+    );
+    // Seconds are stored halved
+    assert_eq!(
+        dos_datetime_to_datetime(0x4e91, 0x4811),
+        Some(Utc.with_ymd_and_hms(2019, 4, 17, 9, 0, 34).unwrap())
+    );
+    assert_eq!(dos_datetime_to_datetime(0, 0), None);
+    // Month 13
+    assert_eq!(dos_datetime_to_datetime(0x01a1, 0), None);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use std::io::Cursor;
+
+use chrono::{TimeZone, Utc};
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{
+    artifacts::{parse_shimcache, parse_shimcache_data, ShimcacheFormat},
+    Hive, HiveError,
+};
+
+// 2021-01-01 00:00:00 UTC as a FILETIME
+const LAST_MODIFIED: u64 = 132_539_328_000_000_000;
+
+// Function to lay out a Windows 10 AppCompatCache value: a 0x34-byte header, then one "10ts"
+// record per path with its modification time and (ignored) shim data
+fn win10_cache(entries: &[(&str, u64)]) -> Vec<u8> {
+    let mut data = 0x34u32.to_le_bytes().to_vec();
+    data.resize(0x34, 0);
+    for (path, last_modified) in entries {
+        let path: Vec<u8> = path.encode_utf16().flat_map(|unit| unit.to_le_bytes()).collect();
+        let mut entry = (path.len() as u16).to_le_bytes().to_vec();
+        entry.extend(&path);
+        entry.extend(last_modified.to_le_bytes());
+        entry.extend(4u32.to_le_bytes());
+        entry.extend([0xaa; 4]);
+
+        data.extend(b"10ts");
+        data.extend(0u32.to_le_bytes());
+        data.extend((entry.len() as u32).to_le_bytes());
+        data.extend(entry);
+    }
+    data
+}
+
+// Function to build a SYSTEM hive whose current control set holds the given AppCompatCache data
+fn system_hive(cache: Vec<u8>) -> Hive<Cursor<Vec<u8>>> {
+    let app_compat_cache = Key::new("AppCompatCache").binary("AppCompatCache", &cache);
+    let control = Key::new("Control").subkey(Key::new("Session Manager").subkey(app_compat_cache));
+    let root = Key::new("ROOT")
+        .subkey(Key::new("ControlSet001").subkey(control))
+        .subkey(Key::new("Select").dword("Current", 1));
+    Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap()
+}
+
+#[test]
+fn windows_10_cache_entries_are_read_in_order() {
+    let cache = win10_cache(&[("C:\\Windows\\System32\\cmd.exe", LAST_MODIFIED), ("C:\\Users\\analyst\\tool.exe", 0)]);
+    let entries = parse_shimcache(&mut system_hive(cache)).unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].position, 0);
+    assert_eq!(entries[0].path, "C:\\Windows\\System32\\cmd.exe");
+    assert_eq!(entries[0].last_modified, Some(Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()));
+    assert_eq!(entries[0].insertion_flags, None);
+    assert_eq!(entries[1].position, 1);
+    assert_eq!(entries[1].path, "C:\\Users\\analyst\\tool.exe");
+    assert_eq!(entries[1].last_modified, None);
+}
+
+#[test]
+fn format_is_detected_from_the_header() {
+    let (format, entries) = parse_shimcache_data(&win10_cache(&[("a.exe", LAST_MODIFIED)])).unwrap();
+    assert_eq!(format, ShimcacheFormat::Win10);
+    assert_eq!(entries[0].path, "a.exe");
+
+    assert!(matches!(parse_shimcache_data(&[0x12, 0x34, 0x56, 0x78]), Err(HiveError::InvalidValueData(_))));
+}
+
+#[test]
+fn truncated_or_missing_cache_is_an_error() {
+    let mut cache = win10_cache(&[("C:\\Windows\\notepad.exe", LAST_MODIFIED)]);
+    cache.truncate(cache.len() - 10);
+    assert!(matches!(parse_shimcache(&mut system_hive(cache)), Err(HiveError::InvalidValueData(_))));
+
+    let root = Key::new("ROOT").subkey(Key::new("ControlSet001")).subkey(Key::new("Select").dword("Current", 1));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    assert!(parse_shimcache(&mut hive).is_err());
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError, HiveOptions};
+
+// Function to build a hive whose root has three subkeys, the middle one with a child of its own,
+// then break the middle one's key node signature
+fn partly_corrupt_hive() -> Vec<u8> {
+    let root = Key::new("ROOT")
+        .subkey(Key::new("Alpha").dword("Start", 1))
+        .subkey(Key::new("Broken").subkey(Key::new("Hidden")))
+        .subkey(Key::new("Gamma").subkey(Key::new("Delta")));
+    let mut hive_file = HiveBuilder::new().build(&root);
+    let broken_offset = Hive::from_reader(Cursor::new(hive_file.clone())).unwrap().open_key("Broken").unwrap().offset;
+    let signature = 4096 + broken_offset as usize + 4;
+    hive_file[signature..signature + 2].copy_from_slice(b"xx");
+    hive_file
+}
+
+fn skip_errors() -> HiveOptions {
+    HiveOptions {
+        skip_errors: true,
+        ..HiveOptions::default()
+    }
+}
+
+#[test]
+fn walk_carries_on_past_a_corrupt_branch() {
+    let mut hive = Hive::from_reader_with_options(Cursor::new(partly_corrupt_hive()), skip_errors()).unwrap();
+    let keys: Vec<_> = hive.walk().collect();
+
+    let paths: Vec<&str> = keys.iter().filter_map(|key| key.as_ref().ok()).map(|(path, _)| path.as_str()).collect();
+    assert_eq!(paths, ["", "Alpha", "Gamma", "Gamma\\Delta"]);
+    let errors: Vec<&HiveError> = keys.iter().filter_map(|key| key.as_ref().err()).collect();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], HiveError::Read { record: "nk", .. }));
+}
+
+#[test]
+fn subkeys_yield_the_error_in_place_of_the_corrupt_key() {
+    let mut hive = Hive::from_reader_with_options(Cursor::new(partly_corrupt_hive()), skip_errors()).unwrap();
+    let root = hive.root().unwrap();
+    let subkeys: Vec<_> = hive.subkeys(&root).collect();
+
+    assert_eq!(subkeys.len(), 3);
+    assert_eq!(subkeys[0].as_ref().unwrap().0, "Alpha");
+    assert!(subkeys[1].is_err());
+    assert_eq!(subkeys[2].as_ref().unwrap().0, "Gamma");
+}
+
+#[test]
+fn without_skip_errors_the_first_error_ends_the_walk() {
+    let mut hive = Hive::from_reader(Cursor::new(partly_corrupt_hive())).unwrap();
+    let keys: Vec<_> = hive.walk().collect();
+
+    assert_eq!(keys.len(), 3);
+    assert_eq!(keys[1].as_ref().unwrap().0, "Alpha");
+    assert!(keys[2].is_err());
+}
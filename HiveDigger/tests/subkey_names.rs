@@ -0,0 +1,33 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, ListKind};
+use common::fixtures::fixture_path;
+use hivedigger::Hive;
+
+#[test]
+fn names_come_in_stored_order() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let services = hive.open_key("ControlSet001\\Services").unwrap();
+    assert_eq!(hive.subkey_names(&services).unwrap(), ["EventLog", "Tcpip", "TermService", "W32Time"]);
+
+    // An li leaf is kept in the order the builder wrote it, not re-sorted on read
+    let root = Key::new("ROOT").subkey(
+        Key::new("Parent")
+            .list(ListKind::Li)
+            .subkey(Key::new("Zeta"))
+            .subkey(Key::new("alpha"))
+            .subkey(Key::new("Mu")),
+    );
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let parent = hive.open_key("Parent").unwrap();
+    assert_eq!(hive.subkey_names(&parent).unwrap(), ["Zeta", "alpha", "Mu"]);
+}
+
+#[test]
+fn key_without_subkeys_has_no_names() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let w32time = hive.open_key("ControlSet002\\Services\\W32Time").unwrap();
+    assert!(hive.subkey_names(&w32time).unwrap().is_empty());
+}
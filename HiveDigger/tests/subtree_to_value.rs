@@ -0,0 +1,79 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::Hive;
+use serde_json::json;
+
+#[test]
+fn subtree_to_value_nests_keys_with_their_values() {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Services").subkey(
+            Key::new("W32Time")
+                .expand_sz("ImagePath", "%SystemRoot%\\system32\\svchost.exe")
+                .dword("Start", 3)
+                .subkey(
+                    Key::new("Parameters")
+                        .multi_sz("Servers", &["time.windows.com", "pool.ntp.org"])
+                        .binary("Blob", &[0xde, 0xad]),
+                ),
+        ),
+    );
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let services = hive.open_key("Services").unwrap();
+    let subtree = hive.subtree_to_value(&services).unwrap();
+
+    let last_written = "2019-04-17T18:40:00+00:00";
+    assert_eq!(
+        subtree,
+        json!({
+            "last_written": last_written,
+            "values": {},
+            "subkeys": {
+                "W32Time": {
+                    "last_written": last_written,
+                    "values": {
+                        "ImagePath": { "type": "REG_EXPAND_SZ", "data": "%SystemRoot%\\system32\\svchost.exe" },
+                        "Start": { "type": "REG_DWORD", "data": 3 },
+                    },
+                    "subkeys": {
+                        "Parameters": {
+                            "last_written": last_written,
+                            "values": {
+                                "Servers": { "type": "REG_MULTI_SZ", "data": ["time.windows.com", "pool.ntp.org"] },
+                                "Blob": { "type": "REG_BINARY", "data": "dead" },
+                            },
+                            "subkeys": {},
+                        },
+                    },
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn sibling_names_that_would_collide_as_paths_stay_apart() {
+    // "a\b" is a single key name next to "a", whose own subkey is "b"
+    let root = Key::new("ROOT").subkey(
+        Key::new("Run")
+            .subkey(Key::new("a").dword("Depth", 1).subkey(Key::new("b").dword("Depth", 2)))
+            .subkey(Key::new("a\\b").dword("Depth", 3))
+            .subkey(Key::new("Case").dword("Upper", 1))
+            .subkey(Key::new("case").dword("Lower", 1)),
+    );
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let run = hive.open_key("Run").unwrap();
+    let subtree = hive.subtree_to_value(&run).unwrap();
+    let subkeys = &subtree["subkeys"];
+
+    let names: Vec<&String> = subkeys.as_object().unwrap().keys().collect();
+    assert_eq!(names, ["Case", "a", "a\\b", "case"]);
+    assert_eq!(subkeys["a"]["values"]["Depth"]["data"], 1);
+    assert_eq!(subkeys["a"]["subkeys"]["b"]["values"]["Depth"]["data"], 2);
+    assert_eq!(subkeys["a\\b"]["values"]["Depth"]["data"], 3);
+    assert_eq!(subkeys["a\\b"]["subkeys"], json!({}));
+    assert_eq!(subkeys["Case"]["values"], json!({ "Upper": { "type": "REG_DWORD", "data": 1 } }));
+    assert_eq!(subkeys["case"]["values"], json!({ "Lower": { "type": "REG_DWORD", "data": 1 } }));
+}
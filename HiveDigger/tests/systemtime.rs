@@ -0,0 +1,49 @@
+use chrono::{TimeZone, Utc};
+use hivedigger::{
+    time::{filetime_to_datetime, systemtime_to_datetime},
+    HiveError,
+};
+
+// Function to lay out SYSTEMTIME fields as stored: eight little-endian u16s
+fn systemtime(fields: [u16; 8]) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    for (chunk, field) in bytes.chunks_exact_mut(2).zip(fields) {
+        chunk.copy_from_slice(&field.to_le_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn known_systemtime_converts() {
+    // 2023-03-12 18:45:10.250, a Sunday, as a NetworkList DateCreated blob
+    let blob = [
+        0xe7, 0x07, 0x03, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x12, 0x00, 0x2d, 0x00, 0x0a, 0x00, 0xfa, 0x00,
+    ];
+    let expected = Utc.with_ymd_and_hms(2023, 3, 12, 18, 45, 10).unwrap() + chrono::Duration::milliseconds(250);
+    assert_eq!(systemtime_to_datetime(&blob).unwrap(), expected);
+
+    // The same bytes read as a FILETIME give a date nowhere near it
+    let misread = filetime_to_datetime(u64::from_le_bytes(blob[..8].try_into().unwrap()));
+    assert_ne!(misread.date_naive(), expected.date_naive());
+}
+
+#[test]
+fn fields_out_of_range_are_rejected() {
+    for (fields, field) in [
+        ([0, 0, 0, 0, 0, 0, 0, 0], "year"),
+        ([2023, 13, 0, 1, 0, 0, 0, 0], "month"),
+        ([2023, 3, 7, 12, 0, 0, 0, 0], "day of week"),
+        ([2023, 2, 3, 30, 0, 0, 0, 0], "day"),
+        ([2023, 3, 0, 12, 24, 0, 0, 0], "hour"),
+        ([2023, 3, 0, 12, 0, 60, 0, 0], "minute"),
+        ([2023, 3, 0, 12, 0, 0, 60, 0], "second"),
+        ([2023, 3, 0, 12, 0, 0, 0, 1000], "milliseconds"),
+    ] {
+        match systemtime_to_datetime(&systemtime(fields)) {
+            Err(HiveError::InvalidValueData(message)) => {
+                assert!(message.contains(&format!("SYSTEMTIME {} ", field)), "{}", message)
+            }
+            other => panic!("expected {} to be rejected, got {:?}", field, other),
+        }
+    }
+}
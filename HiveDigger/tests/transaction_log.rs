@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+
+use hivedigger::log::{LogFormat, TransactionLog};
+
+// Function to write the first sector of a log's base block: the only part a log stores
+fn log_base_block(file_type: u32, sequence_number: u32, hive_bins_data_size: u32) -> Vec<u8> {
+    let mut base_block = vec![0u8; 512];
+    base_block[0..4].copy_from_slice(b"regf");
+    base_block[4..8].copy_from_slice(&sequence_number.to_le_bytes());
+    base_block[8..12].copy_from_slice(&(sequence_number - 1).to_le_bytes());
+    base_block[20..24].copy_from_slice(&1u32.to_le_bytes());
+    base_block[24..28].copy_from_slice(&5u32.to_le_bytes());
+    base_block[28..32].copy_from_slice(&file_type.to_le_bytes());
+    base_block[32..36].copy_from_slice(&1u32.to_le_bytes());
+    base_block[40..44].copy_from_slice(&hive_bins_data_size.to_le_bytes());
+    base_block[44..48].copy_from_slice(&1u32.to_le_bytes());
+    base_block
+}
+
+// Function to write a new format "HvLE" log entry carrying the given (offset, data) pages
+fn log_entry(sequence_number: u32, pages: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let data_size: usize = pages.iter().map(|(_, data)| data.len()).sum();
+    let size = (40 + 8 * pages.len() + data_size).div_ceil(512) * 512;
+    let mut entry = b"HvLE".to_vec();
+    entry.extend((size as u32).to_le_bytes());
+    entry.extend(0u32.to_le_bytes());
+    entry.extend(sequence_number.to_le_bytes());
+    entry.extend(8192u32.to_le_bytes());
+    entry.extend((pages.len() as u32).to_le_bytes());
+    entry.resize(40, 0);
+    for (offset, data) in pages {
+        entry.extend(offset.to_le_bytes());
+        entry.extend((data.len() as u32).to_le_bytes());
+    }
+    for (_, data) in pages {
+        entry.extend(data);
+    }
+    entry.resize(size, 0);
+    entry
+}
+
+// Function to write a log to a file of its own under the temporary directory
+fn write_log(name: &str, log: &[u8]) -> PathBuf {
+    let log_path = std::env::temp_dir().join(format!("hivedigger-{}-{}.LOG1", name, std::process::id()));
+    std::fs::write(&log_path, log).unwrap();
+    log_path
+}
+
+#[test]
+fn new_format_entries_are_read_in_sequence() {
+    let mut log = log_base_block(6, 7, 8192);
+    log.extend(log_entry(5, &[(0, vec![0xaa; 4096])]));
+    log.extend(log_entry(6, &[(4096, vec![0xbb; 512]), (7680, vec![0xcc; 512])]));
+    // Left over from an earlier cycle: its sequence number doesn't follow on
+    log.extend(log_entry(2, &[(0, vec![0xdd; 512])]));
+    let log_path = write_log("new", &log);
+
+    let mut transaction_log = TransactionLog::open(&log_path).unwrap();
+    assert_eq!(transaction_log.format(), LogFormat::New);
+    assert_eq!(transaction_log.primary_sequence_number(), 7);
+    assert_eq!(transaction_log.secondary_sequence_number(), 6);
+    assert_eq!(transaction_log.hive_bins_data_size(), 8192);
+
+    let entries = transaction_log.entries().collect::<Result<Vec<_>, _>>().unwrap();
+    std::fs::remove_file(&log_path).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].file_offset, 512);
+    assert_eq!(entries[0].sequence_number, 5);
+    assert_eq!(entries[1].sequence_number, 6);
+    let pages: Vec<(u32, usize, u8)> = entries[1]
+        .dirty_pages
+        .iter()
+        .map(|page| (page.offset, page.data.len(), page.data[0]))
+        .collect();
+    assert_eq!(pages, [(4096, 512, 0xbb), (7680, 512, 0xcc)]);
+}
+
+#[test]
+fn old_format_dirty_vector_is_one_entry() {
+    let mut log = log_base_block(1, 3, 8192);
+    // One bit per 512-byte page of the 8192 bytes of hive bins data: pages 0 and 9 are dirty
+    log.extend(b"DIRT");
+    log.extend([0b0000_0001, 0b0000_0010]);
+    log.resize(1024, 0);
+    log.extend([0x11; 512]);
+    log.extend([0x99; 512]);
+    let log_path = write_log("old", &log);
+
+    let mut transaction_log = TransactionLog::open(&log_path).unwrap();
+    assert_eq!(transaction_log.format(), LogFormat::Old);
+    let entries = transaction_log.entries().collect::<Result<Vec<_>, _>>().unwrap();
+    std::fs::remove_file(&log_path).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].sequence_number, 3);
+    let pages: Vec<(u32, u8)> = entries[0].dirty_pages.iter().map(|page| (page.offset, page.data[0])).collect();
+    assert_eq!(pages, [(0, 0x11), (4608, 0x99)]);
+}
+
+#[test]
+fn oversized_entry_fields_fail_without_allocating_them() {
+    // An entry claiming almost 4 GiB and half a billion dirty pages in a one-sector file
+    let mut entry = log_entry(5, &[(0, vec![0xaa; 256])]);
+    entry[4..8].copy_from_slice(&0xFFFF_FE00u32.to_le_bytes());
+    entry[20..24].copy_from_slice(&0x1FFF_FF00u32.to_le_bytes());
+    let mut log = log_base_block(6, 7, 8192);
+    log.extend(entry);
+    let log_path = write_log("oversized", &log);
+
+    let mut transaction_log = TransactionLog::open(&log_path).unwrap();
+    let entries: Vec<_> = transaction_log.entries().collect();
+    std::fs::remove_file(&log_path).unwrap();
+    let [Err(error)] = &entries[..] else {
+        panic!("expected one error, got {:?}", entries);
+    };
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn primary_hive_is_not_a_log() {
+    let log_path = write_log("primary", &log_base_block(0, 1, 4096));
+    let error = TransactionLog::open(&log_path).err().unwrap();
+    std::fs::remove_file(&log_path).unwrap();
+    assert_eq!(error.to_string(), "File type 0 is not a transaction log");
+}
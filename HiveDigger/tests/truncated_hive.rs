@@ -0,0 +1,21 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveError};
+
+#[test]
+fn truncated_key_node_error_names_the_record_and_offset() {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    let root_offset = u32::from_le_bytes(hive_file[36..40].try_into().unwrap());
+    // Cut the file off in the middle of the root key node
+    hive_file.truncate(4096 + root_offset as usize + 4 + 10);
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+
+    let Err(error) = hive.open_key("ControlSet001\\Services") else {
+        panic!("expected the truncated key node to fail");
+    };
+    assert!(matches!(error, HiveError::Read { record: "nk", offset, .. } if offset == root_offset));
+    assert_eq!(error.to_string(), format!("Failed reading nk at {:#x}: failed to fill whole buffer", root_offset));
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, RegistryValue};
+
+#[test]
+fn clone_answers_lookups_in_the_middle_of_a_walk() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let mut clone = hive.try_clone().unwrap();
+    let expected: Vec<String> = Hive::open(&fixture_path("SYSTEM"))
+        .unwrap()
+        .walk()
+        .map(|key| key.unwrap().0)
+        .collect();
+
+    // Both handles share one file position, so each lookup moves it under the walk
+    let mut walked = Vec::new();
+    for key in hive.walk() {
+        let (path, _) = key.unwrap();
+        let w32time = clone.open_key("ControlSet001\\Services\\W32Time").unwrap();
+        assert_eq!(clone.subkey_names(&w32time).unwrap(), Vec::<String>::new());
+        assert_eq!(clone.value("Select", "Current").unwrap(), RegistryValue::Dword(1));
+        walked.push(path);
+    }
+    assert_eq!(walked, expected);
+}
+
+#[test]
+fn clone_keeps_the_header_and_is_independent_of_the_original() {
+    let hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    let mut clone = hive.try_clone().unwrap();
+    assert_eq!(clone.file_name(), hive.file_name());
+    assert_eq!(clone.secondary_seq_num(), hive.secondary_seq_num());
+
+    drop(hive);
+    let lsa = clone.open_key("ControlSet001\\Control\\Lsa").unwrap();
+    let mut names = clone.subkey_names(&lsa).unwrap();
+    names.sort();
+    assert_eq!(names, ["Data", "GBG", "JD", "Skew1"]);
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, HiveError};
+
+#[test]
+fn typed_helpers_coerce_values_of_their_type() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+
+    assert_eq!(
+        hive.value_as_string("ControlSet001\\Control\\ComputerName\\ComputerName", "ComputerName").unwrap(),
+        "WORKSTATION-01"
+    );
+    // REG_EXPAND_SZ comes back unexpanded
+    assert_eq!(
+        hive.value_as_string("ControlSet001\\Control\\Session Manager\\Environment", "windir").unwrap(),
+        "%SystemRoot%"
+    );
+    assert_eq!(hive.value_as_u32("Select", "LastKnownGood").unwrap(), 2);
+    assert_eq!(hive.value_as_u32("Setup", "BigEndian").unwrap(), 0x12345678);
+    assert_eq!(hive.value_as_u64("Setup", "Qword").unwrap(), 0x01d70000_12345678);
+    assert_eq!(hive.value_as_bytes("Setup", "Short").unwrap(), [0xde, 0xad]);
+}
+
+#[test]
+fn typed_helpers_reject_values_of_another_type() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+
+    let error = hive.value_as_u32("Setup", "Multi").unwrap_err();
+    assert!(matches!(error, HiveError::TypeMismatch { found: 7, .. }));
+    assert_eq!(error.to_string(), "Expected a REG_DWORD or REG_DWORD_BIG_ENDIAN value, found REG_MULTI_SZ");
+
+    assert!(matches!(
+        hive.value_as_string("Select", "Current"),
+        Err(HiveError::TypeMismatch { found: 4, .. })
+    ));
+    assert!(matches!(hive.value_as_u64("Setup", "Short"), Err(HiveError::TypeMismatch { found: 3, .. })));
+    // DWORDs aren't widened to a QWORD
+    let error = hive.value_as_u64("Select", "Current").unwrap_err();
+    assert!(matches!(error, HiveError::TypeMismatch { found: 4, .. }));
+    assert_eq!(error.to_string(), "Expected a REG_QWORD value, found REG_DWORD");
+    assert!(matches!(hive.value_as_u64("Setup", "BigEndian"), Err(HiveError::TypeMismatch { found: 5, .. })));
+    assert!(matches!(hive.value_as_bytes("Setup", ""), Err(HiveError::TypeMismatch { found: 1, .. })));
+    assert!(matches!(hive.value_as_u32("Select", "Missing"), Err(HiveError::ValueNotFound { .. })));
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError, HiveOptions, RegFormat, RegistryValue};
+
+const VENDOR_TYPE: u32 = 0x12345678;
+
+fn build_hive() -> Vec<u8> {
+    let root = Key::new("ROOT").subkey(
+        Key::new("Vendor")
+            .value("Blob", VENDOR_TYPE, vec![0xde, 0xad, 0xbe, 0xef, 0x01])
+            .dword("Version", 2),
+    );
+    HiveBuilder::new().build(&root)
+}
+
+#[test]
+fn unknown_types_keep_their_bytes_and_type() {
+    let mut hive = Hive::from_reader(Cursor::new(build_hive())).unwrap();
+    let value = hive.value("Vendor", "Blob").unwrap();
+
+    assert_eq!(
+        value,
+        RegistryValue::Unknown {
+            type_id: VENDOR_TYPE,
+            raw: vec![0xde, 0xad, 0xbe, 0xef, 0x01],
+        }
+    );
+    assert_eq!(value.data_type(), VENDOR_TYPE);
+    assert_eq!(value.to_string(), "de ad be ef 01 (5 bytes)");
+
+    let vendor = hive.open_key("Vendor").unwrap();
+    assert_eq!(hive.values_map(&vendor).unwrap().len(), 2);
+    let all_values: Vec<_> = hive.all_values().collect::<Result<_, _>>().unwrap();
+    assert_eq!(all_values.len(), 2);
+}
+
+#[test]
+fn strict_mode_rejects_unknown_types() {
+    let options = HiveOptions { strict: true, ..HiveOptions::default() };
+    let mut hive = Hive::from_reader_with_options(Cursor::new(build_hive()), options).unwrap();
+
+    assert!(matches!(hive.value("Vendor", "Blob"), Err(HiveError::UnknownValueType(VENDOR_TYPE))));
+    assert_eq!(hive.value("Vendor", "Version").unwrap(), RegistryValue::Dword(2));
+
+    // Exports work from the raw data, so an odd value doesn't stop them even in strict mode
+    let mut export = Vec::new();
+    hive.export_reg(&mut export, "HKEY_LOCAL_MACHINE\\VENDOR", RegFormat::RegEdit4).unwrap();
+    assert!(String::from_utf8(export).unwrap().contains("\"Blob\"=hex(12345678):de,ad,be,ef,01"));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key, ListKind};
+use common::fixtures::{fixture_path, FIXTURES};
+use hivedigger::{Hive, ValidationIssue};
+
+const PAST_THE_BINS: u32 = 0x7fff_fff0;
+
+// Function to overwrite a u32 field `field` bytes into the cell data at `offset`
+fn patch_u32(hive_file: &mut [u8], offset: u32, field: usize, value: u32) {
+    let position = 4096 + offset as usize + 4 + field;
+    hive_file[position..position + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+#[test]
+fn fixtures_validate_cleanly() {
+    for fixture in FIXTURES {
+        let report = Hive::open(&fixture_path(fixture.name)).unwrap().validate().unwrap();
+        assert!(report.is_valid(), "{}: {:?}", fixture.name, report.issues);
+    }
+}
+
+#[test]
+fn every_seeded_defect_is_reported() {
+    let mut hive_file = std::fs::read(fixture_path("SYSTEM")).unwrap();
+    let (services, time_zone_key_name, jd) = {
+        let mut hive = Hive::from_bytes(&hive_file).unwrap();
+        let services = hive.open_key("ControlSet001\\Services").unwrap();
+        let time_zone = hive.open_key("ControlSet001\\Control\\TimeZoneInformation").unwrap();
+        let time_zone_key_name = hive
+            .values(&time_zone)
+            .map(|value| value.unwrap())
+            .find(|(name, _)| name == "TimeZoneKeyName")
+            .unwrap()
+            .1;
+        let jd = hive.open_key("ControlSet001\\Control\\Lsa\\JD").unwrap();
+        (services.offset, time_zone_key_name.offset, jd.offset)
+    };
+
+    // One subkey more than Services lists, a value whose data lies past the bins and a class
+    // name offset past the bins
+    patch_u32(&mut hive_file, services, 20, 5);
+    patch_u32(&mut hive_file, time_zone_key_name, 8, PAST_THE_BINS);
+    patch_u32(&mut hive_file, jd, 48, PAST_THE_BINS);
+    let report = Hive::from_reader(Cursor::new(hive_file)).unwrap().validate().unwrap();
+
+    assert!(!report.is_valid());
+    let mut issues = report.issues;
+    issues.sort_by_key(|issue| issue.to_string());
+    assert_eq!(
+        issues,
+        [
+            ValidationIssue::OffsetOutOfBounds {
+                path: "ControlSet001\\Control\\Lsa\\JD".to_string(),
+                field: "class name offset",
+                offset: PAST_THE_BINS,
+            },
+            ValidationIssue::OffsetOutOfBounds {
+                path: "ControlSet001\\Control\\TimeZoneInformation".to_string(),
+                field: "value data offset",
+                offset: PAST_THE_BINS,
+            },
+            ValidationIssue::SubkeyCountMismatch {
+                path: "ControlSet001\\Services".to_string(),
+                recorded: 5,
+                found: 4,
+            },
+        ]
+    );
+}
+
+#[test]
+fn zero_subkey_count_over_a_hash_leaf_is_reported() {
+    for list_kind in [ListKind::Lf, ListKind::Lh] {
+        let root = Key::new("ROOT").subkey(
+            Key::new("Parent")
+                .list(list_kind)
+                .subkey(Key::new("One"))
+                .subkey(Key::new("Two")),
+        );
+        let mut hive_file = HiveBuilder::new().build(&root);
+        let parent = Hive::from_bytes(&hive_file).unwrap().open_key("Parent").unwrap().offset;
+        patch_u32(&mut hive_file, parent, 20, 0);
+        let report = Hive::from_reader(Cursor::new(hive_file)).unwrap().validate().unwrap();
+
+        assert_eq!(
+            report.issues,
+            [ValidationIssue::SubkeyCountMismatch {
+                path: "Parent".to_string(),
+                recorded: 0,
+                found: 2,
+            }],
+            "{:?}",
+            list_kind
+        );
+    }
+}
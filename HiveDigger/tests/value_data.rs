@@ -0,0 +1,48 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{
+    builder::{utf16z, HiveBuilder, Key},
+    fixtures::fixture_path,
+};
+use hivedigger::{Hive, RegistryValue};
+
+#[test]
+fn external_value_data_starts_after_the_cell_header() {
+    let root = Key::new("ROOT").subkey(Key::new("ComputerName").sz("ComputerName", "WORKSTATION-01"));
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let key_node = hive.open_key("ComputerName").unwrap();
+    let (_, key_value) = hive.values(&key_node).next().unwrap().unwrap();
+
+    assert!(!key_value.is_resident());
+    assert_eq!(hive.value_data(&key_value).unwrap(), utf16z("WORKSTATION-01"));
+}
+
+#[test]
+fn external_and_resident_values_decode_from_a_fixture() {
+    let mut hive = Hive::open(&fixture_path("SYSTEM")).unwrap();
+    assert_eq!(
+        hive.value("ControlSet001\\Control\\ComputerName\\ComputerName", "ComputerName").unwrap(),
+        RegistryValue::String("WORKSTATION-01".to_string())
+    );
+    assert_eq!(hive.value("Select", "Current").unwrap(), RegistryValue::Dword(1));
+    assert_eq!(
+        hive.value("Setup", "Multi").unwrap(),
+        RegistryValue::MultiString(vec!["a".to_string(), "bc".to_string()])
+    );
+}
+
+#[test]
+fn resident_data_with_an_oversized_length_is_clamped() {
+    let root = Key::new("ROOT").dword("Count", 7);
+    let mut hive_file = HiveBuilder::new().build(&root);
+    // Claim 16 resident bytes where only the 4-byte data offset field exists
+    let vk_position = hive_file.windows(4).position(|window| window == b"vk\x05\x00").unwrap();
+    hive_file[vk_position + 4..vk_position + 8].copy_from_slice(&0x80000010u32.to_le_bytes());
+
+    let mut hive = Hive::from_reader(Cursor::new(hive_file)).unwrap();
+    let root = hive.root().unwrap();
+    let (_, key_value) = hive.values(&root).next().unwrap().unwrap();
+    assert_eq!(hive.value_data(&key_value).unwrap(), 7u32.to_le_bytes());
+}
@@ -0,0 +1,38 @@
+use hivedigger::RegistryValue;
+
+#[test]
+fn each_variant_is_displayed_for_people() {
+    let cases = [
+        (RegistryValue::String("explorer.exe".to_string()), "explorer.exe"),
+        (RegistryValue::ExpandString("%SystemRoot%\\system32".to_string()), "%SystemRoot%\\system32"),
+        (RegistryValue::Link("\\Registry\\Machine\\System\\ControlSet001".to_string()), "\\Registry\\Machine\\System\\ControlSet001"),
+        (RegistryValue::Dword(3), "0x00000003 (3)"),
+        (RegistryValue::DwordBigEndian(0x01020304), "0x01020304 (16909060)"),
+        (RegistryValue::Qword(1 << 40), "0x0000010000000000 (1099511627776)"),
+        (RegistryValue::MultiString(vec!["one".to_string(), "two".to_string()]), "one, two"),
+        (RegistryValue::MultiString(Vec::new()), ""),
+        (RegistryValue::Binary(vec![0xde, 0xad, 0xbe, 0xef]), "de ad be ef (4 bytes)"),
+        (RegistryValue::None(Vec::new()), "(0 bytes)"),
+        (RegistryValue::Other { data_type: 8, data: vec![1, 2] }, "01 02 (2 bytes)"),
+        (RegistryValue::Unknown { type_id: 0x12345678, raw: vec![0xff] }, "ff (1 bytes)"),
+    ];
+
+    for (value, expected) in cases {
+        assert_eq!(value.to_string(), expected, "{:?}", value);
+    }
+}
+
+#[test]
+fn long_binary_is_cut_to_a_preview_with_its_length() {
+    let value = RegistryValue::Binary((0..40).collect());
+    assert_eq!(value.to_string(), "00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f ... (40 bytes)");
+}
+
+#[test]
+fn debug_keeps_the_variant_and_full_data() {
+    let value = RegistryValue::Binary((0..40).collect());
+    let debug = format!("{:?}", value);
+    assert!(debug.starts_with("Binary(["));
+    assert!(debug.contains("39"));
+    assert_eq!(format!("{:?}", RegistryValue::Dword(3)), "Dword(3)");
+}
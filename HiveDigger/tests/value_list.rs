@@ -0,0 +1,34 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::extract_syskey_from_reader;
+
+// Function to build a hive whose Lsa key lists JD among other values, at the given position
+fn hive_with_jd_at(position: usize) -> Vec<u8> {
+    let mut lsa = Key::new("Lsa");
+    for index in 0..3 {
+        lsa = if index == position {
+            lsa.binary("JD", &[0x01, 0x23, 0xab, 0xcd])
+        } else {
+            lsa.dword(&format!("Other{}", index), index as u32)
+        };
+    }
+    let root = Key::new("ROOT").subkey(Key::new("CurrentControlSet").subkey(Key::new("Control").subkey(lsa)));
+    HiveBuilder::new().build(&root)
+}
+
+#[test]
+fn second_value_of_a_multi_value_key_is_found() {
+    let mut hive_file = Cursor::new(hive_with_jd_at(1));
+    assert_eq!(extract_syskey_from_reader(&mut hive_file).unwrap(), [0x01, 0x23, 0xab, 0xcd]);
+}
+
+#[test]
+fn first_and_last_values_are_found() {
+    for position in [0, 2] {
+        let mut hive_file = Cursor::new(hive_with_jd_at(position));
+        assert_eq!(extract_syskey_from_reader(&mut hive_file).unwrap(), [0x01, 0x23, 0xab, 0xcd], "{}", position);
+    }
+}
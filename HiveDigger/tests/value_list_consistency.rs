@@ -0,0 +1,62 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{Hive, HiveError, RegFormat};
+
+// Function to build a hive with a key of two values whose value count and value list offset
+// are then replaced with the given ones (None keeps the list offset)
+fn hive_with_value_list(number_of_key_values: u32, list_offset: Option<u32>) -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("ROOT").subkey(Key::new("Key").dword("A", 1).sz("B", "b"));
+    let mut hive_file = HiveBuilder::new().build(&root);
+    let key_offset = Hive::from_bytes(&hive_file).unwrap().open_key("Key").unwrap().offset;
+
+    let nk = 4096 + key_offset as usize + 4;
+    hive_file[nk + 36..nk + 40].copy_from_slice(&number_of_key_values.to_le_bytes());
+    if let Some(list_offset) = list_offset {
+        hive_file[nk + 40..nk + 44].copy_from_slice(&list_offset.to_le_bytes());
+    }
+    Hive::from_reader(Cursor::new(hive_file)).unwrap()
+}
+
+// Function to check that a key reads as having no values at all, without error
+fn assert_no_values(mut hive: Hive<Cursor<Vec<u8>>>) {
+    let key_node = hive.open_key("Key").unwrap();
+    assert_eq!(hive.values(&key_node).count(), 0);
+    assert_eq!(hive.count_values(&key_node), 0);
+    assert!(matches!(hive.value("Key", "A"), Err(HiveError::ValueNotFound { .. })));
+
+    let mut export = Vec::new();
+    hive.export_reg(&mut export, "HKEY_LOCAL_MACHINE\\TEST", RegFormat::RegEdit4).unwrap();
+    assert!(String::from_utf8(export).unwrap().ends_with("[HKEY_LOCAL_MACHINE\\TEST\\Key]\r\n\r\n"));
+}
+
+#[test]
+fn zero_count_with_a_list_means_no_values() {
+    assert_no_values(hive_with_value_list(0, None));
+}
+
+#[test]
+fn zero_count_without_a_list_means_no_values() {
+    assert_no_values(hive_with_value_list(0, Some(0xFFFFFFFF)));
+}
+
+#[test]
+fn nonzero_count_without_a_list_is_inconsistent() {
+    let mut hive = hive_with_value_list(2, Some(0xFFFFFFFF));
+    let key_node = hive.open_key("Key").unwrap();
+
+    let values: Vec<_> = hive.values(&key_node).collect();
+    assert!(matches!(values[..], [Err(HiveError::InconsistentValueList { number_of_key_values: 2 })]));
+    assert!(matches!(
+        hive.value("Key", "A"),
+        Err(HiveError::InconsistentValueList { number_of_key_values: 2 })
+    ));
+
+    let mut export = Vec::new();
+    assert!(matches!(
+        hive.export_reg(&mut export, "HKEY_LOCAL_MACHINE\\TEST", RegFormat::RegEdit4),
+        Err(HiveError::InconsistentValueList { number_of_key_values: 2 })
+    ));
+}
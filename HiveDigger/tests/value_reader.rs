@@ -0,0 +1,41 @@
+mod common;
+
+use std::io::{Cursor, Read};
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::Hive;
+
+#[test]
+fn streamed_data_equals_value_data() {
+    let big: Vec<u8> = (0..40000u32).map(|position| (position % 241) as u8).collect();
+    let root = Key::new("ROOT")
+        .binary("Big", &big)
+        .sz("External", "C:\\Windows\\System32")
+        .dword("Resident", 0x1234_5678);
+    let mut hive = Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap();
+    let root = hive.root().unwrap();
+    let values = hive.values(&root).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(values.len(), 3);
+
+    for (name, key_value) in &values {
+        let data = hive.value_data(key_value).unwrap();
+
+        let mut streamed = Vec::new();
+        hive.value_reader(key_value).unwrap().read_to_end(&mut streamed).unwrap();
+        assert_eq!(streamed, data, "{}", name);
+
+        // Small reads that straddle the big data segment boundaries come out the same
+        let mut reader = hive.value_reader(key_value).unwrap();
+        let mut chunked = Vec::new();
+        let mut buffer = [0u8; 1000];
+        loop {
+            let read = reader.read(&mut buffer).unwrap();
+            if read == 0 {
+                break;
+            }
+            chunked.extend_from_slice(&buffer[..read]);
+        }
+        assert_eq!(chunked, data, "{}", name);
+    }
+    assert_eq!(hive.value_data(&values[0].1).unwrap(), big);
+}
@@ -0,0 +1,20 @@
+mod common;
+
+use common::fixtures::fixture_path;
+use hivedigger::{Hive, RegistryValue};
+
+#[test]
+fn values_map_decodes_every_value_of_a_key() {
+    let mut hive = Hive::open(&fixture_path("SOFTWARE")).unwrap();
+    let key_node = hive.open_key("Microsoft\\Windows NT\\CurrentVersion").unwrap();
+    let values = hive.values_map(&key_node).unwrap();
+
+    let names: Vec<&str> = values.keys().map(String::as_str).collect();
+    assert_eq!(
+        names,
+        ["CurrentBuild", "DisplayVersion", "EditionID", "InstallDate", "InstallTime", "ProductName", "RegisteredOwner"]
+    );
+    assert_eq!(values["ProductName"], RegistryValue::String("Windows 10 Pro".to_string()));
+    assert_eq!(values["InstallDate"], RegistryValue::Dword(1555526400));
+    assert_eq!(values["InstallTime"], RegistryValue::Qword(132000000000000000));
+}
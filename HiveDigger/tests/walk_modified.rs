@@ -0,0 +1,46 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::builder::{HiveBuilder, Key};
+use hivedigger::{time::TimeRange, Hive};
+
+// FILETIMEs of 2024-03-01, 2024-03-02 and 2024-03-03 at midnight UTC
+const MARCH_1: u64 = 133537248000000000;
+const MARCH_2: u64 = 133538112000000000;
+const MARCH_3: u64 = 133538976000000000;
+
+fn hive() -> Hive<Cursor<Vec<u8>>> {
+    let root = Key::new("ROOT")
+        .timestamp(MARCH_1)
+        .subkey(Key::new("Before").timestamp(MARCH_1 - 1))
+        .subkey(Key::new("Start").timestamp(MARCH_1))
+        .subkey(
+            Key::new("Old")
+                .timestamp(MARCH_1 - 1)
+                .subkey(Key::new("Inside").timestamp(MARCH_2)),
+        )
+        .subkey(Key::new("End").timestamp(MARCH_3));
+    Hive::from_reader(Cursor::new(HiveBuilder::new().build(&root))).unwrap()
+}
+
+#[test]
+fn keys_outside_the_window_are_left_out() {
+    let range = TimeRange::parse(Some("2024-03-01T00:00:00Z"), Some("2024-03-03T00:00:00Z")).unwrap();
+    let mut hive = hive();
+    let paths: Vec<String> = hive.walk_modified(range).map(|key| key.unwrap().0).collect();
+
+    // `since` is inclusive, `until` exclusive, and a key older than the window is still walked
+    assert_eq!(paths, ["", "Start", "Old\\Inside"]);
+}
+
+#[test]
+fn open_ended_ranges() {
+    let mut hive = hive();
+    let since = TimeRange::parse(Some("2024-03-02T00:00:00+00:00"), None).unwrap();
+    let paths: Vec<String> = hive.walk_modified(since).map(|key| key.unwrap().0).collect();
+    assert_eq!(paths, ["Old\\Inside", "End"]);
+
+    assert_eq!(hive.walk_modified(TimeRange::default()).count(), hive.walk().count());
+    assert!(TimeRange::parse(Some("yesterday"), None).is_err());
+}